@@ -8,7 +8,6 @@ use crate::claude::runner::{Claude, ClaudeMetadata, SessionMode};
 use crate::config::Config;
 use crate::error::Result;
 use crate::intent::registry::Intent;
-use crate::prompt;
 
 /// Summary of another active intent, passed to Analyze Agent for dependency detection.
 #[derive(Debug, Clone)]
@@ -51,6 +50,20 @@ pub struct TaskSpec {
   pub context: String,
   #[serde(default)]
   pub depends_on: Vec<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub checks: Option<CheckOverride>,
+}
+
+/// Per-task override of where and how verification commands run,
+/// e.g. only the affected package in a workspace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CheckOverride {
+  /// Subdirectory of the worktree to run checks in.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub dir: Option<String>,
+  /// Replaces the repo's `lint_command` for this task.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub lint_command: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +103,8 @@ struct RawAnalysis {
   depends_on_intents: Vec<String>,
   #[serde(default)]
   observations: Vec<String>,
+  #[serde(default)]
+  checks: Option<CheckOverride>,
 }
 
 fn default_outcome() -> String {
@@ -115,6 +130,7 @@ impl From<RawAnalysis> for AnalysisOutcome {
             implementation_steps: raw.implementation_steps,
             context: raw.context,
             depends_on: vec![],
+            checks: raw.checks,
           }])
         } else {
           AnalysisOutcome::Tasks(raw.tasks)
@@ -124,6 +140,22 @@ impl From<RawAnalysis> for AnalysisOutcome {
   }
 }
 
+/// The analyze system prompt (built-in or `.forge/prompts/analyze.md`) with the
+/// memory server name filled in.
+pub fn system_prompt(config: &Config) -> String {
+  let mut prompt = format!(
+    "{}\n\nThe external memory MCP server name is `{}`. Use tools like `mcp__{}__search_memories` and `mcp__{}__create_memory`.",
+    config.prompts.get("analyze"), config.memory_server, config.memory_server, config.memory_server
+  );
+  if !config.lint_commands.is_empty() {
+    prompt.push_str("\n\n## Allowed lint commands\n\n");
+    for command in &config.lint_commands {
+      prompt.push_str(&format!("- `{command}`\n"));
+    }
+  }
+  prompt
+}
+
 pub fn analyze(
   intent: &Intent,
   config: &Config,
@@ -143,13 +175,10 @@ pub fn analyze(
 
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
-  let system_prompt = format!(
-    "{}\n\nThe external memory MCP server name is `{}`. Use tools like `mcp__{}__search_memories` and `mcp__{}__create_memory`.",
-    prompt::ANALYZE, config.memory_server, config.memory_server, config.memory_server
-  );
+  let system_prompt = system_prompt(config);
 
   info!("analyzing: {intent}");
-  let (raw, metadata): (RawAnalysis, _) = runner.run_json_with_meta(
+  let (raw, metadata): (RawAnalysis, _) = runner.in_stage("analyze").run_json_with_meta(
     &prompt,
     &system_prompt,
     deep_model,
@@ -47,7 +47,7 @@ pub fn audit(
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
   info!("auditing: {}", target_path.unwrap_or("."));
-  let (result, metadata): (AuditResult, _) = runner.run_json_with_meta(
+  let (result, metadata): (AuditResult, _) = runner.in_stage("audit").run_json_with_meta(
     &prompt,
     prompt::AUDIT,
     audit_model,
@@ -0,0 +1,57 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::claude::model;
+use crate::claude::runner::{Claude, ClaudeMetadata, SessionMode};
+use crate::config::Config;
+use crate::error::Result;
+use crate::intent::registry::Intent;
+use crate::prompt;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectFix {
+  pub original: String,
+  pub fixed: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectFixResult {
+  pub subjects: Vec<SubjectFix>,
+}
+
+/// Ask for replacement subjects for commits that don't match `commit_pattern`.
+/// The Runner applies the result; the agent does not touch git.
+pub fn fix_subjects(
+  intent: &Intent,
+  config: &Config,
+  runner: &impl Claude,
+  worktree_path: &Path,
+  pattern: &str,
+  subjects: &[String],
+) -> Result<(SubjectFixResult, ClaudeMetadata)> {
+  let fix_model = model::resolve(&config.models.implement);
+
+  let mut prompt = format!(
+    "## Intent: {title}\n\n{body}\n\n## Required pattern\n\n`{pattern}`\n\n## Subjects to fix\n\n",
+    title = intent.title,
+    body = intent.body,
+  );
+  for subject in subjects {
+    prompt.push_str(&format!("- {subject}\n"));
+  }
+
+  let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
+
+  info!("fixing {} commit subject(s) for {intent}", subjects.len());
+  runner.in_stage("commit_message").run_json_with_meta(
+    &prompt,
+    prompt::COMMIT_MESSAGE,
+    fix_model,
+    worktree_path,
+    timeout,
+    &SessionMode::None,
+  )
+}
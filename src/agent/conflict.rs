@@ -0,0 +1,121 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tracing::info;
+
+use crate::claude::model;
+use crate::claude::runner::{Claude, SessionMode};
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::intent::registry::Intent;
+use crate::prompt;
+use crate::task::Task;
+
+/// Upper bound on the conflict hunks included in the prompt.
+const MAX_HUNK_CHARS: usize = 30_000;
+
+/// The `<<<<<<<` … `>>>>>>>` regions of a conflicted file, separated by blank lines.
+pub fn conflict_hunks(content: &str) -> String {
+  let mut hunks = Vec::new();
+  let mut current: Option<Vec<&str>> = None;
+  for line in content.lines() {
+    if line.starts_with("<<<<<<<") {
+      current = Some(vec![line]);
+    } else if let Some(hunk) = current.as_mut() {
+      hunk.push(line);
+      if line.starts_with(">>>>>>>") {
+        hunks.push(current.take().unwrap().join("\n"));
+      }
+    }
+  }
+  hunks.join("\n\n")
+}
+
+/// Resolve the conflicts of a stopped rebase in place. The agent edits and
+/// stages the conflicted files; the caller checks the result and continues the rebase.
+pub fn resolve(
+  intent: &Intent,
+  task: &Task,
+  config: &Config,
+  runner: &impl Claude,
+  worktree_path: &Path,
+  conflicts: &[String],
+  timeout: Option<Duration>,
+) -> Result<String> {
+  let (base_side, task_side) = git::branch::rebase_sides(worktree_path)?;
+
+  let mut hunks = String::new();
+  for file in conflicts {
+    let content = std::fs::read_to_string(worktree_path.join(file)).unwrap_or_default();
+    hunks.push_str(&format!(
+      "### {file}\n\n```\n{}\n```\n\n",
+      conflict_hunks(&content)
+    ));
+  }
+  if hunks.len() > MAX_HUNK_CHARS {
+    let mut end = MAX_HUNK_CHARS;
+    while !hunks.is_char_boundary(end) {
+      end -= 1;
+    }
+    hunks.truncate(end);
+    hunks.push_str("\n\n... (truncated; read the files for the remaining hunks)");
+  }
+
+  let prompt = format!(
+    "## Intent: {title}\n\n{body}\n\n## Task: {task_title}\n\n**Plan:**\n{plan}\n\n\
+     ## Base Branch Commits (`{base}`)\n\n{base_side}\n\n\
+     ## Task Commit Being Replayed\n\n{task_side}\n\n\
+     ## Conflicting Hunks\n\n{hunks}",
+    title = intent.title,
+    body = intent.body,
+    task_title = task.title,
+    plan = task.plan,
+    base = config.base_branch,
+    hunks = hunks.trim_end(),
+  );
+
+  info!(
+    "resolving rebase conflicts for {intent}: {}",
+    conflicts.join(", ")
+  );
+  runner.in_stage("conflict").run_prompt(
+    &prompt,
+    prompt::CONFLICT,
+    model::resolve(&config.models.conflict),
+    worktree_path,
+    timeout,
+    &SessionMode::None,
+  )
+}
+
+/// Stage the conflicted paths that no longer contain conflict markers, for
+/// sandboxed agents that cannot write the index themselves.
+pub fn stage_resolved(worktree_path: &Path, conflicts: &[String]) {
+  for file in conflicts {
+    let clean = std::fs::read_to_string(worktree_path.join(file))
+      .is_ok_and(|content| conflict_hunks(&content).is_empty());
+    if !clean {
+      continue;
+    }
+    let added = crate::process::command("git")
+      .args(["add", "--", file])
+      .current_dir(worktree_path)
+      .output();
+    if !matches!(added, Ok(ref o) if o.status.success()) {
+      tracing::warn!("failed to stage resolved file {file}");
+    }
+  }
+}
+
+/// Whether every conflicted path is staged and free of conflict markers.
+pub fn is_resolved(worktree_path: &Path, conflicts: &[String]) -> bool {
+  if !git::branch::conflicted_files(worktree_path).is_ok_and(|f| f.is_empty()) {
+    return false;
+  }
+  conflicts.iter().all(|file| {
+    std::fs::read_to_string(worktree_path.join(file))
+      .map(|content| conflict_hunks(&content).is_empty())
+      .unwrap_or(true)
+  })
+}
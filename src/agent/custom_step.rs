@@ -0,0 +1,48 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tracing::info;
+
+use crate::claude::model;
+use crate::claude::runner::{Claude, SessionMode};
+use crate::config::{Config, CustomStep};
+use crate::error::Result;
+use crate::intent::registry::Intent;
+use crate::task::Task;
+
+/// Run a user-defined flow step (`steps.<name>` in config) on the task's worktree.
+/// The step's `prompt` is the system prompt; it may commit changes like Implement.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+  name: &str,
+  step: &CustomStep,
+  intent: &Intent,
+  task: &Task,
+  config: &Config,
+  runner: &impl Claude,
+  worktree_path: &Path,
+  timeout: Option<Duration>,
+) -> Result<String> {
+  let step_model = model::resolve(step.model.as_deref().unwrap_or(&config.models.implement));
+
+  let prompt = format!(
+    "## Intent: {title}\n\n{body}\n\n## Task: {task_title}\n\n**Plan:**\n{plan}\n\n\
+     The implementation of this task is committed on the current branch \
+     (compare with `{base}`). Carry out the `{name}` step and commit any changes you make.",
+    title = intent.title,
+    body = intent.body,
+    task_title = task.title,
+    plan = task.plan,
+    base = config.base_branch,
+  );
+
+  info!("running step {name}: {intent}");
+  runner.in_stage(name).run_prompt(
+    &prompt,
+    &step.prompt,
+    step_model,
+    worktree_path,
+    timeout,
+    &SessionMode::None,
+  )
+}
@@ -4,19 +4,44 @@ use std::time::Duration;
 use tracing::info;
 
 use crate::agent::review::ReviewResult;
-use crate::claude::runner::{Claude, SessionMode};
+use crate::claude::runner::{Claude, ClaudeResponse, SessionMode};
+use crate::intent::criteria;
 use crate::intent::registry::Intent;
-use crate::prompt;
 use crate::task::Task;
 
+/// Feedback from a previous attempt, injected into the retry prompt.
+#[derive(Debug, Clone, Copy)]
+pub enum Feedback<'a> {
+  Review(&'a ReviewResult),
+  Lint(&'a str),
+  Coverage {
+    coverage: f64,
+    min_coverage: f64,
+    output: &'a str,
+  },
+}
+
+/// Heading of the per-issue notes the agent ends with after a review rejection.
+pub const REMEDIATION_HEADING: &str = "## Remediation";
+
+/// The agent's `## Remediation` notes from a raw Implement result, if present.
+pub fn remediation_notes(raw: &str) -> Option<String> {
+  let text = ClaudeResponse::parse(raw).ok()?.result;
+  let start = text.find(REMEDIATION_HEADING)? + REMEDIATION_HEADING.len();
+  let notes = text[start..].trim();
+  (!notes.is_empty()).then(|| notes.to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run(
   intent: &Intent,
   task: &Task,
   runner: &impl Claude,
+  system_prompt: &str,
   selected_model: &str,
   worktree_path: &Path,
   timeout: Option<Duration>,
-  review_feedback: Option<&ReviewResult>,
+  feedback: Option<Feedback>,
   session: &SessionMode,
 ) -> Result<String, crate::error::ForgeError> {
   let mut prompt = format!(
@@ -49,6 +74,8 @@ pub fn run(
     prompt.push_str(&format!("\n\n**Context:**\n{}", task.context));
   }
 
+  prompt.push_str(&criteria::parse(&intent.body).render());
+
   // Include clarifications if present
   if !intent.clarifications.is_empty() {
     let answered: Vec<_> = intent
@@ -64,13 +91,18 @@ pub fn run(
     }
   }
 
-  if let Some(review) = review_feedback {
+  if let Some(Feedback::Review(review)) = feedback {
     prompt.push_str("\n\n## Previous Review Feedback\n\nThe previous implementation was rejected. Address the following:\n");
     if !review.issues.is_empty() {
       prompt.push_str("\n### Issues\n");
-      for issue in &review.issues {
-        prompt.push_str(&format!("- {issue}\n"));
+      for (i, issue) in review.issues.iter().enumerate() {
+        prompt.push_str(&format!("{}. {issue}\n", i + 1));
       }
+      prompt.push_str(&format!(
+        "\nEnd your final response with a `{REMEDIATION_HEADING}` section that lists each issue \
+         number and what you changed for it, or why you left it as is. The reviewer checks \
+         your changes against it point by point.\n"
+      ));
     }
     if !review.suggestions.is_empty() {
       prompt.push_str("\n### Suggestions\n");
@@ -80,10 +112,27 @@ pub fn run(
     }
   }
 
+  if let Some(Feedback::Lint(output)) = feedback {
+    prompt.push_str(&format!(
+      "\n\n## Lint Failure\n\nThe lint command failed on your changes. Fix every reported problem and commit the fix:\n\n```\n{output}\n```\n"
+    ));
+  }
+
+  if let Some(Feedback::Coverage {
+    coverage,
+    min_coverage,
+    output,
+  }) = feedback
+  {
+    prompt.push_str(&format!(
+      "\n\n## Coverage Failure\n\nTest coverage is {coverage:.1}%, below the required {min_coverage:.1}%. Add tests for the code you changed until coverage meets the threshold, and commit them:\n\n```\n{output}\n```\n"
+    ));
+  }
+
   info!("implementing: {intent}");
-  runner.run_prompt(
+  runner.in_stage("implement").run_prompt(
     &prompt,
-    prompt::IMPLEMENT,
+    system_prompt,
     selected_model,
     worktree_path,
     timeout,
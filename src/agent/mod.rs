@@ -1,5 +1,8 @@
 pub mod analyze;
 pub mod audit;
+pub mod commit_message;
+pub mod conflict;
+pub mod custom_step;
 pub mod implement;
 pub mod operator;
 pub mod reflect;
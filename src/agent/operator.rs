@@ -1,17 +1,33 @@
 use std::path::Path;
+use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::claude::model;
+use crate::claude::runner::{Claude, ClaudeMetadata, SessionMode};
 use crate::config::Config;
 use crate::error::Result;
 use crate::intent::registry::{Intent, IntentStatus};
+use crate::knowledge::history::{self, HistoryEntry, Outcome};
+use crate::knowledge::run_report::{self, RunReport};
+use crate::mcp;
+use crate::process;
 use crate::prompt;
 
-pub fn launch(_config: &Config, model: Option<&str>, repo_path: &Path) -> Result<()> {
-  let mut cmd = std::process::Command::new("claude");
+pub fn launch(config: &Config, model: Option<&str>, repo_path: &Path) -> Result<()> {
+  let mut cmd = process::command(&config.claude.bin);
   cmd
     .arg("--append-system-prompt")
     .arg(prompt::OPERATOR)
     .arg("--allowedTools")
-    .arg("Bash");
+    .arg(format!("Bash,mcp__{}", mcp::SERVER_NAME))
+    .arg("--mcp-config")
+    .arg(mcp_config(&std::env::current_exe()?))
+    .args(&config.claude.extra_args);
+  if let Some(options) = config.claude.stages.get("operator") {
+    cmd.args(options.args());
+  }
 
   if let Some(m) = model {
     cmd.arg("--model").arg(m);
@@ -27,11 +43,55 @@ pub fn launch(_config: &Config, model: Option<&str>, repo_path: &Path) -> Result
   )))
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorReport {
+  pub report: String,
+}
+
+/// Non-interactive alternative to `launch`: one Claude call that turns the
+/// briefing into a prose status report (shipped / blocked / needs humans).
+pub fn report(
+  config: &Config,
+  runner: &impl Claude,
+  model: Option<&str>,
+  repo_path: &Path,
+) -> Result<(OperatorReport, ClaudeMetadata)> {
+  let report_model = model::resolve(model.unwrap_or(&config.models.reflect));
+  let prompt = build_initial_message(repo_path);
+  let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
+
+  info!("writing operator report");
+  runner.in_stage("operator").run_json_with_meta(
+    &prompt,
+    prompt::OPERATOR_REPORT,
+    report_model,
+    repo_path,
+    timeout,
+    &SessionMode::None,
+  )
+}
+
+/// Inline `--mcp-config` that registers `pfl-forge mcp` (this binary) as a stdio server.
+pub fn mcp_config(forge_bin: &Path) -> String {
+  serde_json::json!({
+    "mcpServers": {
+      mcp::SERVER_NAME: {
+        "command": forge_bin,
+        "args": ["mcp"],
+      }
+    }
+  })
+  .to_string()
+}
+
 pub fn build_initial_message(repo_path: &Path) -> String {
   let intents_dir = repo_path.join(".forge").join("intents");
   let intents = Intent::fetch_all(&intents_dir).unwrap_or_default();
 
-  if intents.is_empty() {
+  let last_run = run_report::load(repo_path).ok().flatten();
+  let entries = history::load_all(repo_path).unwrap_or_default();
+
+  if intents.is_empty() && last_run.is_none() && entries.is_empty() {
     return "pfl-forge is ready. No intents found.".to_string();
   }
 
@@ -43,6 +103,8 @@ pub fn build_initial_message(repo_path: &Path) -> String {
   let mut done = 0usize;
   let mut blocked = 0usize;
   let mut error = 0usize;
+  let mut rejected = 0usize;
+  let mut setup_failure = 0usize;
 
   for i in &intents {
     match i.status {
@@ -51,17 +113,21 @@ pub fn build_initial_message(repo_path: &Path) -> String {
       IntentStatus::Done => done += 1,
       IntentStatus::Blocked => blocked += 1,
       IntentStatus::Error => error += 1,
+      IntentStatus::Rejected => rejected += 1,
+      IntentStatus::SetupFailure => setup_failure += 1,
     }
   }
 
   msg.push_str(&format!(
-    "Total: {} intents (proposed: {}, approved: {}, done: {}, blocked: {}, error: {})\n",
+    "Total: {} intents (proposed: {}, approved: {}, done: {}, blocked: {}, error: {}, rejected: {}, setup failure: {})\n",
     intents.len(),
     proposed,
     approved,
     done,
     blocked,
     error,
+    rejected,
+    setup_failure,
   ));
 
   // Inbox: proposed, blocked, error, needs_clarification
@@ -81,6 +147,8 @@ pub fn build_initial_message(repo_path: &Path) -> String {
       let status = format!("{:?}", i.status).to_lowercase();
       let clarification = if i.needs_clarification() {
         " [needs clarification]"
+      } else if i.awaiting_plan_approval() {
+        " [plan review]"
       } else {
         ""
       };
@@ -99,5 +167,152 @@ pub fn build_initial_message(repo_path: &Path) -> String {
     }
   }
 
+  if let Some(report) = last_run {
+    msg.push_str(&format_last_run(&report));
+  }
+
+  msg.push_str(&format_recent_failures(&entries));
+  msg.push_str(&format_cost_today(&entries));
+  msg.push_str(&format_next_actions(&intents));
+
   msg
 }
+
+/// How many failed intents the briefing lists, newest first.
+const RECENT_FAILURES: usize = 5;
+/// Failure reasons longer than this are cut so the briefing stays short.
+const FAILURE_EXCERPT_CHARS: usize = 300;
+
+fn format_recent_failures(entries: &[HistoryEntry]) -> String {
+  let mut failures: Vec<&HistoryEntry> = entries
+    .iter()
+    .filter(|e| e.outcome != Outcome::Success)
+    .collect();
+  if failures.is_empty() {
+    return String::new();
+  }
+  // RFC 3339 timestamps sort chronologically as strings
+  failures.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+  let mut out = String::from("\n## Recent Failures\n\n");
+  for entry in failures.iter().take(RECENT_FAILURES) {
+    let outcome = format!("{:?}", entry.outcome).to_lowercase();
+    let at = entry.created_at.as_deref().unwrap_or("unknown time");
+    out.push_str(&format!(
+      "- **{}** ({outcome}, {at}) — {}\n",
+      entry.intent_id, entry.title
+    ));
+    if let Some(reason) = &entry.failure_reason {
+      out.push_str(&format!("  - Error: {}\n", excerpt(reason)));
+    }
+  }
+  out
+}
+
+fn excerpt(text: &str) -> String {
+  let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+  if flat.chars().count() <= FAILURE_EXCERPT_CHARS {
+    return flat;
+  }
+  let cut: String = flat.chars().take(FAILURE_EXCERPT_CHARS).collect();
+  format!("{cut}…")
+}
+
+fn format_cost_today(entries: &[HistoryEntry]) -> String {
+  let today = chrono::Utc::now().date_naive();
+  let today_entries: Vec<&HistoryEntry> = entries
+    .iter()
+    .filter(|e| {
+      e.created_at
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .is_some_and(|t| t.with_timezone(&chrono::Utc).date_naive() == today)
+    })
+    .collect();
+  if today_entries.is_empty() {
+    return String::new();
+  }
+  let cost = today_entries
+    .iter()
+    .fold(0.0, |total, e| total + e.cost_usd());
+  format!(
+    "\n## Cost Today\n\n${cost:.2} across {} intent(s) finished today (UTC)\n",
+    today_entries.len()
+  )
+}
+
+fn format_next_actions(intents: &[Intent]) -> String {
+  let count = |pred: &dyn Fn(&Intent) -> bool| intents.iter().filter(|i| pred(i)).count();
+  let clarifications = count(&|i| i.needs_clarification());
+  let proposed = count(&|i| i.status == IntentStatus::Proposed);
+  let plans = count(&|i| i.awaiting_plan_approval());
+  let errors = count(&|i| {
+    matches!(i.status, IntentStatus::Error | IntentStatus::Blocked)
+      && !i.needs_clarification()
+      && !i.awaiting_plan_approval()
+  });
+  let approved = count(&|i| i.status == IntentStatus::Approved);
+
+  let mut actions = Vec::new();
+  if clarifications > 0 {
+    actions.push(format!(
+      "Ask the user the open clarification questions ({clarifications} intent(s)) and record the answers"
+    ));
+  }
+  if proposed > 0 {
+    actions.push(format!(
+      "Review {proposed} proposed intent(s) with the user and approve the ones to run"
+    ));
+  }
+  if plans > 0 {
+    actions.push(format!(
+      "Walk the user through {plans} plan(s) awaiting review (.forge/tasks/<id>.yaml) and approve the accepted ones"
+    ));
+  }
+  if errors > 0 {
+    actions.push(format!(
+      "Investigate {errors} failed intent(s) (see Recent Failures) before re-approving"
+    ));
+  }
+  if approved > 0 {
+    actions.push(format!(
+      "Start a run for {approved} approved intent(s) when the user is ready"
+    ));
+  }
+  if actions.is_empty() {
+    return String::new();
+  }
+
+  let mut out = String::from("\n## Suggested Next Actions\n\n");
+  for (i, action) in actions.iter().enumerate() {
+    out.push_str(&format!("{}. {action}\n", i + 1));
+  }
+  out
+}
+
+fn format_last_run(report: &RunReport) -> String {
+  let mut out = format!(
+    "\n## Last Run\n\nFinished: {} — {} intent(s), total cost ${:.2}\n\n",
+    report.finished_at,
+    report.intents.len(),
+    report.total_cost_usd(),
+  );
+  if report.intents.is_empty() {
+    out.push_str("No approved intents were processed.\n");
+    return out;
+  }
+  for entry in &report.intents {
+    let outcome = format!("{:?}", entry.outcome).to_lowercase();
+    out.push_str(&format!(
+      "- **{}** ({}, ${:.2}) — {}\n",
+      entry.intent_id, outcome, entry.cost_usd, entry.title
+    ));
+    if let Some(reason) = &entry.failure_reason {
+      out.push_str(&format!("  - Reason: {reason}\n"));
+    }
+    if let Some(action) = &entry.next_action {
+      out.push_str(&format!("  - Next: {action}\n"));
+    }
+  }
+  out
+}
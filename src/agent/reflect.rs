@@ -25,6 +25,25 @@ pub struct ReflectIntent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReflectResult {
   pub intents: Vec<ReflectIntent>,
+  /// Lessons for the repo's agent guidance file (build quirks, conventions,
+  /// flaky tests). Collected into one separate guidance-update intent.
+  #[serde(default)]
+  pub guidance: Vec<String>,
+}
+
+/// Agent guidance files, in order of preference when both exist.
+const GUIDANCE_FILES: &[&str] = &["CLAUDE.md", "AGENTS.md"];
+
+/// Cap on how much of the current guidance file is shown to Reflect.
+const GUIDANCE_EXCERPT_CHARS: usize = 8000;
+
+/// The guidance file guidance-update intents target: the first of
+/// `CLAUDE.md` / `AGENTS.md` that exists, else `CLAUDE.md`.
+pub fn guidance_file(repo_path: &Path) -> &'static str {
+  GUIDANCE_FILES
+    .iter()
+    .find(|f| repo_path.join(f).is_file())
+    .unwrap_or(&GUIDANCE_FILES[0])
 }
 
 /// Run Reflect Agent on unprocessed observations for the given intent.
@@ -42,7 +61,13 @@ pub fn reflect(
 
   if unprocessed.is_empty() {
     info!("reflect: no unprocessed observations");
-    return Ok((ReflectResult { intents: vec![] }, ClaudeMetadata::default()));
+    return Ok((
+      ReflectResult {
+        intents: vec![],
+        guidance: vec![],
+      },
+      ClaudeMetadata::default(),
+    ));
   }
 
   let reflect_model = model::resolve(&config.models.reflect);
@@ -50,7 +75,7 @@ pub fn reflect(
   let mut prompt = format!("## Intent: {title}\n\n", title = intent.title);
 
   // Include execution summary if available
-  if let Ok(exec_summary) = summary::load(repo_path, &intent.id()) {
+  if let Ok(exec_summary) = summary::load(repo_path, intent.id()) {
     prompt.push_str("## Execution Summary\n\n");
     if let Some(ref analyze) = exec_summary.analyze {
       prompt.push_str(&format!(
@@ -81,6 +106,20 @@ pub fn reflect(
     }
   }
 
+  let guidance_path = guidance_file(repo_path);
+  prompt.push_str(&format!("## Current Agent Guidance ({guidance_path})\n\n"));
+  match std::fs::read_to_string(repo_path.join(guidance_path)) {
+    Ok(content) if !content.trim().is_empty() => {
+      let excerpt: String = content.chars().take(GUIDANCE_EXCERPT_CHARS).collect();
+      prompt.push_str(&excerpt);
+      if excerpt.len() < content.len() {
+        prompt.push_str("\n…");
+      }
+      prompt.push_str("\n\n");
+    }
+    _ => prompt.push_str("(none)\n\n"),
+  }
+
   prompt.push_str("## Unprocessed Observations\n\n");
   for obs in &unprocessed {
     prompt.push_str(&format!("- {}\n", obs.content));
@@ -89,7 +128,7 @@ pub fn reflect(
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
   info!("reflecting on {} observations", unprocessed.len());
-  let (result, metadata): (ReflectResult, _) = runner.run_json_with_meta(
+  let (result, metadata): (ReflectResult, _) = runner.in_stage("reflect").run_json_with_meta(
     &prompt,
     prompt::REFLECT,
     reflect_model,
@@ -114,14 +153,51 @@ pub fn reflect(
     let content = serde_yaml::to_string(&intent_yaml)?;
     std::fs::write(intents_dir.join(format!("{id}.yaml")), content)?;
   }
+  if !result.guidance.is_empty() {
+    write_guidance_intent(&intents_dir, intent, guidance_path, &result.guidance)?;
+  }
 
   // Mark observations as processed
-  observation::mark_processed(&obs_path, &intent.id(), metadata.session_id.as_deref())?;
+  observation::mark_processed(&obs_path, intent.id(), metadata.session_id.as_deref())?;
 
   info!("reflect: generated {} intents", result.intents.len());
   Ok((result, metadata))
 }
 
+/// Write one small intent that edits only the guidance file, so the lessons
+/// land on their own branch instead of being mixed into follow-up work.
+fn write_guidance_intent(
+  intents_dir: &Path,
+  source: &Intent,
+  guidance_path: &str,
+  lessons: &[String],
+) -> Result<()> {
+  let mut body = format!(
+    "Update `{guidance_path}` with what was learned while working on `{id}`, \
+     so future workers on this repo benefit from it. Edit only `{guidance_path}`; \
+     merge with existing entries instead of duplicating them.\n\n",
+    id = source.id(),
+  );
+  for lesson in lessons {
+    body.push_str(&format!("- {lesson}\n"));
+  }
+  let intent_yaml = GeneratedIntent {
+    title: format!("Update {guidance_path} from {}", source.id()),
+    body,
+    intent_type: None,
+    source: "reflection".to_string(),
+    risk: Some("medium".to_string()),
+    created_at: Some(chrono::Utc::now().to_rfc3339()),
+  };
+  let id = format!("update-agent-guidance-{}", source.id());
+  std::fs::write(
+    intents_dir.join(format!("{id}.yaml")),
+    serde_yaml::to_string(&intent_yaml)?,
+  )?;
+  info!("reflect: proposed {guidance_path} update as {id}");
+  Ok(())
+}
+
 #[derive(Serialize)]
 struct GeneratedIntent {
   title: String,
@@ -1,5 +1,4 @@
 use std::path::Path;
-use std::process::Command;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -9,8 +8,9 @@ use crate::claude::model;
 use crate::claude::runner::{Claude, ClaudeMetadata, SessionMode};
 use crate::config::Config;
 use crate::error::{ForgeError, Result};
+use crate::intent::criteria;
 use crate::intent::registry::Intent;
-use crate::prompt;
+use crate::process;
 use crate::task::Task;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +27,37 @@ pub struct ReviewResult {
   pub session_id: Option<String>,
 }
 
+/// What a follow-up review needs to check a retry against the rejection.
+pub struct FollowUp<'a> {
+  pub previous: &'a ReviewResult,
+  /// `git diff` from the rejected attempt's HEAD to the current HEAD.
+  pub changes: Option<&'a str>,
+  /// The Implement agent's per-issue `## Remediation` notes.
+  pub remediation: Option<&'a str>,
+}
+
+impl FollowUp<'_> {
+  fn render(&self) -> String {
+    let mut out = String::from(
+      "\n\n## Previous Review\n\nThe previous attempt was rejected for these issues:\n\n",
+    );
+    for (i, issue) in self.previous.issues.iter().enumerate() {
+      out.push_str(&format!("{}. {issue}\n", i + 1));
+    }
+    if let Some(changes) = self.changes {
+      out.push_str(&format!(
+        "\n## Changes Since Previous Attempt\n\n```\n{}\n```\n",
+        truncate_diff(changes, 20000)
+      ));
+    }
+    if let Some(notes) = self.remediation {
+      out.push_str(&format!("\n## Remediation Notes\n\n{notes}\n"));
+    }
+    out
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn review(
   intent: &Intent,
   task: &Task,
@@ -34,6 +65,7 @@ pub fn review(
   runner: &impl Claude,
   worktree_path: &Path,
   base_branch: &str,
+  bench_report: Option<&str>,
   session: &SessionMode,
 ) -> Result<(ReviewResult, ClaudeMetadata)> {
   review_inner(
@@ -44,6 +76,36 @@ pub fn review(
     worktree_path,
     base_branch,
     None,
+    bench_report,
+    None,
+    session,
+  )
+}
+
+/// Review a retry after a rejection, checking each previous issue against the
+/// changes made since the rejected attempt.
+#[allow(clippy::too_many_arguments)]
+pub fn re_review(
+  intent: &Intent,
+  task: &Task,
+  config: &Config,
+  runner: &impl Claude,
+  worktree_path: &Path,
+  base_branch: &str,
+  bench_report: Option<&str>,
+  follow_up: &FollowUp,
+  session: &SessionMode,
+) -> Result<(ReviewResult, ClaudeMetadata)> {
+  review_inner(
+    intent,
+    task,
+    config,
+    runner,
+    worktree_path,
+    base_branch,
+    None,
+    bench_report,
+    Some(follow_up),
     session,
   )
 }
@@ -65,10 +127,13 @@ pub fn review_with_diff(
     worktree_path,
     "",
     Some(diff_override),
+    None,
+    None,
     session,
   )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn review_inner(
   intent: &Intent,
   task: &Task,
@@ -77,6 +142,8 @@ fn review_inner(
   worktree_path: &Path,
   base_branch: &str,
   diff_override: Option<&str>,
+  bench_report: Option<&str>,
+  follow_up: Option<&FollowUp>,
   session: &SessionMode,
 ) -> Result<(ReviewResult, ClaudeMetadata)> {
   let review_model = model::resolve(&config.models.review);
@@ -86,10 +153,10 @@ fn review_inner(
     None => get_diff(worktree_path, base_branch)?,
   };
 
-  let prompt = format!(
+  let mut prompt = format!(
     r#"## Task {id}: {title}
 
-{body}
+{body}{criteria}
 
 ## Implementation Plan
 
@@ -103,16 +170,24 @@ fn review_inner(
     id = intent.id(),
     title = intent.title,
     body = intent.body,
+    criteria = criteria::parse(&intent.body).render(),
     plan = task.plan,
     diff = truncate_diff(&diff, 50000),
   );
 
+  if let Some(report) = bench_report {
+    prompt.push_str(&format!("\n\n## Benchmark\n\n{report}"));
+  }
+  if let Some(follow_up) = follow_up {
+    prompt.push_str(&follow_up.render());
+  }
+
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
   info!("reviewing: {intent}");
-  let (mut result, metadata): (ReviewResult, _) = runner.run_json_with_meta(
+  let (mut result, metadata): (ReviewResult, _) = runner.in_stage("review").run_json_with_meta(
     &prompt,
-    prompt::REVIEW,
+    config.prompts.get("review"),
     review_model,
     worktree_path,
     timeout,
@@ -131,7 +206,7 @@ fn review_inner(
 }
 
 fn get_diff(worktree_path: &Path, base_branch: &str) -> Result<String> {
-  let output = Command::new("git")
+  let output = process::command("git")
     .args(["diff", &format!("origin/{base_branch}...HEAD")])
     .current_dir(worktree_path)
     .output()?;
@@ -44,8 +44,7 @@ pub fn observe(
   runner: &impl Claude,
   repo_path: &Path,
 ) -> Result<(ObserveResult, ClaudeMetadata)> {
-  let history_dir = repo_path.join(".forge").join("knowledge").join("history");
-  let entries = load_recent_history(&history_dir)?;
+  let entries = load_recent_history(repo_path)?;
 
   if entries.is_empty() {
     info!("skill observe: no history entries");
@@ -72,7 +71,7 @@ pub fn observe(
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
   info!("skill observe: analyzing {} history entries", entries.len());
-  runner.run_json_with_meta(
+  runner.in_stage("skill").run_json_with_meta(
     &prompt,
     prompt::SKILL_OBSERVE,
     observe_model,
@@ -109,7 +108,7 @@ pub fn abstract_patterns(
   let timeout = Some(Duration::from_secs(config.analyze_timeout_secs));
 
   info!("skill abstract: processing {} patterns", patterns.len());
-  runner.run_json_with_meta(
+  runner.in_stage("skill").run_json_with_meta(
     &prompt,
     prompt::SKILL_ABSTRACT,
     abstract_model,
@@ -160,26 +159,16 @@ impl HistorySummary {
   }
 }
 
-fn load_recent_history(history_dir: &Path) -> Result<Vec<HistorySummary>> {
-  if !history_dir.exists() {
-    return Ok(Vec::new());
-  }
-
-  let mut entries = Vec::new();
-  for entry in std::fs::read_dir(history_dir)? {
-    let entry = entry?;
-    let path = entry.path();
-    if path.extension().and_then(|e| e.to_str()) != Some("yaml") {
-      continue;
-    }
-    let content = std::fs::read_to_string(&path)?;
-    let h: history::HistoryEntry = serde_yaml::from_str(&content)?;
-    entries.push(HistorySummary {
-      intent_id: h.intent_id,
-      title: h.title,
-      step_results: h.step_results,
-      outcome: h.outcome,
-    });
-  }
-  Ok(entries)
+fn load_recent_history(repo_path: &Path) -> Result<Vec<HistorySummary>> {
+  Ok(
+    history::load_all(repo_path)?
+      .into_iter()
+      .map(|h| HistorySummary {
+        intent_id: h.intent_id,
+        title: h.title,
+        step_results: h.step_results,
+        outcome: h.outcome,
+      })
+      .collect(),
+  )
 }
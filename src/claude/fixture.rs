@@ -0,0 +1,189 @@
+//! Record/replay of `claude` calls for deterministic pipeline tests.
+//!
+//! [`RecordingRunner`] wraps a real runner and appends every exchange (stage,
+//! model, prompt, raw CLI output or error) to a YAML fixture as it happens.
+//! [`ReplayRunner`] serves those exchanges back in order without touching the
+//! API. Prompts contain worktree paths and timestamps, so replay checks only the
+//! stage of each call; the recorded prompt is kept for reading the fixture.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::claude::runner::{Claude, SessionMode};
+use crate::error::{ForgeError, Result};
+
+/// One recorded `claude` call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Exchange {
+  /// Stage from [`Claude::in_stage`]; `None` for unstaged calls.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub stage: Option<String>,
+  pub model: String,
+  pub prompt: String,
+  /// Raw CLI output (the JSON wrapper) on success.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub response: Option<String>,
+  /// Error message on failure.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+}
+
+pub fn load(path: &Path) -> Result<Vec<Exchange>> {
+  let content = std::fs::read_to_string(path)?;
+  Ok(serde_yaml::from_str(&content)?)
+}
+
+fn save(path: &Path, exchanges: &[Exchange]) -> Result<()> {
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, serde_yaml::to_string(exchanges)?)?;
+  Ok(())
+}
+
+pub struct RecordingRunner<C> {
+  inner: C,
+  path: PathBuf,
+  exchanges: Mutex<Vec<Exchange>>,
+}
+
+impl<C: Claude> RecordingRunner<C> {
+  /// Record into `path`, replacing any fixture already there.
+  pub fn new(inner: C, path: impl Into<PathBuf>) -> Self {
+    Self {
+      inner,
+      path: path.into(),
+      exchanges: Mutex::new(Vec::new()),
+    }
+  }
+
+  fn record(&self, stage: Option<&str>, model: &str, prompt: &str, result: &Result<String>) {
+    let mut exchanges = self.exchanges.lock().unwrap();
+    exchanges.push(Exchange {
+      stage: stage.map(str::to_string),
+      model: model.to_string(),
+      prompt: prompt.to_string(),
+      response: result.as_ref().ok().cloned(),
+      error: result.as_ref().err().map(|e| e.to_string()),
+    });
+    // Saved after every call so an interrupted run still leaves a fixture
+    if let Err(e) = save(&self.path, &exchanges) {
+      tracing::warn!("failed to write fixture {}: {e}", self.path.display());
+    }
+  }
+}
+
+impl<C: Claude> Claude for RecordingRunner<C> {
+  fn run_prompt(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    let result = self
+      .inner
+      .run_prompt(prompt, system_prompt, model, cwd, timeout, session);
+    self.record(None, model, prompt, &result);
+    result
+  }
+
+  fn run_prompt_in_stage(
+    &self,
+    stage: &str,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    let result =
+      self
+        .inner
+        .run_prompt_in_stage(stage, prompt, system_prompt, model, cwd, timeout, session);
+    self.record(Some(stage), model, prompt, &result);
+    result
+  }
+}
+
+pub struct ReplayRunner {
+  exchanges: Vec<Exchange>,
+  next: Mutex<usize>,
+}
+
+impl ReplayRunner {
+  pub fn new(exchanges: Vec<Exchange>) -> Self {
+    Self {
+      exchanges,
+      next: Mutex::new(0),
+    }
+  }
+
+  pub fn load(path: &Path) -> Result<Self> {
+    Ok(Self::new(load(path)?))
+  }
+
+  /// Exchanges not replayed yet; zero once the run made every recorded call.
+  pub fn remaining(&self) -> usize {
+    self.exchanges.len() - *self.next.lock().unwrap()
+  }
+
+  fn replay(&self, stage: Option<&str>) -> Result<String> {
+    let mut next = self.next.lock().unwrap();
+    let exchange = self.exchanges.get(*next).ok_or_else(|| {
+      ForgeError::Claude(format!(
+        "replay fixture exhausted after {} call(s)",
+        self.exchanges.len()
+      ))
+    })?;
+    if exchange.stage.as_deref() != stage {
+      return Err(ForgeError::Claude(format!(
+        "replay fixture mismatch at call {}: recorded stage {}, called from {}",
+        *next + 1,
+        exchange.stage.as_deref().unwrap_or("-"),
+        stage.unwrap_or("-"),
+      )));
+    }
+    *next += 1;
+    match (&exchange.response, &exchange.error) {
+      (Some(response), _) => Ok(response.clone()),
+      (None, Some(error)) => Err(ForgeError::Claude(error.clone())),
+      (None, None) => Err(ForgeError::Claude(
+        "replay fixture entry has no response".into(),
+      )),
+    }
+  }
+}
+
+impl Claude for ReplayRunner {
+  fn run_prompt(
+    &self,
+    _prompt: &str,
+    _system_prompt: &str,
+    _model: &str,
+    _cwd: &Path,
+    _timeout: Option<Duration>,
+    _session: &SessionMode,
+  ) -> Result<String> {
+    self.replay(None)
+  }
+
+  fn run_prompt_in_stage(
+    &self,
+    stage: &str,
+    _prompt: &str,
+    _system_prompt: &str,
+    _model: &str,
+    _cwd: &Path,
+    _timeout: Option<Duration>,
+    _session: &SessionMode,
+  ) -> Result<String> {
+    self.replay(Some(stage))
+  }
+}
@@ -1,2 +1,4 @@
+pub mod fixture;
 pub mod model;
 pub mod runner;
+pub mod sandbox;
@@ -1,12 +1,19 @@
+use std::future::Future;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Output, Stdio};
 use std::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+use tokio::runtime::RuntimeFlavor;
+use tracing::{debug, info, info_span, warn};
 
+use crate::claude::{model, sandbox};
+use crate::config::ClaudeSettings;
 use crate::error::{ForgeError, Result};
+use crate::process;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ClaudeMetadata {
@@ -30,6 +37,36 @@ pub struct ClaudeMetadata {
   pub num_turns: Option<u64>,
 }
 
+/// A `claude -p --output-format json` response: the final text and the
+/// wrapper's metadata (cost, turns, session) for logging, budgets and resume.
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeResponse {
+  pub result: String,
+  pub metadata: ClaudeMetadata,
+}
+
+impl ClaudeResponse {
+  pub fn parse(raw: &str) -> Result<Self> {
+    let wrapper: serde_json::Value = serde_json::from_str(raw)
+      .map_err(|e| ForgeError::Claude(format!("failed to parse claude output as JSON: {e}")))?;
+    let result = wrapper
+      .get("result")
+      .and_then(|v| v.as_str())
+      .ok_or_else(|| ForgeError::Claude("claude output missing 'result' field".into()))?;
+    Ok(Self {
+      result: result.to_string(),
+      metadata: metadata_of(&wrapper),
+    })
+  }
+
+  /// Parse the result text as JSON of the expected type; it may be wrapped in
+  /// a markdown code block or surrounded by prose.
+  pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+    serde_json::from_str(extract_json(&self.result))
+      .map_err(|e| ForgeError::Claude(format!("failed to parse result as expected type: {e}")))
+  }
+}
+
 /// Session handling for Claude CLI invocations.
 #[derive(Debug, Clone, Default)]
 pub enum SessionMode {
@@ -67,6 +104,45 @@ pub trait Claude {
     session: &SessionMode,
   ) -> Result<String>;
 
+  /// `run_prompt` with the CLI options of `stage` (`claude.stages.<stage>`).
+  /// Implementations without per-stage options ignore the stage.
+  #[allow(clippy::too_many_arguments)]
+  fn run_prompt_in_stage(
+    &self,
+    _stage: &str,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    self.run_prompt(prompt, system_prompt, model, cwd, timeout, session)
+  }
+
+  /// This runner as seen by one stage: every call goes through
+  /// [`run_prompt_in_stage`](Claude::run_prompt_in_stage).
+  fn in_stage<'a>(&'a self, stage: &'a str) -> Staged<'a, Self>
+  where
+    Self: Sized,
+  {
+    Staged { inner: self, stage }
+  }
+
+  /// `run_prompt` with the wrapper parsed into a [`ClaudeResponse`].
+  fn run(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<ClaudeResponse> {
+    let raw = self.run_prompt(prompt, system_prompt, model, cwd, timeout, session)?;
+    ClaudeResponse::parse(&raw)
+  }
+
   fn run_json<T: DeserializeOwned>(
     &self,
     prompt: &str,
@@ -75,18 +151,98 @@ pub trait Claude {
     cwd: &Path,
     timeout: Option<Duration>,
   ) -> Result<T> {
-    let raw = self.run_prompt(
-      prompt,
-      system_prompt,
-      model,
+    self
+      .run_json_with_meta(
+        prompt,
+        system_prompt,
+        model,
+        cwd,
+        timeout,
+        &SessionMode::None,
+      )
+      .map(|(result, _)| result)
+  }
+
+  /// Run and parse the result as `T`. Output that does not deserialize gets one
+  /// [`repair_json`](Claude::repair_json) pass before the parse error is returned.
+  fn run_json_with_meta<T: DeserializeOwned>(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<(T, ClaudeMetadata)> {
+    let response = self.run(prompt, system_prompt, model, cwd, timeout, session)?;
+    let mut metadata = response.metadata.clone();
+    let result = match response.json() {
+      Ok(result) => result,
+      Err(error) => {
+        warn!("{error}; attempting JSON repair");
+        let (result, repair) = self
+          .repair_json(&response.result, &error, system_prompt, cwd, timeout)
+          .map_err(|e| ForgeError::Claude(format!("{error} (repair failed: {e})")))?;
+        if let Some(cost) = repair.cost_usd {
+          metadata.cost_usd = Some(metadata.cost_usd.unwrap_or_default() + cost);
+        }
+        result
+      }
+    };
+    Ok((result, metadata))
+  }
+
+  /// Ask a cheap model to rewrite `output` so it deserializes as `T`, given the
+  /// parse error and the agent's instructions (which specify the output format).
+  fn repair_json<T: DeserializeOwned>(
+    &self,
+    output: &str,
+    error: &ForgeError,
+    system_prompt: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+  ) -> Result<(T, ClaudeMetadata)> {
+    let prompt = format!(
+      "## Parse Error\n\n{error}\n\n\
+       ## Agent Instructions (output format)\n\n{instructions}\n\n\
+       ## Output To Fix\n\n{output}",
+      instructions = truncate(system_prompt, MAX_REPAIR_INPUT),
+      output = truncate(output, MAX_REPAIR_INPUT),
+    );
+    let response = self.run(
+      &prompt,
+      crate::prompt::JSON_REPAIR,
+      model::HAIKU,
       cwd,
       timeout,
       &SessionMode::None,
     )?;
-    parse_claude_json_output(&raw)
+    Ok((response.json()?, response.metadata))
   }
+}
 
-  fn run_json_with_meta<T: DeserializeOwned>(
+/// Bytes of the broken output and of the agent instructions sent to the repair call.
+const MAX_REPAIR_INPUT: usize = 20_000;
+
+fn truncate(text: &str, max_len: usize) -> &str {
+  if text.len() <= max_len {
+    return text;
+  }
+  let mut end = max_len;
+  while !text.is_char_boundary(end) {
+    end -= 1;
+  }
+  &text[..end]
+}
+
+/// A [`Claude`] bound to a stage; see [`Claude::in_stage`].
+pub struct Staged<'a, C> {
+  inner: &'a C,
+  stage: &'a str,
+}
+
+impl<C: Claude> Claude for Staged<'_, C> {
+  fn run_prompt(
     &self,
     prompt: &str,
     system_prompt: &str,
@@ -94,11 +250,18 @@ pub trait Claude {
     cwd: &Path,
     timeout: Option<Duration>,
     session: &SessionMode,
-  ) -> Result<(T, ClaudeMetadata)> {
-    let raw = self.run_prompt(prompt, system_prompt, model, cwd, timeout, session)?;
-    let metadata = parse_metadata(&raw);
-    let result = parse_claude_json_output(&raw)?;
-    Ok((result, metadata))
+  ) -> Result<String> {
+    let span = info_span!("stage", stage = self.stage);
+    let _span = span.enter();
+    self.inner.run_prompt_in_stage(
+      self.stage,
+      prompt,
+      system_prompt,
+      model,
+      cwd,
+      timeout,
+      session,
+    )
   }
 }
 
@@ -106,6 +269,7 @@ pub trait Claude {
 pub struct ClaudeRunner {
   allowed_tools: Vec<String>,
   mcp_config: Option<String>,
+  cli: ClaudeSettings,
 }
 
 impl ClaudeRunner {
@@ -124,31 +288,49 @@ impl ClaudeRunner {
     Self {
       allowed_tools,
       mcp_config,
+      cli: ClaudeSettings::default(),
     }
   }
+
+  /// Use `settings.bin` instead of `claude` from PATH and append `settings.extra_args`.
+  pub fn with_cli(mut self, settings: &ClaudeSettings) -> Self {
+    self.cli = settings.clone();
+    self
+  }
 }
 
-impl Claude for ClaudeRunner {
-  fn run_prompt(
+impl ClaudeRunner {
+  fn command(
     &self,
-    prompt: &str,
     system_prompt: &str,
     model: &str,
     cwd: &Path,
-    timeout: Option<Duration>,
     session: &SessionMode,
-  ) -> Result<String> {
+    stage: Option<&str>,
+  ) -> Result<(Command, Option<sandbox::Container>)> {
     let tools_csv = self.allowed_tools.join(",");
 
-    info!("running claude -p with model={model} in {}", cwd.display());
-    debug!("prompt: {prompt}");
-
-    let mut cmd = Command::new("claude");
+    let sandbox = self
+      .cli
+      .sandbox
+      .as_ref()
+      .filter(|_| sandbox::can_write(&self.allowed_tools));
+    let (mut cmd, container) = match sandbox {
+      Some(settings) => {
+        let (cmd, container) = sandbox::command(settings, cwd, self.mcp_config.as_deref())?;
+        (cmd, Some(container))
+      }
+      None => (process::async_command(&self.cli.bin), None),
+    };
     cmd
       .args(["-p", "--model", model, "--output-format", "json"])
       .args(["--allowedTools", &tools_csv])
+      .args(&self.cli.extra_args)
       .current_dir(cwd)
       .env_remove("CLAUDE_CODE_ENTRYPOINT");
+    if let Some(options) = stage.and_then(|stage| self.cli.stages.get(stage)) {
+      cmd.args(options.args());
+    }
 
     match session {
       SessionMode::New(id) => {
@@ -164,127 +346,268 @@ impl Claude for ClaudeRunner {
       cmd.args(["--mcp-config", mcp_path]);
     }
 
-    if !system_prompt.is_empty() {
+    if sandbox.is_some() {
+      cmd.args([
+        "--append-system-prompt",
+        &format!("{system_prompt}\n\n{}", sandbox::PROMPT_NOTE),
+      ]);
+    } else if !system_prompt.is_empty() {
       cmd.args(["--append-system-prompt", system_prompt]);
     }
 
     // Remove CLAUDECODE env var to allow nested Claude Code invocation
     cmd.env_remove("CLAUDECODE");
+    Ok((cmd, container))
+  }
 
-    let mut child = cmd
-      .stdin(std::process::Stdio::piped())
-      .stdout(std::process::Stdio::piped())
-      .stderr(std::process::Stdio::piped())
-      .spawn()?;
-
-    // Write prompt to stdin
-    use std::io::Write;
-    if let Some(mut stdin) = child.stdin.take() {
-      stdin.write_all(prompt.as_bytes())?;
+  #[allow(clippy::too_many_arguments)]
+  fn run_once(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+    stage: Option<&str>,
+  ) -> Result<String> {
+    let (cmd, container) = self.command(system_prompt, model, cwd, session, stage)?;
+    // On a timeout or error `container` drops here and the container is killed
+    let output = block_on(run_command(cmd, prompt.to_string(), timeout))??;
+    if let Some(container) = container {
+      container.finish();
     }
 
-    let output = if let Some(dur) = timeout {
-      wait_with_timeout(child, dur)?
-    } else {
-      child.wait_with_output()?
-    };
-
     if !output.status.success() {
+      // The CLI reports API errors (e.g. overloaded) in the JSON on stdout
       let stderr = String::from_utf8_lossy(&output.stderr);
+      let detail = if stderr.trim().is_empty() {
+        String::from_utf8_lossy(&output.stdout)
+      } else {
+        stderr
+      };
       return Err(ForgeError::Claude(format!(
-        "claude exited with {}: {stderr}",
+        "claude exited with {}: {detail}",
         output.status
       )));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    debug!("claude output length: {} bytes", stdout.len());
+    if stdout.trim().is_empty() {
+      return Err(ForgeError::Claude("claude returned empty output".into()));
+    }
     Ok(stdout)
   }
 }
 
-fn wait_with_timeout(
-  mut child: std::process::Child,
-  timeout: Duration,
-) -> Result<std::process::Output> {
-  use std::io::Read;
+impl ClaudeRunner {
+  /// Runs the CLI, retrying transient failures up to `claude.max_retries` times
+  /// with exponential backoff. A retried `New` session is resumed instead,
+  /// since the first attempt may already have created it.
+  #[allow(clippy::too_many_arguments)]
+  fn run_with_retries(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+    stage: Option<&str>,
+  ) -> Result<String> {
+    info!(
+      "running {} -p with model={model} in {}",
+      self.cli.bin,
+      cwd.display()
+    );
+    debug!("prompt: {prompt}");
 
-  let stdout_pipe = child.stdout.take();
-  let stderr_pipe = child.stderr.take();
+    let mut session = session.clone();
+    let mut attempt = 0;
+    let stdout = loop {
+      match self.run_once(prompt, system_prompt, model, cwd, timeout, &session, stage) {
+        Err(e) if attempt < self.cli.max_retries && is_transient(&e) => {
+          attempt += 1;
+          let delay = backoff(
+            self.cli.retry_backoff_secs,
+            self.cli.max_backoff_secs,
+            attempt,
+          );
+          warn!(
+            "transient claude failure, retrying in {}s ({attempt}/{}): {e}",
+            delay.as_secs(),
+            self.cli.max_retries
+          );
+          std::thread::sleep(delay);
+          if let SessionMode::New(id) = &session {
+            session = SessionMode::Resume(id.clone());
+          }
+        }
+        result => break result?,
+      }
+    };
 
-  let stdout_handle = std::thread::spawn(move || {
-    let mut buf = Vec::new();
-    if let Some(mut pipe) = stdout_pipe {
-      let _ = pipe.read_to_end(&mut buf);
-    }
-    buf
-  });
+    debug!("claude output ({} bytes): {stdout}", stdout.len());
+    let meta = parse_metadata(&stdout);
+    info!(
+      "claude finished: cost=${:.4} turns={} session={}",
+      meta.cost_usd.unwrap_or_default(),
+      meta.num_turns.unwrap_or_default(),
+      meta.session_id.as_deref().unwrap_or("-")
+    );
+    Ok(stdout)
+  }
+}
+
+impl Claude for ClaudeRunner {
+  fn run_prompt(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    self.run_with_retries(prompt, system_prompt, model, cwd, timeout, session, None)
+  }
+
+  fn run_prompt_in_stage(
+    &self,
+    stage: &str,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    self.run_with_retries(
+      prompt,
+      system_prompt,
+      model,
+      cwd,
+      timeout,
+      session,
+      Some(stage),
+    )
+  }
+}
 
-  let stderr_handle = std::thread::spawn(move || {
-    let mut buf = Vec::new();
-    if let Some(mut pipe) = stderr_pipe {
-      let _ = pipe.read_to_end(&mut buf);
+/// Wait before retry `attempt` (1-based): `base` doubled per earlier retry,
+/// capped at `max` and never overflowing.
+fn backoff(base: u64, max: u64, attempt: u32) -> Duration {
+  let secs = 1u64
+    .checked_shl(attempt.saturating_sub(1))
+    .and_then(|factor| base.checked_mul(factor))
+    .unwrap_or(u64::MAX);
+  Duration::from_secs(secs.min(max))
+}
+
+/// Failures worth retrying as-is; see [`ForgeError::is_transient`].
+pub fn is_transient(error: &ForgeError) -> bool {
+  error.is_transient()
+}
+
+/// Spawn `cmd`, feed `prompt` on stdin while stdout and stderr are read
+/// concurrently, and wait for exit. On timeout the child is killed and reaped.
+async fn run_command(
+  mut cmd: Command,
+  prompt: String,
+  timeout: Option<Duration>,
+) -> Result<Output> {
+  let mut child = cmd
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true)
+    .spawn()?;
+
+  let stdin = child.stdin.take();
+  let stdout = child.stdout.take();
+  let stderr = child.stderr.take();
+  let write_prompt = async move {
+    if let Some(mut stdin) = stdin {
+      stdin.write_all(prompt.as_bytes()).await?;
+      // Dropping stdin closes the pipe so the CLI sees EOF
     }
-    buf
-  });
-
-  let start = std::time::Instant::now();
-  let poll_interval = Duration::from_secs(1);
-
-  loop {
-    match child.try_wait()? {
-      Some(status) => {
-        let stdout = stdout_handle.join().unwrap_or_default();
-        let stderr = stderr_handle.join().unwrap_or_default();
-        return Ok(std::process::Output {
-          status,
-          stdout,
-          stderr,
-        });
-      }
-      None if start.elapsed() >= timeout => {
-        warn!(
-          "claude process timed out after {}s, killing",
-          timeout.as_secs()
-        );
-        let _ = child.kill();
-        let _ = child.wait();
-        return Err(ForgeError::Timeout(format!(
-          "timed out after {}s",
-          timeout.as_secs()
-        )));
-      }
-      None => std::thread::sleep(poll_interval),
+    Ok::<_, std::io::Error>(())
+  };
+  let run = async {
+    let ((), stdout, stderr, status) = tokio::try_join!(
+      write_prompt,
+      read_all(stdout),
+      read_all(stderr),
+      child.wait()
+    )?;
+    Ok::<_, std::io::Error>(Output {
+      status,
+      stdout,
+      stderr,
+    })
+  };
+
+  let finished = match timeout {
+    Some(dur) => tokio::time::timeout(dur, run).await.ok(),
+    None => Some(run.await),
+  };
+  match finished {
+    Some(output) => Ok(output?),
+    None => {
+      let secs = timeout.unwrap_or_default().as_secs();
+      warn!("claude process timed out after {secs}s, killing");
+      let _ = child.kill().await;
+      Err(ForgeError::Timeout(format!("timed out after {secs}s")))
     }
   }
 }
 
-/// Parse Claude's --output-format json response.
-/// The response is a JSON object with a "result" field containing the actual text output.
-/// We then try to parse that text as JSON of the expected type.
-fn parse_claude_json_output<T: DeserializeOwned>(raw: &str) -> Result<T> {
-  // First, parse the wrapper object
-  let wrapper: serde_json::Value = serde_json::from_str(raw)
-    .map_err(|e| ForgeError::Claude(format!("failed to parse claude output as JSON: {e}")))?;
-
-  let result_text = wrapper
-    .get("result")
-    .and_then(|v| v.as_str())
-    .ok_or_else(|| ForgeError::Claude("claude output missing 'result' field".into()))?;
+async fn read_all(pipe: Option<impl AsyncRead + Unpin>) -> std::io::Result<Vec<u8>> {
+  let mut buf = Vec::new();
+  if let Some(mut pipe) = pipe {
+    pipe.read_to_end(&mut buf).await?;
+  }
+  Ok(buf)
+}
 
-  // Try to extract JSON from the result text (may be wrapped in markdown code block)
-  let json_str = extract_json(result_text);
+/// Drive `fut` to completion from the synchronous `Claude` trait. Inside a
+/// multi-thread runtime (the CLI's `#[tokio::main]`) the worker is handed over
+/// with `block_in_place`; elsewhere (scoped worker threads) a current-thread
+/// runtime is built on the calling thread. A current-thread runtime cannot be
+/// blocked on, so that case runs on a helper thread.
+fn block_on<F>(fut: F) -> Result<F::Output>
+where
+  F: Future + Send,
+  F::Output: Send,
+{
+  match tokio::runtime::Handle::try_current() {
+    Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+      Ok(tokio::task::block_in_place(|| handle.block_on(fut)))
+    }
+    Ok(_) => std::thread::scope(|s| s.spawn(|| block_on_local(fut)).join())
+      .map_err(|_| ForgeError::Claude("claude runner thread panicked".into()))?,
+    Err(_) => block_on_local(fut),
+  }
+}
 
-  serde_json::from_str(json_str)
-    .map_err(|e| ForgeError::Claude(format!("failed to parse result as expected type: {e}")))
+fn block_on_local<F: Future>(fut: F) -> Result<F::Output> {
+  Ok(
+    tokio::runtime::Builder::new_current_thread()
+      .enable_all()
+      .build()?
+      .block_on(fut),
+  )
 }
 
 /// Extract metadata from the Claude JSON wrapper (session_id, tokens, cost, etc.)
 pub fn parse_metadata(raw: &str) -> ClaudeMetadata {
-  let wrapper: serde_json::Value = match serde_json::from_str(raw) {
-    Ok(v) => v,
-    Err(_) => return ClaudeMetadata::default(),
-  };
+  match serde_json::from_str(raw) {
+    Ok(wrapper) => metadata_of(&wrapper),
+    Err(_) => ClaudeMetadata::default(),
+  }
+}
+
+fn metadata_of(wrapper: &serde_json::Value) -> ClaudeMetadata {
   let usage = wrapper.get("usage");
   ClaudeMetadata {
     session_id: wrapper
@@ -371,10 +694,27 @@ mod tests {
     }
 
     let raw = r#"{"result": "{\"actionable\": true}", "cost_usd": 0.01}"#;
-    let parsed: TestOutput = parse_claude_json_output(raw).unwrap();
+    let parsed: TestOutput = ClaudeResponse::parse(raw).unwrap().json().unwrap();
     assert!(parsed.actionable);
   }
 
+  #[test]
+  fn レスポンスにresultとメタデータをまとめる() {
+    let raw = r#"{"result": "done", "session_id": "s-1", "total_cost_usd": 0.5, "num_turns": 7, "duration_ms": 1200}"#;
+    let response = ClaudeResponse::parse(raw).unwrap();
+    assert_eq!(response.result, "done");
+    assert_eq!(response.metadata.session_id.as_deref(), Some("s-1"));
+    assert_eq!(response.metadata.cost_usd, Some(0.5));
+    assert_eq!(response.metadata.num_turns, Some(7));
+    assert_eq!(response.metadata.duration_ms, Some(1200));
+  }
+
+  #[test]
+  fn resultのないレスポンスはエラーにする() {
+    assert!(ClaudeResponse::parse(r#"{"session_id": "s-1"}"#).is_err());
+    assert!(ClaudeResponse::parse("not json").is_err());
+  }
+
   #[test]
   fn ラッパーからメタデータを抽出する() {
     let raw = r#"{
@@ -481,4 +821,325 @@ mod tests {
       1
     );
   }
+
+  #[test]
+  fn 再試行の待機は倍々で上限を超えずオーバーフローしない() {
+    assert_eq!(backoff(10, 300, 1), Duration::from_secs(10));
+    assert_eq!(backoff(10, 300, 3), Duration::from_secs(40));
+    assert_eq!(backoff(10, 300, 10), Duration::from_secs(300));
+    assert_eq!(backoff(10, 300, 64), Duration::from_secs(300));
+    assert_eq!(backoff(u64::MAX, 300, 200), Duration::from_secs(300));
+  }
+
+  #[test]
+  fn 設定したバイナリと追加引数でclaudeを起動する() {
+    let dir = tempfile::tempdir().unwrap();
+    let bin = dir.path().join("claude-wrapper");
+    std::fs::write(&bin, "#!/bin/sh\ncat >/dev/null\necho \"$@\"\n").unwrap();
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let runner = ClaudeRunner::new(vec!["Read".into()], None, None).with_cli(&ClaudeSettings {
+      bin: bin.to_string_lossy().into_owned(),
+      extra_args: vec!["--settings".into(), "forge.json".into()],
+      ..Default::default()
+    });
+    let output = runner
+      .run_prompt("hi", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .unwrap();
+
+    assert!(output.starts_with("-p --model sonnet"));
+    assert!(output.contains("--settings forge.json"));
+  }
+
+  fn script_runner(dir: &Path, script: &str) -> ClaudeRunner {
+    use std::os::unix::fs::PermissionsExt;
+    let bin = dir.join("claude-script");
+    std::fs::write(&bin, format!("#!/bin/sh\n{script}\n")).unwrap();
+    std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+    ClaudeRunner::new(vec![], None, None).with_cli(&ClaudeSettings {
+      bin: bin.to_string_lossy().into_owned(),
+      extra_args: vec![],
+      max_retries: 2,
+      retry_backoff_secs: 0,
+      max_backoff_secs: 0,
+      stages: Default::default(),
+      sandbox: None,
+    })
+  }
+
+  #[test]
+  fn ステージ設定の禁止ツールと権限モードをそのステージにだけ渡す() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut runner = script_runner(dir.path(), r#"printf '{"result": "%s"}' "$*""#);
+    runner.cli.stages.insert(
+      "review".into(),
+      crate::config::StageOptions {
+        disallowed_tools: vec!["Edit".into(), "Write".into()],
+        permission_mode: Some("plan".into()),
+      },
+    );
+    let run = |stage: &str| {
+      runner
+        .in_stage(stage)
+        .run_prompt("", "", "sonnet", dir.path(), None, &SessionMode::None)
+        .unwrap()
+    };
+
+    let review = run("review");
+    assert!(review.contains("--disallowedTools Edit,Write"), "{review}");
+    assert!(review.contains("--permission-mode plan"), "{review}");
+    let implement = run("implement");
+    assert!(!implement.contains("--disallowedTools"), "{implement}");
+    assert!(!implement.contains("--permission-mode"), "{implement}");
+  }
+
+  #[test]
+  fn sandbox設定時は書き込めるツールを持つ呼び出しをステージによらずコンテナ経由で起動する() {
+    let dir = tempfile::tempdir().unwrap();
+    let worktree = dir.path().join("wt");
+    std::fs::create_dir(&worktree).unwrap();
+    std::process::Command::new("git")
+      .args(["init", "-q"])
+      .current_dir(&worktree)
+      .status()
+      .unwrap();
+    // Stands in for docker: report the arguments it was given
+    let runtime = script_runner(
+      &worktree,
+      r#"printf '{"result": "%s"}' "$(echo "$*" | tr '\n' ' ')""#,
+    )
+    .cli
+    .bin;
+    let mut runner = script_runner(dir.path(), r#"printf '{"result": "host"}'"#);
+    runner.cli.sandbox = Some(crate::config::SandboxSettings {
+      runtime,
+      image: "forge-worker:latest".into(),
+      bin: "claude".into(),
+      env: vec!["ANTHROPIC_API_KEY".into()],
+      extra_args: vec!["--network".into(), "bridge".into()],
+    });
+    let read_only = runner.clone();
+    runner.allowed_tools = vec!["Read".into(), "Bash(cargo test:*)".into()];
+    let run = |stage: &str| {
+      let raw = runner
+        .in_stage(stage)
+        .run_prompt("", "", "sonnet", &worktree, None, &SessionMode::None)
+        .unwrap();
+      ClaudeResponse::parse(&raw).unwrap().result
+    };
+
+    let implement = run("implement");
+    let wt = worktree.to_string_lossy();
+    assert!(
+      implement.starts_with("run --rm -i --name forge-"),
+      "{implement}"
+    );
+    assert!(
+      implement.contains(&format!("--volume {wt}:{wt}")),
+      "{implement}"
+    );
+    assert!(implement.contains("--env ANTHROPIC_API_KEY"), "{implement}");
+    assert!(
+      implement.contains("--network bridge forge-worker:latest claude -p"),
+      "{implement}"
+    );
+    // git metadata is never writable from the container
+    let git_dir = worktree.join(".git").canonicalize().unwrap();
+    let git_dir = git_dir.to_string_lossy();
+    assert!(
+      implement.contains(&format!("--volume {git_dir}:{git_dir}:ro")),
+      "{implement}"
+    );
+    assert!(implement.contains(sandbox::PROMPT_NOTE), "{implement}");
+    for stage in ["conflict", "lint-fix"] {
+      assert!(run(stage).starts_with("run --rm -i"), "{stage}");
+    }
+
+    let raw = read_only
+      .in_stage("review")
+      .run_prompt("", "", "sonnet", &worktree, None, &SessionMode::None)
+      .unwrap();
+    assert_eq!(ClaudeResponse::parse(&raw).unwrap().result, "host");
+  }
+
+  #[test]
+  fn sandboxの呼び出しがタイムアウトしたらコンテナを名前で止める() {
+    let dir = tempfile::tempdir().unwrap();
+    let worktree = dir.path().join("wt");
+    std::fs::create_dir(&worktree).unwrap();
+    std::process::Command::new("git")
+      .args(["init", "-q"])
+      .current_dir(&worktree)
+      .status()
+      .unwrap();
+    let calls = dir.path().join("calls");
+    let runtime_dir = dir.path().join("runtime");
+    std::fs::create_dir(&runtime_dir).unwrap();
+    // Stands in for docker: `run` hangs, every invocation is recorded
+    let runtime = script_runner(
+      &runtime_dir,
+      &format!(
+        "echo \"$*\" >> {}\nif [ \"$1\" = run ]; then sleep 30; fi",
+        calls.display()
+      ),
+    )
+    .cli
+    .bin;
+    let mut runner = script_runner(dir.path(), "true");
+    runner.cli.sandbox = Some(crate::config::SandboxSettings {
+      runtime,
+      image: "forge-worker:latest".into(),
+      bin: "claude".into(),
+      env: vec![],
+      extra_args: vec![],
+    });
+    runner.allowed_tools = vec!["Edit".into()];
+
+    let err = runner
+      .run_prompt(
+        "hi",
+        "",
+        "sonnet",
+        &worktree,
+        Some(Duration::from_secs(1)),
+        &SessionMode::None,
+      )
+      .unwrap_err();
+
+    assert!(matches!(err, ForgeError::Timeout(_)));
+    let calls = std::fs::read_to_string(&calls).unwrap();
+    let name = calls
+      .split_whitespace()
+      .skip_while(|arg| *arg != "--name")
+      .nth(1)
+      .unwrap();
+    assert!(name.starts_with("forge-"));
+    assert!(
+      calls.lines().any(|l| l == format!("kill {name}")),
+      "{calls}"
+    );
+  }
+
+  #[test]
+  fn 大きなプロンプトと出力をstdoutとstderrを並行して読みながら扱う() {
+    let dir = tempfile::tempdir().unwrap();
+    // Echo stdin back on stdout and fill stderr past the pipe buffer
+    let runner = script_runner(dir.path(), "head -c 200000 /dev/zero >&2\ncat");
+    let prompt = "x".repeat(300_000);
+    let output = runner
+      .run_prompt(
+        &prompt,
+        "",
+        "sonnet",
+        dir.path(),
+        Some(Duration::from_secs(30)),
+        &SessionMode::None,
+      )
+      .unwrap();
+    assert_eq!(output.len(), prompt.len());
+  }
+
+  #[test]
+  fn タイムアウトでプロセスを止めてtimeoutエラーを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(dir.path(), "sleep 30");
+    let start = std::time::Instant::now();
+    let err = runner
+      .run_prompt(
+        "hi",
+        "",
+        "sonnet",
+        dir.path(),
+        Some(Duration::from_secs(1)),
+        &SessionMode::None,
+      )
+      .unwrap_err();
+    assert!(matches!(err, ForgeError::Timeout(_)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+  }
+
+  #[test]
+  fn 非ゼロ終了ではstderrを含むclaudeエラーを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(
+      dir.path(),
+      "cat >/dev/null\necho 'not logged in' >&2\nexit 1",
+    );
+    let err = runner
+      .run_prompt("hi", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .unwrap_err();
+    assert!(err.to_string().contains("not logged in"));
+  }
+
+  #[test]
+  fn 一時的な失敗はリトライして成功を返す() {
+    let dir = tempfile::tempdir().unwrap();
+    // First call fails with an overloaded error, later calls succeed
+    let runner = script_runner(
+      dir.path(),
+      "cat >/dev/null\necho x >> calls\nif [ $(wc -l < calls) -eq 1 ]; then\n  echo '{\"is_error\":true,\"result\":\"API Error: 529 overloaded_error\"}'\n  exit 1\nfi\necho \"$@\"",
+    );
+    let output = runner
+      .run_prompt(
+        "hi",
+        "",
+        "sonnet",
+        dir.path(),
+        None,
+        &SessionMode::New("s-1".into()),
+      )
+      .unwrap();
+    let calls = std::fs::read_to_string(dir.path().join("calls")).unwrap();
+    assert_eq!(calls.lines().count(), 2);
+    // The retry resumes the session the first attempt may have created
+    assert!(output.contains("--resume s-1"));
+  }
+
+  #[test]
+  fn 空の出力はリトライし上限に達したらエラーを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(dir.path(), "cat >/dev/null\necho x >> calls");
+    let err = runner
+      .run_prompt("hi", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .unwrap_err();
+    assert!(err.to_string().contains("empty output"));
+    let calls = std::fs::read_to_string(dir.path().join("calls")).unwrap();
+    assert_eq!(calls.lines().count(), 3);
+  }
+
+  #[test]
+  fn 決定的な失敗はリトライしない() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(
+      dir.path(),
+      "cat >/dev/null\necho x >> calls\necho 'invalid model' >&2\nexit 1",
+    );
+    assert!(runner
+      .run_prompt("hi", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .is_err());
+    let calls = std::fs::read_to_string(dir.path().join("calls")).unwrap();
+    assert_eq!(calls.lines().count(), 1);
+    assert!(!is_transient(&ForgeError::Timeout("1200s".into())));
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn マルチスレッドランタイム内からも呼び出せる() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(dir.path(), "cat");
+    let output = runner
+      .run_prompt("hello", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .unwrap();
+    assert_eq!(output, "hello");
+  }
+
+  #[tokio::test]
+  async fn カレントスレッドランタイム内からも呼び出せる() {
+    let dir = tempfile::tempdir().unwrap();
+    let runner = script_runner(dir.path(), "cat");
+    let output = runner
+      .run_prompt("hello", "", "sonnet", dir.path(), None, &SessionMode::None)
+      .unwrap();
+    assert_eq!(output, "hello");
+  }
 }
@@ -0,0 +1,112 @@
+//! Running `claude` in a container (`claude.sandbox`) for every call whose
+//! allowed tools can write.
+//!
+//! The container sees the working directory (read-write, at the same path so
+//! session and log paths line up), and the repository's git directory and the
+//! MCP config read-only. The git directory stays read-only because forge runs
+//! git on the host afterwards: hooks or `core.*` settings written there would
+//! execute outside the container. The agent therefore cannot commit; the runner
+//! commits its changes from the host. Nothing else from the host is mounted —
+//! no home directory, SSH keys or cloud credentials — and environment
+//! variables are passed only when listed in `env`.
+//!
+//! Killing the runtime client on a timeout does not stop the container, so each
+//! one is named and killed through the runtime unless the call ran to completion.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use tokio::process::Command;
+use tracing::warn;
+
+use crate::config::SandboxSettings;
+use crate::error::Result;
+use crate::git;
+use crate::process;
+
+/// Appended to the system prompt of sandboxed calls.
+pub const PROMPT_NOTE: &str = "You are running in a sandbox where the git directory is read-only. \
+Do not run `git commit` or other commands that write to `.git`; the runner commits your changes.";
+
+/// Tools that change files or run commands. A call allowed any of them is sandboxed.
+const WRITE_TOOLS: &[&str] = &["Write", "Edit", "MultiEdit", "NotebookEdit", "Bash"];
+
+/// Whether `allowed_tools` (`--allowedTools` entries such as `Bash(git:*)`) can write.
+pub fn can_write(allowed_tools: &[String]) -> bool {
+  allowed_tools.iter().any(|tool| {
+    let name = tool.split('(').next().unwrap_or_default().trim();
+    WRITE_TOOLS.contains(&name)
+  })
+}
+
+/// A started container, killed by name when dropped before [`Container::finish`].
+pub struct Container {
+  runtime: String,
+  name: String,
+  finished: bool,
+}
+
+impl Container {
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// The call ran to completion; `--rm` already removed the container.
+  pub fn finish(mut self) {
+    self.finished = true;
+  }
+}
+
+impl Drop for Container {
+  fn drop(&mut self) {
+    if self.finished {
+      return;
+    }
+    warn!("killing sandbox container {}", self.name);
+    let killed = process::command(&self.runtime)
+      .args(["kill", &self.name])
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .status();
+    if let Err(e) = killed {
+      warn!("failed to kill sandbox container {}: {e}", self.name);
+    }
+  }
+}
+
+/// `<runtime> run ... <image> <bin>`; the caller appends the CLI arguments and
+/// keeps the returned [`Container`] alive until the call has finished.
+pub fn command(
+  settings: &SandboxSettings,
+  cwd: &Path,
+  mcp_config: Option<&str>,
+) -> Result<(Command, Container)> {
+  let cwd = cwd.to_string_lossy();
+  let git_dir = git::worktree::common_dir(Path::new(cwd.as_ref()))?;
+  let git_dir = git_dir.to_string_lossy();
+  let container = Container {
+    runtime: settings.runtime.clone(),
+    name: format!("forge-{}", uuid::Uuid::new_v4()),
+    finished: false,
+  };
+
+  let mut cmd = process::async_command(&settings.runtime);
+  cmd
+    .args(["run", "--rm", "-i", "--name", container.name()])
+    .args(["--workdir", &cwd])
+    .args(["--volume", &format!("{cwd}:{cwd}")]);
+  // Also when it lies under `cwd`: the nested read-only mount takes precedence
+  cmd.args(["--volume", &format!("{git_dir}:{git_dir}:ro")]);
+  if let Some(path) = mcp_config.filter(|p| Path::new(p).is_absolute()) {
+    cmd.args(["--volume", &format!("{path}:{path}:ro")]);
+  }
+  for name in &settings.env {
+    // `--env NAME` copies the host value, and is skipped when it is unset
+    cmd.args(["--env", name]);
+  }
+  cmd
+    .args(&settings.extra_args)
+    .arg(&settings.image)
+    .arg(&settings.bin);
+  Ok((cmd, container))
+}
@@ -0,0 +1,412 @@
+//! Shell completion scripts for `pfl-forge completions <shell>`, generated from
+//! the clap command tree. Positional `id` / `ids` arguments complete the intent
+//! IDs in `./.forge/intents/` when the shell asks, so the list stays current.
+
+use clap::builder::PossibleValue;
+use clap::{Arg, Command, ValueHint};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Shell {
+  Bash,
+  Zsh,
+  Fish,
+}
+
+/// Positional argument names that take intent IDs.
+const INTENT_ID_ARGS: &[&str] = &["id", "ids"];
+
+/// Option names whose value is an intent ID (`run --intent`).
+const INTENT_ID_OPTIONS: &[&str] = &["intent"];
+
+pub fn generate(shell: Shell, cmd: &Command) -> String {
+  let mut cmd = cmd.clone();
+  // Adds --help and propagates global flags to subcommands
+  cmd.build();
+  match shell {
+    Shell::Bash => bash(&cmd),
+    // zsh runs the bash function through its compatibility layer
+    Shell::Zsh => format!(
+      "#compdef {name}\nautoload -U +X bashcompinit && bashcompinit\n{}",
+      bash(&cmd),
+      name = cmd.get_name()
+    ),
+    Shell::Fish => fish(&cmd),
+  }
+}
+
+fn visible_subcommands(cmd: &Command) -> impl Iterator<Item = &Command> {
+  cmd
+    .get_subcommands()
+    .filter(|s| !s.is_hide_set() && s.get_name() != "help")
+}
+
+fn options(cmd: &Command) -> impl Iterator<Item = &Arg> {
+  cmd
+    .get_arguments()
+    .filter(|a| !a.is_positional() && !a.is_hide_set())
+}
+
+fn flags(arg: &Arg) -> Vec<String> {
+  let mut flags = Vec::new();
+  if let Some(short) = arg.get_short() {
+    flags.push(format!("-{short}"));
+  }
+  if let Some(long) = arg.get_long() {
+    flags.push(format!("--{long}"));
+  }
+  flags
+}
+
+fn takes_value(arg: &Arg) -> bool {
+  arg.get_action().takes_values()
+}
+
+fn possible_values(arg: &Arg) -> Vec<String> {
+  arg
+    .get_possible_values()
+    .iter()
+    .filter(|v| !v.is_hide_set())
+    .map(PossibleValue::get_name)
+    .map(String::from)
+    .collect()
+}
+
+fn takes_intent_id_value(arg: &Arg) -> bool {
+  INTENT_ID_OPTIONS.contains(&arg.get_id().as_str())
+}
+
+fn takes_intent_ids(cmd: &Command) -> bool {
+  cmd
+    .get_positionals()
+    .any(|a| INTENT_ID_ARGS.contains(&a.get_id().as_str()))
+}
+
+fn fn_name(cmd: &Command) -> String {
+  format!("_{}", cmd.get_name().replace('-', "_"))
+}
+
+fn bash(cmd: &Command) -> String {
+  let name = cmd.get_name();
+  let func = fn_name(cmd);
+  let mut out = format!(
+    "{func}_intents() {{\n\
+     \x20 local f\n\
+     \x20 for f in .forge/intents/*.yaml; do\n\
+     \x20   [[ -e $f ]] || continue\n\
+     \x20   f=${{f##*/}}\n\
+     \x20   echo \"${{f%.yaml}}\"\n\
+     \x20 done\n\
+     }}\n\n\
+     {func}() {{\n\
+     \x20 local cur=${{COMP_WORDS[COMP_CWORD]}} prev=${{COMP_WORDS[COMP_CWORD-1]}}\n\
+     \x20 local cmd=\"\" sub=\"\" i\n\
+     \x20 for ((i = 1; i < COMP_CWORD; i++)); do\n\
+     \x20   case ${{COMP_WORDS[i]}} in\n"
+  );
+  // Skip the values of top-level options while looking for the subcommand
+  let valued: Vec<String> = options(cmd)
+    .filter(|a| takes_value(a))
+    .flat_map(flags)
+    .collect();
+  if !valued.is_empty() {
+    out.push_str(&format!("      {}) ((i++)) ;;\n", valued.join("|")));
+  }
+  out.push_str(
+    "      -*) ;;\n\
+     \x20     *) if [[ -z $cmd ]]; then cmd=${COMP_WORDS[i]}; elif [[ -z $sub ]]; then sub=${COMP_WORDS[i]}; fi ;;\n\
+     \x20   esac\n\
+     \x20 done\n\
+     \x20 case $cmd in\n",
+  );
+
+  let top: Vec<String> = visible_subcommands(cmd)
+    .map(|s| s.get_name().to_string())
+    .chain(options(cmd).flat_map(flags))
+    .collect();
+  out.push_str("    \"\")\n");
+  bash_value_cases(&mut out, cmd, &func, "      ");
+  out.push_str(&format!(
+    "      COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+    top.join(" ")
+  ));
+
+  for sub in visible_subcommands(cmd) {
+    out.push_str(&format!("    {})\n", sub.get_name()));
+    bash_value_cases(&mut out, sub, &func, "      ");
+    let nested: Vec<&Command> = visible_subcommands(sub).collect();
+    let words: Vec<String> = nested
+      .iter()
+      .map(|s| s.get_name().to_string())
+      .chain(sub.get_positionals().flat_map(possible_values))
+      .chain(options(sub).flat_map(flags))
+      .collect();
+    if takes_intent_ids(sub) {
+      out.push_str(&format!(
+        "      if [[ $cur == -* ]]; then\n\
+         \x20       COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n\
+         \x20     else\n\
+         \x20       COMPREPLY=($(compgen -W \"$({func}_intents)\" -- \"$cur\"))\n\
+         \x20     fi ;;\n",
+        words.join(" ")
+      ));
+    } else if nested.is_empty() {
+      out.push_str(&format!(
+        "      COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+        words.join(" ")
+      ));
+    } else {
+      out.push_str("      case $sub in\n");
+      for nested_cmd in &nested {
+        let nested_words: Vec<String> = options(nested_cmd).flat_map(flags).collect();
+        out.push_str(&format!(
+          "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+          nested_cmd.get_name(),
+          nested_words.join(" ")
+        ));
+      }
+      out.push_str(&format!(
+        "        *) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n\
+         \x20     esac ;;\n",
+        words.join(" ")
+      ));
+    }
+  }
+  out.push_str(&format!("  esac\n}}\ncomplete -F {func} {name}\n"));
+  out
+}
+
+/// `case $prev` completing the values of `cmd`'s options.
+fn bash_value_cases(out: &mut String, cmd: &Command, func: &str, indent: &str) {
+  let valued: Vec<&Arg> = options(cmd).filter(|a| takes_value(a)).collect();
+  if valued.is_empty() {
+    return;
+  }
+  out.push_str(&format!("{indent}case $prev in\n"));
+  for arg in valued {
+    let values = possible_values(arg);
+    let reply = if !values.is_empty() {
+      format!("compgen -W \"{}\" -- \"$cur\"", values.join(" "))
+    } else if takes_intent_id_value(arg) {
+      format!("compgen -W \"$({func}_intents)\" -- \"$cur\"")
+    } else {
+      match arg.get_value_hint() {
+        ValueHint::DirPath => "compgen -d -- \"$cur\"".to_string(),
+        ValueHint::FilePath | ValueHint::AnyPath => "compgen -f -- \"$cur\"".to_string(),
+        // Free-form value: offer nothing
+        _ => "true".to_string(),
+      }
+    };
+    out.push_str(&format!(
+      "{indent}  {}) COMPREPLY=($({reply})); return ;;\n",
+      flags(arg).join("|")
+    ));
+  }
+  out.push_str(&format!("{indent}esac\n"));
+}
+
+fn fish(cmd: &Command) -> String {
+  let name = cmd.get_name();
+  let func = fn_name(cmd);
+  let mut out = format!(
+    "function {func}_intents\n\
+     \x20   for f in .forge/intents/*.yaml\n\
+     \x20       string replace -r '\\.yaml$' '' -- (basename $f)\n\
+     \x20   end\n\
+     end\n\n\
+     complete -c {name} -f\n"
+  );
+  for arg in options(cmd) {
+    out.push_str(&fish_option(name, &func, "", arg));
+  }
+  for sub in visible_subcommands(cmd) {
+    out.push_str(&format!(
+      "complete -c {name} -n __fish_use_subcommand -a {} -d {}\n",
+      sub.get_name(),
+      fish_quote(&about(sub))
+    ));
+  }
+  for sub in visible_subcommands(cmd) {
+    let cond = format!(
+      " -n {}",
+      fish_quote(&format!("__fish_seen_subcommand_from {}", sub.get_name()))
+    );
+    for arg in options(sub).filter(|a| !a.is_global_set()) {
+      out.push_str(&fish_option(name, &func, &cond, arg));
+    }
+    for nested in visible_subcommands(sub) {
+      out.push_str(&format!(
+        "complete -c {name}{cond} -a {} -d {}\n",
+        nested.get_name(),
+        fish_quote(&about(nested))
+      ));
+    }
+    for values in sub.get_positionals().map(possible_values) {
+      if !values.is_empty() {
+        out.push_str(&format!(
+          "complete -c {name}{cond} -a {}\n",
+          fish_quote(&values.join(" "))
+        ));
+      }
+    }
+    if takes_intent_ids(sub) {
+      out.push_str(&format!("complete -c {name}{cond} -a '({func}_intents)'\n"));
+    }
+  }
+  out
+}
+
+fn fish_option(name: &str, func: &str, cond: &str, arg: &Arg) -> String {
+  let mut line = format!("complete -c {name}{cond}");
+  if let Some(short) = arg.get_short() {
+    line.push_str(&format!(" -s {short}"));
+  }
+  if let Some(long) = arg.get_long() {
+    line.push_str(&format!(" -l {long}"));
+  }
+  if takes_value(arg) {
+    let values = possible_values(arg);
+    if !values.is_empty() {
+      line.push_str(&format!(" -x -a {}", fish_quote(&values.join(" "))));
+    } else if takes_intent_id_value(arg) {
+      line.push_str(&format!(" -x -a '({func}_intents)'"));
+    } else if matches!(
+      arg.get_value_hint(),
+      ValueHint::FilePath | ValueHint::DirPath | ValueHint::AnyPath
+    ) {
+      line.push_str(" -r -F");
+    } else {
+      line.push_str(" -x");
+    }
+  }
+  if let Some(help) = arg.get_help() {
+    line.push_str(&format!(
+      " -d {}",
+      fish_quote(&first_line(&help.to_string()))
+    ));
+  }
+  line.push('\n');
+  line
+}
+
+fn about(cmd: &Command) -> String {
+  cmd
+    .get_about()
+    .map(|a| first_line(&a.to_string()))
+    .unwrap_or_default()
+}
+
+fn first_line(text: &str) -> String {
+  text.lines().next().unwrap_or_default().to_string()
+}
+
+fn fish_quote(text: &str) -> String {
+  format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cli() -> Command {
+    Command::new("forge")
+      .arg(
+        Arg::new("config")
+          .short('c')
+          .long("config")
+          .value_hint(ValueHint::FilePath)
+          .help("Path to config file"),
+      )
+      .subcommand(
+        Command::new("approve")
+          .about("Approve intents")
+          .arg(Arg::new("ids")),
+      )
+      .subcommand(
+        Command::new("run")
+          .about("Process intents")
+          .arg(Arg::new("intent").long("intent").help("Intent to run")),
+      )
+      .subcommand(
+        Command::new("history").about("Show history").arg(
+          Arg::new("format")
+            .long("format")
+            .value_parser(["text", "json"])
+            .help("Output format"),
+        ),
+      )
+      .subcommand(
+        Command::new("completions")
+          .about("Print a completion script")
+          .arg(Arg::new("shell").value_parser(["bash", "fish"])),
+      )
+      .subcommand(
+        Command::new("prompts")
+          .about("Inspect prompts")
+          .subcommand(Command::new("show").about("Show a prompt")),
+      )
+  }
+
+  #[test]
+  fn bashはサブコマンドと値とintent_idを補完する() {
+    let script = generate(Shell::Bash, &cli());
+    assert!(script.contains("-c|--config) ((i++)) ;;"));
+    assert!(script.contains("approve run history completions prompts -c --config -h --help"));
+    assert!(
+      script.contains("    completions)\n      COMPREPLY=($(compgen -W \"bash fish -h --help\"")
+    );
+    assert!(
+      script.contains("--format) COMPREPLY=($(compgen -W \"text json\" -- \"$cur\")); return ;;")
+    );
+    assert!(script.contains("-c|--config) COMPREPLY=($(compgen -f -- \"$cur\")); return ;;"));
+    assert!(script.contains("COMPREPLY=($(compgen -W \"$(_forge_intents)\" -- \"$cur\"))"));
+    assert!(script.contains(
+      "--intent) COMPREPLY=($(compgen -W \"$(_forge_intents)\" -- \"$cur\")); return ;;"
+    ));
+    assert!(script.contains("        show) COMPREPLY"));
+    assert!(script.ends_with("complete -F _forge forge\n"));
+  }
+
+  #[test]
+  fn bashの補完スクリプトは構文として正しい() {
+    let script = generate(Shell::Bash, &cli());
+    let Ok(output) = std::process::Command::new("bash")
+      .args(["-n", "-c", &script])
+      .output()
+    else {
+      return;
+    };
+    assert!(
+      output.status.success(),
+      "{}",
+      String::from_utf8_lossy(&output.stderr)
+    );
+  }
+
+  #[test]
+  fn zshはbash互換層で同じ関数を使う() {
+    let script = generate(Shell::Zsh, &cli());
+    assert!(script.starts_with("#compdef forge\nautoload -U +X bashcompinit && bashcompinit\n"));
+    assert!(script.contains("complete -F _forge forge"));
+  }
+
+  #[test]
+  fn fishはサブコマンドごとの条件で補完する() {
+    let script = generate(Shell::Fish, &cli());
+    assert!(script.contains("complete -c forge -s c -l config -r -F -d 'Path to config file'\n"));
+    assert!(script
+      .contains("complete -c forge -n __fish_use_subcommand -a approve -d 'Approve intents'\n"));
+    assert!(script.contains(
+      "complete -c forge -n '__fish_seen_subcommand_from history' -l format -x -a 'text json' -d 'Output format'\n"
+    ));
+    assert!(script.contains(
+      "complete -c forge -n '__fish_seen_subcommand_from approve' -a '(_forge_intents)'\n"
+    ));
+    assert!(script.contains(
+      "-n '__fish_seen_subcommand_from run' -l intent -x -a '(_forge_intents)' -d 'Intent to run'\n"
+    ));
+    assert!(
+      script.contains("-n '__fish_seen_subcommand_from prompts' -a show -d 'Show a prompt'\n")
+    );
+    assert!(script.contains("-n '__fish_seen_subcommand_from completions' -a 'bash fish'\n"));
+  }
+}
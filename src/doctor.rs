@@ -0,0 +1,272 @@
+//! `pfl-forge doctor`: checks the tools and directories a run depends on and
+//! says how to fix what is missing, before a run fails halfway through.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::process;
+
+/// Oldest git with `git worktree remove`.
+const MIN_GIT_VERSION: (u32, u32) = (2, 17);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+  Pass,
+  /// Could not be confirmed, but a run may still work.
+  Warn,
+  Fail,
+}
+
+impl Status {
+  pub fn label(self) -> &'static str {
+    match self {
+      Status::Pass => "PASS",
+      Status::Warn => "WARN",
+      Status::Fail => "FAIL",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+  pub name: &'static str,
+  pub status: Status,
+  pub detail: String,
+  /// How to fix a warning or failure.
+  pub hint: Option<String>,
+}
+
+impl Check {
+  fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+    Self {
+      name,
+      status: Status::Pass,
+      detail: detail.into(),
+      hint: None,
+    }
+  }
+
+  fn problem(
+    name: &'static str,
+    status: Status,
+    detail: impl Into<String>,
+    hint: impl Into<String>,
+  ) -> Self {
+    Self {
+      name,
+      status,
+      detail: detail.into(),
+      hint: Some(hint.into()),
+    }
+  }
+}
+
+/// Run every check. Later checks use the defaults when the config does not load.
+pub fn run(config_path: &Path, repo_path: &Path) -> Vec<Check> {
+  let (config_check, config) = match Config::load(config_path) {
+    Ok(config) => (
+      Check::pass("config", config_path.display().to_string()),
+      config,
+    ),
+    Err(e) => (
+      Check::problem(
+        "config",
+        Status::Fail,
+        e.to_string(),
+        "run `pfl-forge init`, or fix the file (see pfl-forge.yaml.example)",
+      ),
+      serde_yaml::from_str("{}").expect("empty config"),
+    ),
+  };
+
+  let mut checks = vec![
+    config_check,
+    claude_cli(&config.claude.bin),
+    claude_auth(),
+    git_version(),
+    git_worktree(repo_path),
+  ];
+  if let Some(sandbox) = &config.claude.sandbox {
+    checks.push(container_runtime(&sandbox.runtime));
+  }
+  checks.push(writable("state dir", &repo_path.join(".forge")));
+  checks.push(writable(
+    "worktree dir",
+    &repo_path.join(&config.worktree_dir),
+  ));
+  checks
+}
+
+fn claude_cli(bin: &str) -> Check {
+  match version_output(bin, &["--version"]) {
+    Some(version) => Check::pass("claude CLI", version),
+    None => Check::problem(
+      "claude CLI",
+      Status::Fail,
+      format!("`{bin} --version` failed"),
+      "install it with `npm install -g @anthropic-ai/claude-code`, or set claude.bin",
+    ),
+  }
+}
+
+/// API key or OAuth token in the environment, or a stored login. Logins kept
+/// in the macOS keychain are not visible here, hence only a warning.
+fn claude_auth() -> Check {
+  for var in ["ANTHROPIC_API_KEY", "CLAUDE_CODE_OAUTH_TOKEN"] {
+    if std::env::var(var).is_ok_and(|v| !v.is_empty()) {
+      return Check::pass("claude auth", format!("{var} is set"));
+    }
+  }
+  let credentials = std::env::var_os("HOME").map(|home| {
+    PathBuf::from(home)
+      .join(".claude")
+      .join(".credentials.json")
+  });
+  match credentials {
+    Some(path) if path.exists() => Check::pass("claude auth", path.display().to_string()),
+    _ => Check::problem(
+      "claude auth",
+      Status::Warn,
+      "no API key, OAuth token or stored login found",
+      "run `claude` and log in with /login, or set ANTHROPIC_API_KEY",
+    ),
+  }
+}
+
+fn git_version() -> Check {
+  let Some(output) = version_output("git", &["--version"]) else {
+    return Check::problem("git", Status::Fail, "`git --version` failed", "install git");
+  };
+  match parse_git_version(&output) {
+    Some(version) if version >= MIN_GIT_VERSION => Check::pass("git", output),
+    Some(_) => Check::problem(
+      "git",
+      Status::Fail,
+      output,
+      format!(
+        "upgrade git to {}.{} or later (needed for `git worktree remove`)",
+        MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+      ),
+    ),
+    None => Check::problem(
+      "git",
+      Status::Warn,
+      format!("unrecognised version: {output}"),
+      format!(
+        "make sure git is {}.{} or later",
+        MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+      ),
+    ),
+  }
+}
+
+fn git_worktree(repo_path: &Path) -> Check {
+  let ok = process::command("git")
+    .args(["worktree", "list", "--porcelain"])
+    .current_dir(repo_path)
+    .output()
+    .is_ok_and(|o| o.status.success());
+  if ok {
+    Check::pass("git worktree", repo_path.display().to_string())
+  } else {
+    Check::problem(
+      "git worktree",
+      Status::Fail,
+      format!("{} is not a git repository", repo_path.display()),
+      "run pfl-forge from the root of the repository it works on",
+    )
+  }
+}
+
+fn container_runtime(runtime: &str) -> Check {
+  let ok = process::command(runtime)
+    .arg("version")
+    .output()
+    .is_ok_and(|o| o.status.success());
+  if ok {
+    Check::pass("sandbox runtime", format!("{runtime} is running"))
+  } else {
+    Check::problem(
+      "sandbox runtime",
+      Status::Fail,
+      format!("`{runtime} version` failed"),
+      format!("claude.sandbox is set: install and start {runtime}, or remove claude.sandbox"),
+    )
+  }
+}
+
+/// Creates and removes a probe file in `dir`, or in its nearest existing
+/// ancestor when `dir` will only be created by the first run.
+fn writable(name: &'static str, dir: &Path) -> Check {
+  let existing = dir.ancestors().find(|p| p.is_dir()).unwrap_or(dir);
+  let probe = existing.join(format!(".pfl-forge-doctor-{}", std::process::id()));
+  match std::fs::write(&probe, b"") {
+    Ok(()) => {
+      let _ = std::fs::remove_file(&probe);
+      Check::pass(name, dir.display().to_string())
+    }
+    Err(e) => Check::problem(
+      name,
+      Status::Fail,
+      format!("{}: {e}", existing.display()),
+      format!("make {} writable by this user", existing.display()),
+    ),
+  }
+}
+
+/// First line of `program args` stdout, when it exits successfully.
+fn version_output(program: &str, args: &[&str]) -> Option<String> {
+  let output = process::command(program).args(args).output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  Some(stdout.lines().next().unwrap_or_default().trim().to_string())
+}
+
+/// `(major, minor)` from `git version 2.39.3 (Apple Git-145)`.
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+  let version = output.strip_prefix("git version ")?;
+  let mut parts = version.split(['.', ' ']);
+  let major = parts.next()?.parse().ok()?;
+  let minor = parts.next()?.parse().ok()?;
+  Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn gitのバージョン文字列からメジャーとマイナーを読む() {
+    assert_eq!(
+      parse_git_version("git version 2.39.3 (Apple Git-145)"),
+      Some((2, 39))
+    );
+    assert_eq!(parse_git_version("git version 2.17.1"), Some((2, 17)));
+    assert_eq!(parse_git_version("hub version 2.14"), None);
+    assert!(parse_git_version("git version 2.9.5").unwrap() < MIN_GIT_VERSION);
+  }
+
+  #[test]
+  fn 未作成のディレクトリは既存の親で書き込みを確かめる() {
+    let dir = tempfile::tempdir().unwrap();
+    let check = writable("state dir", &dir.path().join(".forge").join("worktrees"));
+    assert_eq!(check.status, Status::Pass);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+  }
+
+  #[test]
+  fn 設定がなくても残りの確認を続ける() {
+    let dir = tempfile::tempdir().unwrap();
+    let checks = run(&dir.path().join("pfl-forge.yaml"), dir.path());
+    assert_eq!(checks[0].name, "config");
+    assert_eq!(checks[0].status, Status::Fail);
+    assert!(checks
+      .iter()
+      .any(|c| c.name == "git worktree" && c.status == Status::Fail));
+    assert!(checks
+      .iter()
+      .any(|c| c.name == "state dir" && c.status == Status::Pass));
+  }
+}
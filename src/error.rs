@@ -17,9 +17,19 @@ pub enum ForgeError {
   #[error("parse error: {0}")]
   Parse(String),
 
+  #[error("check error: {0}")]
+  Check(String),
+
+  /// Agent-chosen settings that the repository does not allow.
+  #[error("policy violation: {0}")]
+  PolicyViolation(String),
+
   #[error("timeout: {0}")]
   Timeout(String),
 
+  #[error("run aborted: {0}")]
+  RunAborted(String),
+
   #[error("io error: {0}")]
   Io(#[from] std::io::Error),
 
@@ -31,3 +41,138 @@ pub enum ForgeError {
 }
 
 pub type Result<T> = std::result::Result<T, ForgeError>;
+
+/// Coarse error category, mapped to a process exit code so wrappers and CI
+/// can tell a config typo from a Claude outage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+  UserConfig,
+  Environment,
+  TransientNetwork,
+  AgentFailure,
+  PolicyViolation,
+}
+
+impl ErrorCategory {
+  pub fn exit_code(self) -> i32 {
+    match self {
+      ErrorCategory::UserConfig => 2,
+      ErrorCategory::Environment => 3,
+      ErrorCategory::TransientNetwork => 4,
+      ErrorCategory::AgentFailure => 5,
+      ErrorCategory::PolicyViolation => 6,
+    }
+  }
+}
+
+impl ForgeError {
+  pub fn category(&self) -> ErrorCategory {
+    match self {
+      ForgeError::ConfigNotFound(_) | ForgeError::Config(_) | ForgeError::Yaml(_) => {
+        ErrorCategory::UserConfig
+      }
+      ForgeError::Git(_) | ForgeError::Check(_) | ForgeError::Io(_) | ForgeError::RunAborted(_) => {
+        ErrorCategory::Environment
+      }
+      ForgeError::PolicyViolation(_) => ErrorCategory::PolicyViolation,
+      ForgeError::Claude(_) if self.is_transient() => ErrorCategory::TransientNetwork,
+      // An agent that ran past its time budget failed; waiting and retrying won't help
+      ForgeError::Timeout(_)
+      | ForgeError::Claude(_)
+      | ForgeError::Parse(_)
+      | ForgeError::Json(_) => ErrorCategory::AgentFailure,
+    }
+  }
+
+  /// Failures worth retrying as-is: API overload and rate limits, dropped
+  /// connections, and an empty response. Timeouts, spawn errors and other
+  /// non-zero exits are deterministic enough that a retry would only burn time.
+  pub fn is_transient(&self) -> bool {
+    let ForgeError::Claude(message) = self else {
+      return false;
+    };
+    let lower = message.to_lowercase();
+    [
+      "overloaded",
+      "529",
+      "503",
+      "502",
+      "rate_limit",
+      "rate limit",
+      "econnreset",
+      "etimedout",
+      "socket hang up",
+      "connection reset",
+      "connection error",
+      "network error",
+      "empty output",
+    ]
+    .iter()
+    .any(|marker| lower.contains(marker))
+  }
+
+  pub fn exit_code(&self) -> i32 {
+    self.category().exit_code()
+  }
+
+  /// Single-line JSON for stderr: `{"error":{"category":..,"exit_code":..,"message":..}}`.
+  pub fn to_json(&self) -> String {
+    serde_json::json!({
+      "error": {
+        "category": self.category(),
+        "exit_code": self.exit_code(),
+        "message": self.to_string(),
+      }
+    })
+    .to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn config系エラーはuser_configに分類される() {
+    let err = ForgeError::Config("bad".into());
+    assert_eq!(err.category(), ErrorCategory::UserConfig);
+    assert_eq!(err.exit_code(), 2);
+  }
+
+  #[test]
+  fn 一時的なclaudeエラーだけがtransient_networkになる() {
+    let agent = ForgeError::Claude("exit 1".into());
+    let overloaded = ForgeError::Claude("API error: overloaded_error".into());
+    assert_eq!(agent.category(), ErrorCategory::AgentFailure);
+    assert_eq!(overloaded.category(), ErrorCategory::TransientNetwork);
+    assert_ne!(agent.exit_code(), overloaded.exit_code());
+  }
+
+  #[test]
+  fn 時間切れのエージェントはagent_failureに分類される() {
+    let timeout = ForgeError::Timeout("timed out after 1200s".into());
+    assert_eq!(timeout.category(), ErrorCategory::AgentFailure);
+    assert_eq!(timeout.exit_code(), 5);
+  }
+
+  #[test]
+  fn ポリシー違反は専用のexit_codeになる() {
+    let err = ForgeError::PolicyViolation("checks.dir escapes the worktree: ..".into());
+    assert_eq!(err.category(), ErrorCategory::PolicyViolation);
+    assert_eq!(err.exit_code(), 6);
+    assert_ne!(
+      err.exit_code(),
+      ForgeError::Check("not found".into()).exit_code()
+    );
+  }
+
+  #[test]
+  fn jsonにカテゴリとメッセージが含まれる() {
+    let err = ForgeError::Git("rebase failed".into());
+    let json: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+    assert_eq!(json["error"]["category"], "environment");
+    assert_eq!(json["error"]["exit_code"], 3);
+    assert_eq!(json["error"]["message"], "git error: rebase failed");
+  }
+}
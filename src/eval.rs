@@ -263,6 +263,11 @@ pub fn eval_review(
     implementation_steps: vec![],
     context: String::new(),
     depends_on: vec![],
+    checks: None,
+    implement_failures: 0,
+    rebase_conflicts: 0,
+    errors: vec![],
+    retries_exhausted: false,
   };
 
   info!("eval review: running fixture '{fixture_name}'");
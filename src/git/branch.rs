@@ -1,12 +1,12 @@
 use std::path::Path;
-use std::process::Command;
 
 use tracing::{info, warn};
 
 use crate::error::{ForgeError, Result};
+use crate::process;
 
 pub fn commit_count(repo_path: &Path, base_branch: &str, branch: &str) -> Result<u32> {
-  let output = Command::new("git")
+  let output = process::command("git")
     .args([
       "rev-list",
       "--count",
@@ -26,9 +26,49 @@ pub fn commit_count(repo_path: &Path, base_branch: &str, branch: &str) -> Result
     .map_err(|e| ForgeError::Git(format!("failed to parse commit count: {e}")))
 }
 
-pub fn rebase(worktree_path: &Path, base_branch: &str) -> Result<()> {
+/// Abbreviated SHA of the worktree's HEAD.
+/// `git diff <from> HEAD` in the worktree.
+pub fn diff_since(worktree_path: &Path, from: &str) -> Result<String> {
+  let output = process::command("git")
+    .args(["diff", from, "HEAD"])
+    .current_dir(worktree_path)
+    .output()?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("diff failed: {stderr}")));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn head_commit(worktree_path: &Path) -> Result<String> {
+  let output = process::command("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .current_dir(worktree_path)
+    .output()?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("rev-parse failed: {stderr}")));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// State of a rebase that is left stopped on conflicts instead of aborted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebaseState {
+  Done,
+  /// Stopped while replaying a commit; holds the unmerged paths.
+  Conflicted(Vec<String>),
+}
+
+/// Rebase onto `origin/<base_branch>`, stopping (not aborting) on conflicts
+/// so they can be resolved in place and continued with [`continue_rebase`].
+pub fn start_rebase(worktree_path: &Path, base_branch: &str) -> Result<RebaseState> {
   info!("fetching origin/{base_branch}");
-  let fetch = Command::new("git")
+  let fetch = process::command("git")
     .args(["fetch", "origin", base_branch])
     .current_dir(worktree_path)
     .output()?;
@@ -39,27 +79,85 @@ pub fn rebase(worktree_path: &Path, base_branch: &str) -> Result<()> {
   }
 
   info!("rebasing onto origin/{base_branch}");
-  let rebase = Command::new("git")
+  let rebase = process::command("git")
     .args(["rebase", &format!("origin/{base_branch}")])
     .current_dir(worktree_path)
     .output()?;
+  rebase_state(worktree_path, &rebase)
+}
 
-  if !rebase.status.success() {
-    let stderr = String::from_utf8_lossy(&rebase.stderr);
-    // Abort the failed rebase
-    let _ = Command::new("git")
-      .args(["rebase", "--abort"])
+/// Continue a stopped rebase after its conflicts have been resolved and staged.
+pub fn continue_rebase(worktree_path: &Path) -> Result<RebaseState> {
+  let output = process::command("git")
+    .args(["rebase", "--continue"])
+    .env("GIT_EDITOR", "true")
+    .current_dir(worktree_path)
+    .output()?;
+  rebase_state(worktree_path, &output)
+}
+
+pub fn abort_rebase(worktree_path: &Path) {
+  let _ = process::command("git")
+    .args(["rebase", "--abort"])
+    .current_dir(worktree_path)
+    .output();
+}
+
+/// Paths with unresolved merge conflicts in the worktree.
+pub fn conflicted_files(worktree_path: &Path) -> Result<Vec<String>> {
+  let output = process::command("git")
+    .args(["diff", "--name-only", "--diff-filter=U"])
+    .current_dir(worktree_path)
+    .output()?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("diff failed: {stderr}")));
+  }
+
+  Ok(
+    String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .map(String::from)
+      .collect(),
+  )
+}
+
+/// One-line summaries of both sides of a stopped rebase: the base branch commits
+/// the task is being replayed onto, and the task commit being replayed.
+pub fn rebase_sides(worktree_path: &Path) -> Result<(String, String)> {
+  let log = |args: &[&str]| -> Result<String> {
+    let output = process::command("git")
+      .args(args)
       .current_dir(worktree_path)
-      .output();
+      .output()?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(ForgeError::Git(format!("log failed: {stderr}")));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+  };
+  let base = log(&["log", "--oneline", "HEAD", "--not", "REBASE_HEAD"])?;
+  let task = log(&["show", "--stat", "--format=%h %s%n%n%b", "REBASE_HEAD"])?;
+  Ok((base, task))
+}
+
+fn rebase_state(worktree_path: &Path, output: &std::process::Output) -> Result<RebaseState> {
+  if output.status.success() {
+    return Ok(RebaseState::Done);
+  }
+  let conflicts = conflicted_files(worktree_path)?;
+  if conflicts.is_empty() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    abort_rebase(worktree_path);
     return Err(ForgeError::Git(format!("rebase failed: {stderr}")));
   }
-
-  Ok(())
+  Ok(RebaseState::Conflicted(conflicts))
 }
 
 pub fn delete(repo_path: &Path, branch: &str) -> Result<()> {
   info!("deleting branch {branch}");
-  let output = Command::new("git")
+  let output = process::command("git")
     .args(["branch", "-D", branch])
     .current_dir(repo_path)
     .output()?;
@@ -74,7 +172,7 @@ pub fn delete(repo_path: &Path, branch: &str) -> Result<()> {
 
 /// Get commit messages on the feature branch (relative to base branch).
 pub fn commit_messages(worktree_path: &Path, base_branch: &str) -> Result<Vec<String>> {
-  let output = Command::new("git")
+  let output = process::command("git")
     .args(["log", "--format=%s", &format!("origin/{base_branch}..HEAD")])
     .current_dir(worktree_path)
     .output()?;
@@ -90,14 +188,211 @@ pub fn commit_messages(worktree_path: &Path, base_branch: &str) -> Result<Vec<St
   Ok(messages)
 }
 
-/// Rebase onto base branch. Returns Ok(true) on success, Ok(false) on conflict.
-pub fn try_rebase(worktree_path: &Path, base_branch: &str, label: &str) -> Result<bool> {
-  info!("rebasing {label} onto {base_branch}");
-  match rebase(worktree_path, base_branch) {
-    Ok(()) => Ok(true),
-    Err(e) => {
-      warn!("rebase conflict for {label}: {e}");
-      Ok(false)
+/// Stage and commit all changes in the worktree. Returns Ok(false) if there was nothing to commit.
+pub fn commit_all(worktree_path: &Path, message: &str) -> Result<bool> {
+  let status = process::command("git")
+    .args(["status", "--porcelain"])
+    .current_dir(worktree_path)
+    .output()?;
+  if !status.status.success() {
+    let stderr = String::from_utf8_lossy(&status.stderr);
+    return Err(ForgeError::Git(format!("status failed: {stderr}")));
+  }
+  if status.stdout.is_empty() {
+    return Ok(false);
+  }
+
+  let add = process::command("git")
+    .args(["add", "-A"])
+    .current_dir(worktree_path)
+    .output()?;
+  if !add.status.success() {
+    let stderr = String::from_utf8_lossy(&add.stderr);
+    return Err(ForgeError::Git(format!("add failed: {stderr}")));
+  }
+
+  let commit = process::command("git")
+    .args(["commit", "-m", message])
+    .current_dir(worktree_path)
+    .output()?;
+  if !commit.status.success() {
+    let stderr = String::from_utf8_lossy(&commit.stderr);
+    return Err(ForgeError::Git(format!("commit failed: {stderr}")));
+  }
+  Ok(true)
+}
+
+/// Commits on the feature branch whose author differs from `name` / `email`, or
+/// that are unsigned when `signed` is required. `None` means "don't care".
+pub fn mismatched_commits(
+  worktree_path: &Path,
+  base_branch: &str,
+  name: Option<&str>,
+  email: Option<&str>,
+  signed: bool,
+) -> Result<Vec<String>> {
+  let output = process::command("git")
+    .args([
+      "log",
+      "--format=%H%x1f%an%x1f%ae%x1f%G?",
+      &format!("origin/{base_branch}..HEAD"),
+    ])
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("log failed: {stderr}")));
+  }
+
+  let mismatched = String::from_utf8_lossy(&output.stdout)
+    .lines()
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split('\x1f').collect();
+      let [sha, author, author_email, signature] = fields[..] else {
+        return None;
+      };
+      let wrong = name.is_some_and(|n| n != author)
+        || email.is_some_and(|e| e != author_email)
+        || (signed && signature == "N");
+      wrong.then(|| sha.to_string())
+    })
+    .collect();
+  Ok(mismatched)
+}
+
+/// Re-commit every commit on the feature branch with the worktree's configured
+/// identity (and signing), keeping messages and content.
+pub fn reset_authorship(worktree_path: &Path, base_branch: &str) -> Result<()> {
+  let merge_base = process::command("git")
+    .args(["merge-base", "HEAD", &format!("origin/{base_branch}")])
+    .current_dir(worktree_path)
+    .output()?;
+  if !merge_base.status.success() {
+    let stderr = String::from_utf8_lossy(&merge_base.stderr);
+    return Err(ForgeError::Git(format!("merge-base failed: {stderr}")));
+  }
+  let merge_base = String::from_utf8_lossy(&merge_base.stdout)
+    .trim()
+    .to_string();
+
+  info!("resetting commit authorship onto {merge_base}");
+  let rebase = process::command("git")
+    .args([
+      "rebase",
+      "--exec",
+      "git commit --amend --no-edit --allow-empty --reset-author",
+      &merge_base,
+    ])
+    .current_dir(worktree_path)
+    .output()?;
+  if !rebase.status.success() {
+    let stderr = String::from_utf8_lossy(&rebase.stderr);
+    let _ = process::command("git")
+      .args(["rebase", "--abort"])
+      .current_dir(worktree_path)
+      .output();
+    return Err(ForgeError::Git(format!(
+      "authorship rewrite failed: {stderr}"
+    )));
+  }
+  Ok(())
+}
+
+/// Rewrite the subject lines of the commits on the feature branch. `rewrite` gets
+/// each subject and returns the replacement, or `None` to keep it. Trees, bodies
+/// and authors are preserved; the branch is left untouched when nothing changes.
+/// Returns the number of rewritten commits.
+pub fn rewrite_subjects(
+  worktree_path: &Path,
+  base_branch: &str,
+  sign: bool,
+  rewrite: impl Fn(&str) -> Option<String>,
+) -> Result<usize> {
+  let git = |args: &[&str], envs: &[(&str, &str)]| -> Result<String> {
+    let output = process::command("git")
+      .args(args)
+      .envs(envs.iter().copied())
+      .current_dir(worktree_path)
+      .output()?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(ForgeError::Git(format!("git {} failed: {stderr}", args[0])));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+  };
+
+  let range = format!("origin/{base_branch}..HEAD");
+  let commits = git(&["rev-list", "--reverse", "--parents", &range], &[])?;
+  let commits: Vec<Vec<&str>> = commits.lines().map(|l| l.split(' ').collect()).collect();
+  if commits.iter().any(|c| c.len() != 2) {
+    warn!("skipping commit message rewrite: branch contains merge commits");
+    return Ok(0);
+  }
+
+  let head = git(&["rev-parse", "HEAD"], &[])?;
+  let mut parent = match commits.first() {
+    Some(first) => first[1].to_string(),
+    None => return Ok(0),
+  };
+  let mut rewritten = 0;
+  for commit in &commits {
+    let sha = commit[0];
+    let info = git(&["log", "-1", "--format=%an%x1f%ae%x1f%aI%x1f%B", sha], &[])?;
+    let [name, email, date, message]: [&str; 4] = info
+      .splitn(4, '\x1f')
+      .collect::<Vec<_>>()
+      .try_into()
+      .map_err(|_| ForgeError::Git(format!("unexpected log output for {sha}")))?;
+    let message = message.trim_end();
+    let (subject, body) = message.split_once('\n').unwrap_or((message, ""));
+
+    let new_subject = rewrite(subject);
+    if new_subject.is_none() && rewritten == 0 {
+      parent = sha.to_string();
+      continue;
+    }
+    let new_message = match &new_subject {
+      Some(s) if body.is_empty() => s.clone(),
+      Some(s) => format!("{s}\n{body}"),
+      None => message.to_string(),
+    };
+    if new_subject.is_some() {
+      rewritten += 1;
+    }
+
+    let tree = format!("{sha}^{{tree}}");
+    let mut args = vec![
+      "commit-tree",
+      tree.as_str(),
+      "-p",
+      parent.as_str(),
+      "-m",
+      &new_message,
+    ];
+    if sign {
+      args.push("-S");
     }
+    let envs = [
+      ("GIT_AUTHOR_NAME", name),
+      ("GIT_AUTHOR_EMAIL", email),
+      ("GIT_AUTHOR_DATE", date),
+    ];
+    parent = git(&args, &envs)?.trim().to_string();
+  }
+
+  if rewritten > 0 {
+    info!("rewrote {rewritten} commit message(s)");
+    git(
+      &[
+        "update-ref",
+        "-m",
+        "pfl-forge: apply commit_template",
+        "HEAD",
+        &parent,
+        head.trim(),
+      ],
+      &[],
+    )?;
   }
+  Ok(rewritten)
 }
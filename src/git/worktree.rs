@@ -1,19 +1,22 @@
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 use tracing::{debug, info};
 
 use crate::error::{self, ForgeError, Result};
+use crate::process;
 
 pub fn path_for(repo_path: &Path, worktree_dir: &str, branch: &str) -> PathBuf {
   repo_path.join(worktree_dir).join(branch)
 }
 
+/// Create the worktree for `branch`. When `sparse_paths` is non-empty the checkout
+/// is limited to those directories (cone mode) before any files are written.
 pub fn create(
   repo_path: &Path,
   worktree_dir: &str,
   branch: &str,
   base_branch: &str,
+  sparse_paths: &[String],
 ) -> Result<PathBuf> {
   let worktree_path = repo_path.join(worktree_dir).join(branch);
 
@@ -28,7 +31,7 @@ pub fn create(
 
   // Fetch latest base branch
   debug!("fetching latest {base_branch}");
-  let fetch_output = Command::new("git")
+  let fetch_output = process::command("git")
     .args(["fetch", "origin", base_branch])
     .current_dir(repo_path)
     .output()?;
@@ -39,10 +42,15 @@ pub fn create(
   }
 
   info!("creating worktree: {}", worktree_path.display());
-  let output = Command::new("git")
+  let no_checkout = if sparse_paths.is_empty() {
+    None
+  } else {
+    Some("--no-checkout")
+  };
+  let output = process::command("git")
+    .args(["worktree", "add"])
+    .args(no_checkout)
     .args([
-      "worktree",
-      "add",
       "-b",
       branch,
       worktree_path.to_str().unwrap(),
@@ -56,8 +64,10 @@ pub fn create(
     // Branch may already exist, try without -b
     if stderr.contains("already exists") {
       debug!("branch {branch} already exists, creating worktree without -b");
-      let output2 = Command::new("git")
-        .args(["worktree", "add", worktree_path.to_str().unwrap(), branch])
+      let output2 = process::command("git")
+        .args(["worktree", "add"])
+        .args(no_checkout)
+        .args([worktree_path.to_str().unwrap(), branch])
         .current_dir(repo_path)
         .output()?;
 
@@ -70,13 +80,42 @@ pub fn create(
     }
   }
 
+  if !sparse_paths.is_empty() {
+    checkout_sparse(&worktree_path, sparse_paths)?;
+  }
+
   Ok(worktree_path)
 }
 
+fn checkout_sparse(worktree_path: &Path, sparse_paths: &[String]) -> Result<()> {
+  info!("sparse-checkout: {}", sparse_paths.join(", "));
+  let output = process::command("git")
+    .args(["sparse-checkout", "set", "--cone"])
+    .args(sparse_paths)
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!(
+      "sparse-checkout set failed: {stderr}"
+    )));
+  }
+
+  let output = process::command("git")
+    .args(["checkout"])
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("checkout failed: {stderr}")));
+  }
+  Ok(())
+}
+
 pub fn remove(repo_path: &Path, worktree_path: &Path) -> Result<()> {
   info!("removing worktree: {}", worktree_path.display());
 
-  let output = Command::new("git")
+  let output = process::command("git")
     .args([
       "worktree",
       "remove",
@@ -95,7 +134,7 @@ pub fn remove(repo_path: &Path, worktree_path: &Path) -> Result<()> {
 }
 
 pub fn list(repo_path: &Path) -> Result<Vec<String>> {
-  let output = Command::new("git")
+  let output = process::command("git")
     .args(["worktree", "list", "--porcelain"])
     .current_dir(repo_path)
     .output()?;
@@ -115,6 +154,24 @@ pub fn list(repo_path: &Path) -> Result<Vec<String>> {
   Ok(worktrees)
 }
 
+/// The repository's shared git directory (the main `.git`), which a linked
+/// worktree needs alongside it to commit.
+pub fn common_dir(worktree_path: &Path) -> Result<PathBuf> {
+  let output = process::command("git")
+    .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+    .current_dir(worktree_path)
+    .output()?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!("rev-parse failed: {stderr}")));
+  }
+
+  Ok(PathBuf::from(
+    String::from_utf8_lossy(&output.stdout).trim(),
+  ))
+}
+
 pub fn ensure_gitignore_forge(worktree_path: &Path) -> error::Result<()> {
   let gitignore = worktree_path.join(".gitignore");
   if gitignore.exists() {
@@ -129,3 +186,66 @@ pub fn ensure_gitignore_forge(worktree_path: &Path) -> error::Result<()> {
   }
   Ok(())
 }
+
+/// Initialize submodules in a freshly created worktree.
+pub fn init_submodules(worktree_path: &Path) -> Result<()> {
+  info!("initializing submodules: {}", worktree_path.display());
+  let output = process::command("git")
+    .args(["submodule", "update", "--init", "--recursive"])
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Git(format!(
+      "submodule update failed: {stderr}"
+    )));
+  }
+  Ok(())
+}
+
+/// Whether the checkout tracks files with Git LFS, per the root `.gitattributes`.
+pub fn uses_lfs(worktree_path: &Path) -> bool {
+  std::fs::read_to_string(worktree_path.join(".gitattributes"))
+    .map(|content| content.lines().any(|line| line.contains("filter=lfs")))
+    .unwrap_or(false)
+}
+
+/// Replace LFS pointer files in the worktree with their content.
+pub fn pull_lfs(worktree_path: &Path) -> Result<()> {
+  info!("pulling lfs objects: {}", worktree_path.display());
+  for args in [["lfs", "install", "--local"], ["lfs", "pull", "origin"]] {
+    let output = process::command("git")
+      .args(args)
+      .current_dir(worktree_path)
+      .output()?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(ForgeError::Git(format!(
+        "git {} failed: {stderr}",
+        args.join(" ")
+      )));
+    }
+  }
+  Ok(())
+}
+
+/// Environment that makes git commit as `name` / `email`, signing when `sign` is set.
+/// It is passed to each process instead of written to git config: linked worktrees
+/// share the main checkout's config, so any setting there would leak into it.
+pub fn identity_env(name: Option<&str>, email: Option<&str>, sign: bool) -> Vec<(String, String)> {
+  let mut vars = Vec::new();
+  if let Some(name) = name {
+    vars.push(("GIT_AUTHOR_NAME".into(), name.into()));
+    vars.push(("GIT_COMMITTER_NAME".into(), name.into()));
+  }
+  if let Some(email) = email {
+    vars.push(("GIT_AUTHOR_EMAIL".into(), email.into()));
+    vars.push(("GIT_COMMITTER_EMAIL".into(), email.into()));
+  }
+  if sign {
+    vars.push(("GIT_CONFIG_COUNT".into(), "1".into()));
+    vars.push(("GIT_CONFIG_KEY_0".into(), "commit.gpgsign".into()));
+    vars.push(("GIT_CONFIG_VALUE_0".into(), "true".into()));
+  }
+  vars
+}
@@ -0,0 +1,415 @@
+use serde::{Deserialize, Serialize};
+
+use crate::intent::registry::IntentStatus;
+use crate::knowledge::history::Outcome;
+use crate::task::WorkStatus;
+
+/// Language for human-facing CLI output and generated reports. Log lines
+/// (`tracing`) are never localized so they stay greppable. Takes plain values;
+/// counting and filtering belong to the modules that own the data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+  #[default]
+  En,
+  Ja,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+  NoIntents,
+  NoApprovedIntents,
+  NoCompletedWorktrees,
+  NoObservations,
+  InboxEmpty,
+  NeedsClarification,
+  Approved,
+  Rejected,
+  NotFound,
+  AllAnswered,
+  NoUnansweredClarifications,
+  Created,
+  Removed,
+  StartedInBackground,
+  Log,
+  Report,
+  Reason,
+  Disabled,
+  Enabled,
+  DisabledInConfig,
+  // Report headings and labels
+  DryRunReport,
+  Audit,
+  Intent,
+  Intents,
+  Generated,
+  Findings,
+  Outcome,
+  Effort,
+  Error,
+  Files,
+  IntentsRun,
+  SuccessRate,
+  Retries,
+  TotalCost,
+  IntentsByStatus,
+  Runs,
+  CostPerDay,
+  NoHistory,
+  Steps,
+  Status,
+  Run,
+  Finished,
+  Title,
+  Duration,
+  Step,
+  Cost,
+  Average,
+  IntentCount,
+  RunCount,
+}
+
+impl Locale {
+  pub fn text(self, msg: Msg) -> &'static str {
+    match self {
+      Locale::En => match msg {
+        Msg::NoIntents => "no intents",
+        Msg::NoApprovedIntents => "no approved intents to process",
+        Msg::NoCompletedWorktrees => "no completed worktrees to clean",
+        Msg::NoObservations => "no observations found",
+        Msg::InboxEmpty => "inbox is empty",
+        Msg::NeedsClarification => "[needs clarification]",
+        Msg::Approved => "approved",
+        Msg::Rejected => "rejected",
+        Msg::NotFound => "not found",
+        Msg::AllAnswered => "all clarifications answered, approved",
+        Msg::NoUnansweredClarifications => "no unanswered clarifications",
+        Msg::Created => "created",
+        Msg::Removed => "removed",
+        Msg::StartedInBackground => "started in background",
+        Msg::Log => "log",
+        Msg::Report => "report",
+        Msg::Reason => "reason",
+        Msg::Disabled => "processing is disabled; run `pfl-forge enable` to resume",
+        Msg::Enabled => "processing enabled",
+        Msg::DisabledInConfig => "still disabled by `enabled: false` in the config",
+        Msg::DryRunReport => "Dry-run report",
+        Msg::Audit => "Audit",
+        Msg::Intent => "Intent",
+        Msg::Intents => "Intents",
+        Msg::Generated => "Generated",
+        Msg::Findings => "Findings",
+        Msg::Outcome => "Outcome",
+        Msg::Effort => "Effort",
+        Msg::Error => "Error",
+        Msg::Files => "Files",
+        Msg::IntentsRun => "intents run",
+        Msg::SuccessRate => "success rate",
+        Msg::Retries => "retries",
+        Msg::TotalCost => "total cost",
+        Msg::IntentsByStatus => "Intents by status",
+        Msg::Runs => "Runs",
+        Msg::CostPerDay => "Cost per day",
+        Msg::NoHistory => "No history yet.",
+        Msg::Steps => "Steps",
+        Msg::Status => "status",
+        Msg::Run => "run",
+        Msg::Finished => "finished",
+        Msg::Title => "title",
+        Msg::Duration => "duration",
+        Msg::Step => "step",
+        Msg::Cost => "cost",
+        Msg::Average => "avg",
+        Msg::IntentCount => "intents",
+        Msg::RunCount => "runs",
+      },
+      Locale::Ja => match msg {
+        Msg::NoIntents => "Intent はありません",
+        Msg::NoApprovedIntents => "処理対象の approved Intent はありません",
+        Msg::NoCompletedWorktrees => "削除対象の worktree はありません",
+        Msg::NoObservations => "Observation は見つかりませんでした",
+        Msg::InboxEmpty => "inbox は空です",
+        Msg::NeedsClarification => "[回答待ち]",
+        Msg::Approved => "承認しました",
+        Msg::Rejected => "却下しました",
+        Msg::NotFound => "見つかりません",
+        Msg::AllAnswered => "すべての質問に回答済み。承認しました",
+        Msg::NoUnansweredClarifications => "未回答の質問はありません",
+        Msg::Created => "作成しました",
+        Msg::Removed => "削除しました",
+        Msg::StartedInBackground => "バックグラウンドで開始しました",
+        Msg::Log => "ログ",
+        Msg::Report => "レポート",
+        Msg::Reason => "理由",
+        Msg::Disabled => "処理は停止中です。再開するには `pfl-forge enable` を実行してください",
+        Msg::Enabled => "処理を再開しました",
+        Msg::DisabledInConfig => "設定の `enabled: false` により引き続き停止中です",
+        Msg::DryRunReport => "Dry-run レポート",
+        Msg::Audit => "監査",
+        Msg::Intent => "Intent",
+        Msg::Intents => "Intent 一覧",
+        Msg::Generated => "生成日時",
+        Msg::Findings => "指摘",
+        Msg::Outcome => "結果",
+        Msg::Effort => "工数",
+        Msg::Error => "エラー",
+        Msg::Files => "ファイル",
+        Msg::IntentsRun => "実行 Intent 数",
+        Msg::SuccessRate => "成功率",
+        Msg::Retries => "リトライ",
+        Msg::TotalCost => "総コスト",
+        Msg::IntentsByStatus => "ステータス別 Intent",
+        Msg::Runs => "実行履歴",
+        Msg::CostPerDay => "日別コスト",
+        Msg::NoHistory => "履歴はまだありません。",
+        Msg::Steps => "ステップ",
+        Msg::Status => "ステータス",
+        Msg::Run => "run",
+        Msg::Finished => "終了",
+        Msg::Title => "タイトル",
+        Msg::Duration => "所要時間",
+        Msg::Step => "ステップ",
+        Msg::Cost => "コスト",
+        Msg::Average => "平均",
+        Msg::IntentCount => "Intent 数",
+        Msg::RunCount => "回数",
+      },
+    }
+  }
+
+  /// Language tag for generated documents (`<html lang>`).
+  pub fn code(self) -> &'static str {
+    match self {
+      Locale::En => "en",
+      Locale::Ja => "ja",
+    }
+  }
+
+  pub fn status_label(self, status: &IntentStatus) -> &'static str {
+    match (self, status) {
+      (Locale::En, IntentStatus::Proposed) => "proposed",
+      (Locale::En, IntentStatus::Approved) => "approved",
+      (Locale::En, IntentStatus::Done) => "done",
+      (Locale::En, IntentStatus::Blocked) => "blocked",
+      (Locale::En, IntentStatus::Error) => "error",
+      (Locale::En, IntentStatus::Rejected) => "rejected",
+      (Locale::En, IntentStatus::SetupFailure) => "setup failure",
+      (Locale::Ja, IntentStatus::Proposed) => "提案",
+      (Locale::Ja, IntentStatus::Approved) => "承認済み",
+      (Locale::Ja, IntentStatus::Done) => "完了",
+      (Locale::Ja, IntentStatus::Blocked) => "停止中",
+      (Locale::Ja, IntentStatus::Error) => "エラー",
+      (Locale::Ja, IntentStatus::Rejected) => "却下",
+      (Locale::Ja, IntentStatus::SetupFailure) => "セットアップ失敗",
+    }
+  }
+
+  pub fn work_status_label(self, status: &WorkStatus) -> &'static str {
+    match (self, status) {
+      (Locale::En, WorkStatus::Pending) => "pending",
+      (Locale::En, WorkStatus::Implementing) => "implementing",
+      (Locale::En, WorkStatus::Implemented) => "implemented",
+      (Locale::En, WorkStatus::Completed) => "completed",
+      (Locale::En, WorkStatus::Failed) => "failed",
+      (Locale::Ja, WorkStatus::Pending) => "未着手",
+      (Locale::Ja, WorkStatus::Implementing) => "実装中",
+      (Locale::Ja, WorkStatus::Implemented) => "実装済み",
+      (Locale::Ja, WorkStatus::Completed) => "完了",
+      (Locale::Ja, WorkStatus::Failed) => "失敗",
+    }
+  }
+
+  pub fn outcome_label(self, outcome: &Outcome) -> &'static str {
+    match (self, outcome) {
+      (Locale::En, Outcome::Success) => "success",
+      (Locale::En, Outcome::Failed) => "failed",
+      (Locale::En, Outcome::Escalated) => "escalated",
+      (Locale::Ja, Outcome::Success) => "成功",
+      (Locale::Ja, Outcome::Failed) => "失敗",
+      (Locale::Ja, Outcome::Escalated) => "エスカレーション",
+    }
+  }
+
+  pub fn intent_count(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("{n} intent(s)"),
+      Locale::Ja => format!("Intent {n} 件"),
+    }
+  }
+
+  pub fn item_count(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("{n} item(s)"),
+      Locale::Ja => format!("{n} 件"),
+    }
+  }
+
+  pub fn observation_count(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("{n} observation(s):"),
+      Locale::Ja => format!("Observation {n} 件:"),
+    }
+  }
+
+  pub fn worktrees_cleaned(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("{n} worktree(s) cleaned"),
+      Locale::Ja => format!("worktree を {n} 件削除しました"),
+    }
+  }
+
+  /// Progress line of an interrupted or running task, after its `task <id>: ` prefix.
+  pub fn checkpoint(
+    self,
+    step: &str,
+    commits: u32,
+    head: Option<&str>,
+    num_turns: Option<u64>,
+    elapsed_secs: u64,
+    updated_at: &str,
+  ) -> String {
+    let head = head.unwrap_or("-");
+    let turns = num_turns
+      .map(|n| n.to_string())
+      .unwrap_or_else(|| "?".into());
+    match self {
+      Locale::En => format!(
+        "{step}, {commits} commit(s) (HEAD {head}), {turns} turn(s), {elapsed_secs}s elapsed, at {updated_at}"
+      ),
+      Locale::Ja => format!(
+        "{step}、コミット {commits} 件 (HEAD {head})、{turns} ターン、経過 {elapsed_secs}秒、{updated_at} 時点"
+      ),
+    }
+  }
+
+  /// Completed count and failed task IDs, for blocked / error intents in `status`.
+  pub fn task_breakdown(self, completed: usize, total: usize, failed: &[&str]) -> String {
+    let failed = list_or_dash(failed.iter().map(|id| id.to_string()).collect());
+    match self {
+      Locale::En => format!("tasks: {completed}/{total} completed, failed: {failed}"),
+      Locale::Ja => format!("タスク {completed}/{total} 完了、失敗: {failed}"),
+    }
+  }
+
+  /// Done count over counted children and the (id, status) of those not done.
+  pub fn child_rollup(
+    self,
+    done: usize,
+    counted: usize,
+    unfinished: &[(&str, &IntentStatus)],
+  ) -> String {
+    let unfinished = list_or_dash(
+      unfinished
+        .iter()
+        .map(|(id, status)| format!("{id} ({})", self.status_label(status)))
+        .collect(),
+    );
+    match self {
+      Locale::En => format!("children: {done}/{counted} done, not done: {unfinished}"),
+      Locale::Ja => format!("子 Intent {done}/{counted} 完了、未完了: {unfinished}"),
+    }
+  }
+
+  /// Totals line of `pfl-forge history`.
+  pub fn history_totals(
+    self,
+    intents: usize,
+    success: usize,
+    failed: usize,
+    escalated: usize,
+    success_rate: f64,
+  ) -> String {
+    match self {
+      Locale::En => format!(
+        "{intents} intent(s): {success} success, {failed} failed, {escalated} escalated ({success_rate:.1}% success)"
+      ),
+      Locale::Ja => format!(
+        "Intent {intents} 件: 成功 {success}、失敗 {failed}、エスカレーション {escalated}（成功率 {success_rate:.1}%）"
+      ),
+    }
+  }
+
+  /// Run count and average duration of one step in `pfl-forge history`.
+  pub fn step_runs(self, runs: usize, avg_secs: f64) -> String {
+    match self {
+      Locale::En => format!("{runs:>4} run(s)  avg {avg_secs:.0}s"),
+      Locale::Ja => format!("{runs:>4} 回  平均 {avg_secs:.0}秒"),
+    }
+  }
+
+  pub fn step_count(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("{n} step(s)"),
+      Locale::Ja => format!("{n} ステップ"),
+    }
+  }
+
+  /// Subtitle of the dry-run report.
+  pub fn dry_run_summary(self, generated_at: &str, intents: usize, cost_usd: f64) -> String {
+    match self {
+      Locale::En => {
+        format!("Generated: {generated_at} — {intents} intent(s), analyze cost ${cost_usd:.2}")
+      }
+      Locale::Ja => {
+        format!("生成日時: {generated_at} — Intent {intents} 件、analyze コスト ${cost_usd:.2}")
+      }
+    }
+  }
+
+  pub fn questions_remaining(self, n: usize) -> String {
+    match self {
+      Locale::En => format!("answered ({n} question(s) remaining)"),
+      Locale::Ja => format!("回答しました（残り {n} 問）"),
+    }
+  }
+}
+
+fn list_or_dash(items: Vec<String>) -> String {
+  if items.is_empty() {
+    "-".to_string()
+  } else {
+    items.join(", ")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 既定のlocaleは英語() {
+    assert_eq!(Locale::default(), Locale::En);
+    assert_eq!(Locale::En.text(Msg::InboxEmpty), "inbox is empty");
+  }
+
+  #[test]
+  fn 日本語ではステータスラベルを翻訳する() {
+    assert_eq!(Locale::Ja.status_label(&IntentStatus::Done), "完了");
+    assert_eq!(Locale::Ja.outcome_label(&Outcome::Failed), "失敗");
+    assert_eq!(Locale::Ja.intent_count(3), "Intent 3 件");
+  }
+
+  #[test]
+  fn タスクの完了数と失敗したタスクを表示する() {
+    assert_eq!(
+      Locale::En.task_breakdown(1, 3, &["b"]),
+      "tasks: 1/3 completed, failed: b"
+    );
+    assert_eq!(
+      Locale::Ja.task_breakdown(1, 1, &[]),
+      "タスク 1/1 完了、失敗: -"
+    );
+  }
+
+  #[test]
+  fn 子intentの完了数と未完了の子を表示する() {
+    assert_eq!(
+      Locale::En.child_rollup(1, 2, &[("b", &IntentStatus::Error)]),
+      "children: 1/2 done, not done: b (error)"
+    );
+    assert_eq!(
+      Locale::Ja.child_rollup(1, 2, &[("b", &IntentStatus::Error)]),
+      "子 Intent 1/2 完了、未完了: b (エラー)"
+    );
+  }
+}
@@ -0,0 +1,119 @@
+/// Sections of an intent body written in the issue-template layout
+/// (`## Expected behavior`, `## Steps to reproduce`, `## Acceptance criteria`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntentSections {
+  pub expected_behavior: Option<String>,
+  pub steps_to_reproduce: Option<String>,
+  pub acceptance_criteria: Vec<String>,
+}
+
+impl IntentSections {
+  pub fn is_empty(&self) -> bool {
+    self.expected_behavior.is_none()
+      && self.steps_to_reproduce.is_none()
+      && self.acceptance_criteria.is_empty()
+  }
+
+  /// Render the extracted sections as prompt sections. Empty when nothing was found.
+  pub fn render(&self) -> String {
+    let mut out = String::new();
+    if let Some(text) = &self.expected_behavior {
+      out.push_str(&format!("\n\n## Expected Behavior\n\n{text}"));
+    }
+    if let Some(text) = &self.steps_to_reproduce {
+      out.push_str(&format!("\n\n## Steps to Reproduce\n\n{text}"));
+    }
+    if !self.acceptance_criteria.is_empty() {
+      out.push_str("\n\n## Acceptance Criteria\n");
+      for (i, criterion) in self.acceptance_criteria.iter().enumerate() {
+        out.push_str(&format!("\n{}. {criterion}", i + 1));
+      }
+    }
+    out
+  }
+}
+
+/// Extract the issue-template sections from a Markdown intent body.
+/// Headings are matched case-insensitively at any level; other sections are ignored.
+pub fn parse(body: &str) -> IntentSections {
+  let mut sections = IntentSections::default();
+  let mut current: Option<&str> = None;
+  let mut buf: Vec<&str> = Vec::new();
+
+  for line in body.lines() {
+    if let Some(heading) = heading(line) {
+      flush(&mut sections, current, &buf);
+      current = section_key(&heading);
+      buf.clear();
+    } else if current.is_some() {
+      buf.push(line);
+    }
+  }
+  flush(&mut sections, current, &buf);
+  sections
+}
+
+fn heading(line: &str) -> Option<String> {
+  let trimmed = line.trim_start();
+  let rest = trimmed.trim_start_matches('#');
+  let level = trimmed.len() - rest.len();
+  if level == 0 || level > 6 || !rest.starts_with(' ') {
+    return None;
+  }
+  Some(rest.trim().trim_end_matches(':').to_lowercase())
+}
+
+fn section_key(heading: &str) -> Option<&'static str> {
+  match heading {
+    "expected behavior" | "expected behaviour" | "expected result" => Some("expected"),
+    "steps to reproduce" | "reproduction steps" => Some("steps"),
+    "acceptance criteria" => Some("criteria"),
+    _ => None,
+  }
+}
+
+fn flush(sections: &mut IntentSections, key: Option<&str>, lines: &[&str]) {
+  let text = lines.join("\n").trim().to_string();
+  if text.is_empty() {
+    return;
+  }
+  match key {
+    Some("expected") => sections.expected_behavior = Some(text),
+    Some("steps") => sections.steps_to_reproduce = Some(text),
+    Some("criteria") => sections.acceptance_criteria.extend(criteria_items(&text)),
+    _ => {}
+  }
+}
+
+/// Split list items (`-`, `*`, `- [ ]`, `1.`) into criteria; unlisted text counts as one.
+fn criteria_items(text: &str) -> Vec<String> {
+  let items: Vec<String> = text
+    .lines()
+    .filter_map(|line| list_item(line.trim()))
+    .filter(|item| !item.is_empty())
+    .collect();
+  if items.is_empty() {
+    vec![text.to_string()]
+  } else {
+    items
+  }
+}
+
+fn list_item(line: &str) -> Option<String> {
+  let rest = if let Some(rest) = line.strip_prefix("- ").or(line.strip_prefix("* ")) {
+    rest
+  } else {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+      return None;
+    }
+    line[digits..]
+      .strip_prefix(". ")
+      .or(line[digits..].strip_prefix(") "))?
+  };
+  let rest = ["[ ] ", "[x] ", "[X] "]
+    .iter()
+    .find_map(|box_| rest.strip_prefix(box_))
+    .unwrap_or(rest);
+  Some(rest.trim().to_string())
+}
@@ -10,6 +10,11 @@ pub struct DraftFrontmatter {
   #[serde(rename = "type")]
   pub intent_type: Option<String>,
   pub risk: Option<String>,
+  pub title: Option<String>,
+  #[serde(default)]
+  pub depends_on: Vec<String>,
+  #[serde(default)]
+  pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +23,8 @@ pub struct IntentDraft {
   pub body: String,
   pub intent_type: Option<String>,
   pub risk: Option<String>,
+  pub depends_on: Vec<String>,
+  pub tags: Vec<String>,
 }
 
 pub fn parse(content: &str) -> Result<IntentDraft> {
@@ -35,13 +42,14 @@ pub fn parse(content: &str) -> Result<IntentDraft> {
     return Err(ForgeError::Parse("draft has no body".into()));
   }
 
-  // First paragraph = title, rest = body
-  let (title, body) = match body_text.find("\n\n") {
-    Some(pos) => (
+  // Frontmatter title wins; otherwise first paragraph = title, rest = body
+  let (title, body) = match (fm.title, body_text.find("\n\n")) {
+    (Some(title), _) => (title.trim().to_string(), body_text.to_string()),
+    (None, Some(pos)) => (
       body_text[..pos].trim().to_string(),
       body_text[pos..].trim().to_string(),
     ),
-    None => (body_text.to_string(), String::new()),
+    (None, None) => (body_text.to_string(), String::new()),
   };
 
   Ok(IntentDraft {
@@ -49,6 +57,8 @@ pub fn parse(content: &str) -> Result<IntentDraft> {
     body,
     intent_type: fm.intent_type,
     risk: fm.risk,
+    depends_on: fm.depends_on,
+    tags: fm.tags,
   })
 }
 
@@ -125,6 +135,18 @@ fn draft_to_yaml(draft: &IntentDraft) -> String {
   if let Some(r) = &draft.risk {
     yaml.push_str(&format!("risk: {r}\n"));
   }
+  if !draft.depends_on.is_empty() {
+    yaml.push_str("depends_on:\n");
+    for dep in &draft.depends_on {
+      yaml.push_str(&format!("  - {dep}\n"));
+    }
+  }
+  if !draft.tags.is_empty() {
+    yaml.push_str("tags:\n");
+    for tag in &draft.tags {
+      yaml.push_str(&format!("  - {tag}\n"));
+    }
+  }
   yaml
 }
 
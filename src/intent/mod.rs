@@ -1,2 +1,3 @@
+pub mod criteria;
 pub mod draft;
 pub mod registry;
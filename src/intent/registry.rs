@@ -41,6 +41,20 @@ pub enum IntentStatus {
   Done,
   Blocked,
   Error,
+  /// Turned down by a human; never picked up by `run`.
+  Rejected,
+  /// `worktree_setup` kept failing. The next `run` retries setup from the
+  /// saved tasks without running Analyze again.
+  SetupFailure,
+}
+
+/// Human checkpoint between Analyze and Implement (`require_plan_approval`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanApproval {
+  /// Tasks are written; waiting for `pfl-forge approve`.
+  Pending,
+  Approved,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +77,22 @@ pub struct Intent {
   pub sessions: SessionIds,
   #[serde(default, skip_serializing_if = "Vec::is_empty")]
   pub depends_on: Vec<String>,
+  /// Free-form labels for selecting subsets with `run --tag` / `only_tags`.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  /// `run` invocation that last picked this intent up.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub run_id: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub plan_approval: Option<PlanApproval>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChildRollup<'a> {
+  pub done: usize,
+  /// Children other than rejected ones.
+  pub counted: usize,
+  pub unfinished: Vec<&'a Intent>,
 }
 
 impl Intent {
@@ -78,6 +108,72 @@ impl Intent {
     self.clarifications.iter().any(|c| c.answer.is_none())
   }
 
+  /// Whether the intent carries one of `tags`; an empty filter selects everything.
+  pub fn matches_tags(&self, tags: &[String]) -> bool {
+    tags.is_empty() || self.tags.iter().any(|t| tags.contains(t))
+  }
+
+  pub fn awaiting_plan_approval(&self) -> bool {
+    self.plan_approval == Some(PlanApproval::Pending)
+  }
+
+  pub fn reject(&mut self) {
+    self.status = IntentStatus::Rejected;
+  }
+
+  /// Mark the intent approved for the next run. A plan waiting for review
+  /// counts as reviewed, so the run continues from its tasks.
+  pub fn approve(&mut self) {
+    self.status = IntentStatus::Approved;
+    if self.awaiting_plan_approval() {
+      self.plan_approval = Some(PlanApproval::Approved);
+    }
+  }
+
+  pub fn unanswered_count(&self) -> usize {
+    self
+      .clarifications
+      .iter()
+      .filter(|c| c.answer.is_none())
+      .count()
+  }
+
+  /// Record `answer` on the first unanswered clarification and return its question.
+  /// Approves the intent once every question is answered.
+  pub fn answer_next(&mut self, answer: String) -> Option<String> {
+    let clarification = self
+      .clarifications
+      .iter_mut()
+      .find(|c| c.answer.is_none())?;
+    clarification.answer = Some(answer);
+    let question = clarification.question.clone();
+    if !self.needs_clarification() {
+      self.status = IntentStatus::Approved;
+    }
+    Some(question)
+  }
+
+  /// Why a parent was settled blocked / error: done count over non-rejected
+  /// children, and the children that did not finish.
+  pub fn child_rollup<'a>(children: &[&'a Intent]) -> ChildRollup<'a> {
+    let counted: Vec<&'a Intent> = children
+      .iter()
+      .copied()
+      .filter(|c| c.status != IntentStatus::Rejected)
+      .collect();
+    ChildRollup {
+      done: counted
+        .iter()
+        .filter(|c| c.status == IntentStatus::Done)
+        .count(),
+      counted: counted.len(),
+      unfinished: counted
+        .into_iter()
+        .filter(|c| c.status != IntentStatus::Done)
+        .collect(),
+    }
+  }
+
   pub fn synthetic(title: &str, body: &str) -> Self {
     Self {
       file_stem: "eval-fixture".to_string(),
@@ -92,6 +188,9 @@ impl Intent {
       created_at: None,
       sessions: SessionIds::default(),
       depends_on: vec![],
+      tags: vec![],
+      run_id: None,
+      plan_approval: None,
     }
   }
 
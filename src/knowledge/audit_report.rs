@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use crate::agent::audit::AuditResult;
+use crate::error::Result;
+use crate::i18n::{Locale, Msg};
+use crate::intent::registry::Intent;
+
+/// `.forge/reports/<intent>.md`, the findings document of a `type: audit` intent.
+pub fn path(repo_path: &Path, intent_id: &str) -> PathBuf {
+  repo_path
+    .join(".forge")
+    .join("reports")
+    .join(format!("{intent_id}.md"))
+}
+
+pub fn write(
+  repo_path: &Path,
+  intent: &Intent,
+  result: &AuditResult,
+  locale: Locale,
+) -> Result<PathBuf> {
+  let path = path(repo_path, intent.id());
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(&path, render(intent, result, locale))?;
+  Ok(path)
+}
+
+pub fn render(intent: &Intent, result: &AuditResult, locale: Locale) -> String {
+  let t = |msg| locale.text(msg);
+  let mut out = format!(
+    "# {audit}: {title}\n\n{intent_label}: {id}\n{generated}: {at}\n{findings}: {count}\n",
+    audit = t(Msg::Audit),
+    intent_label = t(Msg::Intent),
+    generated = t(Msg::Generated),
+    findings = t(Msg::Findings),
+    title = intent.title,
+    id = intent.id(),
+    at = chrono::Utc::now().to_rfc3339(),
+    count = result.observations.len(),
+  );
+  for (i, finding) in result.observations.iter().enumerate() {
+    out.push_str(&format!("\n## {}. {}\n", i + 1, finding.content));
+    if !finding.evidence.is_empty() {
+      out.push('\n');
+      for evidence in &finding.evidence {
+        out.push_str(&format!(
+          "- {}: `{}`\n",
+          evidence.evidence_type, evidence.reference
+        ));
+      }
+    }
+  }
+  out
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::history::{self, Outcome};
+use crate::error::Result;
+use crate::intent::registry::{Intent, IntentStatus};
+
+/// One intent flattened with its latest history entry, for `pfl-forge export`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ExportRow {
+  pub intent_id: String,
+  pub title: String,
+  pub status: IntentStatus,
+  pub intent_type: Option<String>,
+  pub risk: Option<String>,
+  pub created_at: Option<String>,
+  pub run_id: Option<String>,
+  pub outcome: Option<Outcome>,
+  pub failure_reason: Option<String>,
+  pub finished_at: Option<String>,
+  pub duration_secs: u64,
+  pub cost_usd: f64,
+}
+
+const CSV_HEADER: &str = "intent_id,title,status,type,risk,created_at,run_id,outcome,failure_reason,finished_at,duration_secs,cost_usd";
+
+/// Collect a row for every intent in `.forge/intents/`, ordered by intent ID.
+pub fn rows(repo_path: &Path) -> Result<Vec<ExportRow>> {
+  let intents = Intent::fetch_all(&repo_path.join(".forge").join("intents"))?;
+  let entries = history::load_all(repo_path)?;
+
+  let mut rows: Vec<ExportRow> = intents
+    .iter()
+    .map(|intent| {
+      let entry = entries.iter().rfind(|e| e.intent_id == intent.id());
+      let steps = entry.map(|e| e.step_results.as_slice()).unwrap_or_default();
+      ExportRow {
+        intent_id: intent.id().to_string(),
+        title: intent.title.clone(),
+        status: intent.status.clone(),
+        intent_type: intent.intent_type.clone(),
+        risk: intent.risk.clone(),
+        created_at: intent.created_at.clone(),
+        run_id: intent.run_id.clone(),
+        outcome: entry.map(|e| e.outcome.clone()),
+        failure_reason: entry.and_then(|e| e.failure_reason.clone()),
+        finished_at: entry.and_then(|e| e.created_at.clone()),
+        duration_secs: steps.iter().map(|s| s.duration_secs).sum(),
+        cost_usd: entry.map(|e| e.cost_usd()).unwrap_or_default(),
+      }
+    })
+    .collect();
+  rows.sort_by(|a, b| a.intent_id.cmp(&b.intent_id));
+  Ok(rows)
+}
+
+pub fn to_json(rows: &[ExportRow]) -> Result<String> {
+  Ok(format!("{}\n", serde_json::to_string_pretty(rows)?))
+}
+
+pub fn to_csv(rows: &[ExportRow]) -> String {
+  let mut out = format!("{CSV_HEADER}\n");
+  for row in rows {
+    let fields = [
+      row.intent_id.clone(),
+      row.title.clone(),
+      enum_label(&row.status),
+      row.intent_type.clone().unwrap_or_default(),
+      row.risk.clone().unwrap_or_default(),
+      row.created_at.clone().unwrap_or_default(),
+      row.run_id.clone().unwrap_or_default(),
+      row.outcome.as_ref().map(enum_label).unwrap_or_default(),
+      row.failure_reason.clone().unwrap_or_default(),
+      row.finished_at.clone().unwrap_or_default(),
+      row.duration_secs.to_string(),
+      format!("{:.4}", row.cost_usd),
+    ];
+    let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    out.push_str(&line.join(","));
+    out.push('\n');
+  }
+  out
+}
+
+/// The snake_case name serde uses for a unit enum variant.
+fn enum_label<T: Serialize>(value: &T) -> String {
+  serde_json::to_value(value)
+    .ok()
+    .and_then(|v| v.as_str().map(String::from))
+    .unwrap_or_default()
+}
+
+/// Quote a field when it contains a delimiter, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
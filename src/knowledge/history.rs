@@ -1,5 +1,6 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::claude::runner::ClaudeMetadata;
@@ -13,11 +14,35 @@ pub enum Outcome {
   Escalated,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+  #[default]
+  Success,
+  Failed,
+}
+
+impl StepStatus {
+  pub fn of<T, E>(result: &std::result::Result<T, E>) -> Self {
+    if result.is_ok() {
+      StepStatus::Success
+    } else {
+      StepStatus::Failed
+    }
+  }
+}
+
+/// One step run. Turns and cost come from `metadata` for Claude steps;
+/// `model` is the resolved model ID (absent for non-agent steps).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StepResult {
   pub step: String,
+  #[serde(default)]
+  pub status: StepStatus,
   pub duration_secs: u64,
   #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub model: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
   pub metadata: Option<ClaudeMetadata>,
 }
 
@@ -35,24 +60,142 @@ pub struct HistoryEntry {
   #[serde(default)]
   pub observations: Vec<String>,
   pub created_at: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub run_id: Option<String>,
+}
+
+impl HistoryEntry {
+  /// Total Claude cost across all steps.
+  pub fn cost_usd(&self) -> f64 {
+    self
+      .step_results
+      .iter()
+      .filter_map(|s| s.metadata.as_ref().and_then(|m| m.cost_usd))
+      .fold(0.0, |total, c| total + c)
+  }
+
+  /// Total seconds spent per step, in the order steps first ran. Retried steps are summed.
+  pub fn step_durations(&self) -> Vec<(String, u64)> {
+    let mut totals: Vec<(String, u64)> = Vec::new();
+    for result in &self.step_results {
+      match totals.iter_mut().find(|(step, _)| *step == result.step) {
+        Some((_, secs)) => *secs += result.duration_secs,
+        None => totals.push((result.step.clone(), result.duration_secs)),
+      }
+    }
+    totals
+  }
 }
 
-fn history_dir(repo_path: &Path) -> std::path::PathBuf {
+fn history_dir(repo_path: &Path) -> PathBuf {
   repo_path.join(".forge").join("knowledge").join("history")
 }
 
+/// Entries are stored one file per run, `<intent-id>/<run-id>.yaml`, so re-running an
+/// intent keeps the earlier runs. Entries without a run ID are named by the current time.
+fn entry_path(repo_path: &Path, entry: &HistoryEntry) -> PathBuf {
+  let run = entry
+    .run_id
+    .clone()
+    .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%dT%H%M%S%.fZ").to_string());
+  let stem: String = run
+    .chars()
+    .filter(|c| *c != ':')
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+        c
+      } else {
+        '-'
+      }
+    })
+    .collect();
+  history_dir(repo_path)
+    .join(&entry.intent_id)
+    .join(format!("{stem}.yaml"))
+}
+
 pub fn write(repo_path: &Path, entry: &HistoryEntry) -> Result<()> {
-  let dir = history_dir(repo_path);
-  std::fs::create_dir_all(&dir)?;
-  let filename = format!("{}.yaml", entry.intent_id);
+  let path = entry_path(repo_path, entry);
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
   let content = serde_yaml::to_string(entry)?;
-  std::fs::write(dir.join(filename), content)?;
+  std::fs::write(path, content)?;
   Ok(())
 }
 
+/// The latest entry for `intent_id`.
 pub fn load(repo_path: &Path, intent_id: &str) -> Result<HistoryEntry> {
-  let path = history_dir(repo_path).join(format!("{intent_id}.yaml"));
-  let content = std::fs::read_to_string(&path)?;
-  let entry: HistoryEntry = serde_yaml::from_str(&content)?;
-  Ok(entry)
+  let dir = history_dir(repo_path);
+  let mut paths = yaml_files(&dir.join(intent_id))?;
+  let legacy = dir.join(format!("{intent_id}.yaml"));
+  if legacy.exists() {
+    paths.push(legacy);
+  }
+  read_sorted(paths)?.pop().ok_or_else(|| {
+    std::io::Error::new(
+      std::io::ErrorKind::NotFound,
+      format!("no history for intent: {intent_id}"),
+    )
+    .into()
+  })
+}
+
+/// All history entries, one per run, ordered by intent ID and then by time recorded.
+/// Files from the older one-file-per-intent layout (`<intent-id>.yaml`) are read too.
+pub fn load_all(repo_path: &Path) -> Result<Vec<HistoryEntry>> {
+  let dir = history_dir(repo_path);
+  let mut paths = yaml_files(&dir)?;
+  if dir.exists() {
+    for entry in std::fs::read_dir(&dir)?.filter_map(|e| e.ok()) {
+      if entry.path().is_dir() {
+        paths.extend(yaml_files(&entry.path())?);
+      }
+    }
+  }
+  read_sorted(paths)
+}
+
+fn yaml_files(dir: &Path) -> Result<Vec<PathBuf>> {
+  if !dir.is_dir() {
+    return Ok(Vec::new());
+  }
+  Ok(
+    std::fs::read_dir(dir)?
+      .filter_map(|e| e.ok())
+      .map(|e| e.path())
+      .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()) == Some("yaml"))
+      .collect(),
+  )
+}
+
+fn read_sorted(paths: Vec<PathBuf>) -> Result<Vec<HistoryEntry>> {
+  let mut entries = Vec::new();
+  for path in paths {
+    let content = std::fs::read_to_string(&path)?;
+    let entry: HistoryEntry = serde_yaml::from_str(&content)?;
+    entries.push((entry, path));
+  }
+  entries.sort_by(|(a, a_path), (b, b_path)| {
+    let a_time = a
+      .created_at
+      .as_deref()
+      .and_then(|t| t.parse::<DateTime<Utc>>().ok());
+    let b_time = b
+      .created_at
+      .as_deref()
+      .and_then(|t| t.parse::<DateTime<Utc>>().ok());
+    (&a.intent_id, a_time, a_path).cmp(&(&b.intent_id, b_time, b_path))
+  });
+  Ok(entries.into_iter().map(|(entry, _)| entry).collect())
+}
+
+/// History entries recorded by the given `run` invocation, ordered by intent ID.
+pub fn load_by_run(repo_path: &Path, run_id: &str) -> Result<Vec<HistoryEntry>> {
+  Ok(
+    load_all(repo_path)?
+      .into_iter()
+      .filter(|e| e.run_id.as_deref() == Some(run_id))
+      .collect(),
+  )
 }
@@ -0,0 +1,288 @@
+//! Static HTML dashboard for `pfl-forge report --html`: intent table, run
+//! timeline, daily cost chart and step durations in one self-contained
+//! `index.html` (inline CSS and SVG, no scripts), for publishing after runs.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use super::export::{self, ExportRow};
+use super::history::{self, HistoryEntry};
+use super::stats::{self, HistoryStats};
+use crate::error::Result;
+use crate::i18n::{Locale, Msg};
+
+/// Days shown in the cost chart.
+const COST_CHART_DAYS: usize = 30;
+
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+h1{margin-bottom:0}.generated{color:#777;margin-top:.25rem}\
+.cards{display:flex;gap:1rem;flex-wrap:wrap}\
+.card{border:1px solid #ddd;border-radius:6px;padding:.75rem 1rem;min-width:8rem}\
+.card b{display:block;font-size:1.5rem}\
+table{border-collapse:collapse;margin:.5rem 0 1.5rem}\
+th,td{border-bottom:1px solid #eee;padding:.3rem .6rem;text-align:left}\
+td.num{text-align:right;font-variant-numeric:tabular-nums}\
+.success{color:#1a7f37}.failed{color:#cf222e}.escalated{color:#9a6700}\
+.dot{display:inline-block;width:.8rem;height:.8rem;margin-right:2px;border-radius:2px}\
+.dot.success{background:#1a7f37}.dot.failed{background:#cf222e}.dot.escalated{background:#d4a72c}";
+
+/// Render the dashboard for the repository and write `<out_dir>/index.html`.
+pub fn write(repo_path: &Path, out_dir: &Path, locale: Locale) -> Result<PathBuf> {
+  let rows = export::rows(repo_path)?;
+  let entries = history::load_all(repo_path)?;
+  let html = render(&repo_name(repo_path), &rows, &entries, Utc::now(), locale);
+  std::fs::create_dir_all(out_dir)?;
+  let path = out_dir.join("index.html");
+  std::fs::write(&path, html)?;
+  Ok(path)
+}
+
+fn repo_name(repo_path: &Path) -> String {
+  repo_path
+    .canonicalize()
+    .ok()
+    .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+    .unwrap_or_else(|| repo_path.display().to_string())
+}
+
+pub fn render(
+  repo: &str,
+  rows: &[ExportRow],
+  entries: &[HistoryEntry],
+  generated_at: DateTime<Utc>,
+  locale: Locale,
+) -> String {
+  let repo = escape(repo);
+  let mut out = format!(
+    "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+     <title>pfl-forge: {repo}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+     <h1>{repo}</h1>\n<p class=\"generated\">{}: {}</p>\n",
+    locale.code(),
+    locale.text(Msg::Generated),
+    generated_at.format("%Y-%m-%d %H:%M UTC")
+  );
+  let stats = stats::summarize(entries, None);
+  summary_section(&mut out, &stats, locale);
+  status_section(&mut out, rows, locale);
+  timeline_section(&mut out, entries, locale);
+  cost_section(&mut out, entries, locale);
+  intents_section(&mut out, rows, locale);
+  steps_section(&mut out, &stats, locale);
+  out.push_str("</body>\n</html>\n");
+  out
+}
+
+fn summary_section(out: &mut String, stats: &HistoryStats, locale: Locale) {
+  let cards = [
+    (Msg::IntentsRun, stats.intents.to_string()),
+    (Msg::SuccessRate, format!("{:.1}%", stats.success_rate)),
+    (Msg::Retries, stats.retries.to_string()),
+    (Msg::TotalCost, format!("${:.2}", stats.total_cost_usd)),
+  ];
+  out.push_str("<div class=\"cards\">\n");
+  for (label, value) in cards {
+    out.push_str(&format!(
+      "<div class=\"card\"><b>{value}</b>{}</div>\n",
+      locale.text(label)
+    ));
+  }
+  out.push_str("</div>\n");
+}
+
+fn status_section(out: &mut String, rows: &[ExportRow], locale: Locale) {
+  let t = |msg| locale.text(msg);
+  let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+  for row in rows {
+    *counts.entry(locale.status_label(&row.status)).or_default() += 1;
+  }
+  out.push_str(&format!(
+    "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th></tr>\n",
+    t(Msg::IntentsByStatus),
+    t(Msg::Status),
+    t(Msg::IntentCount),
+  ));
+  for (status, count) in counts {
+    out.push_str(&format!(
+      "<tr><td>{status}</td><td class=\"num\">{count}</td></tr>\n"
+    ));
+  }
+  out.push_str("</table>\n");
+}
+
+/// One row per run, newest first, with a coloured mark per intent outcome.
+fn timeline_section(out: &mut String, entries: &[HistoryEntry], locale: Locale) {
+  let t = |msg| locale.text(msg);
+  let mut runs: BTreeMap<String, Vec<&HistoryEntry>> = BTreeMap::new();
+  for entry in entries {
+    let run = entry.run_id.clone().unwrap_or_else(|| "-".into());
+    runs.entry(run).or_default().push(entry);
+  }
+  let mut runs: Vec<(String, Vec<&HistoryEntry>)> = runs.into_iter().collect();
+  for (_, run) in &mut runs {
+    run.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+  }
+  runs.sort_by(|(_, a), (_, b)| finished_at(b).cmp(&finished_at(a)));
+
+  out.push_str(&format!(
+    "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th>\
+     <th>{}</th><th>{}</th></tr>\n",
+    t(Msg::Runs),
+    t(Msg::Run),
+    t(Msg::Finished),
+    t(Msg::Outcome),
+    t(Msg::IntentCount),
+    t(Msg::Cost),
+  ));
+  for (run_id, run) in &runs {
+    let marks: String = run
+      .iter()
+      .map(|e| {
+        format!(
+          "<span class=\"dot {}\" title=\"{}: {}\"></span>",
+          label(&e.outcome),
+          escape(&e.intent_id),
+          locale.outcome_label(&e.outcome)
+        )
+      })
+      .collect();
+    let cost: f64 = run.iter().map(|e| e.cost_usd()).sum();
+    out.push_str(&format!(
+      "<tr><td>{}</td><td>{}</td><td>{marks}</td><td class=\"num\">{}</td>\
+       <td class=\"num\">${cost:.2}</td></tr>\n",
+      escape(run_id),
+      escape(finished_at(run).unwrap_or("-")),
+      run.len(),
+    ));
+  }
+  out.push_str("</table>\n");
+}
+
+fn finished_at<'a>(run: &[&'a HistoryEntry]) -> Option<&'a str> {
+  run.iter().filter_map(|e| e.created_at.as_deref()).max()
+}
+
+/// Bar chart of cost per day over the last `COST_CHART_DAYS` days with history.
+fn cost_section(out: &mut String, entries: &[HistoryEntry], locale: Locale) {
+  let mut per_day: BTreeMap<String, f64> = BTreeMap::new();
+  for entry in entries {
+    let Some(day) = entry.created_at.as_deref().and_then(|t| t.get(..10)) else {
+      continue;
+    };
+    *per_day.entry(day.to_string()).or_default() += entry.cost_usd();
+  }
+  out.push_str(&format!("<h2>{}</h2>\n", locale.text(Msg::CostPerDay)));
+  if per_day.is_empty() {
+    out.push_str(&format!("<p>{}</p>\n", locale.text(Msg::NoHistory)));
+    return;
+  }
+  let days: Vec<(String, f64)> = per_day
+    .into_iter()
+    .rev()
+    .take(COST_CHART_DAYS)
+    .collect::<Vec<_>>()
+    .into_iter()
+    .rev()
+    .collect();
+  let max = days.iter().map(|(_, c)| *c).fold(0.0, f64::max);
+  let (bar, gap, height) = (24.0, 6.0, 120.0);
+  let width = days.len() as f64 * (bar + gap);
+  out.push_str(&format!(
+    "<svg width=\"{width}\" height=\"{}\" role=\"img\" aria-label=\"cost per day\">\n",
+    height + 20.0
+  ));
+  for (i, (day, cost)) in days.iter().enumerate() {
+    let h = if max > 0.0 { cost / max * height } else { 0.0 };
+    out.push_str(&format!(
+      "<rect x=\"{:.0}\" y=\"{:.1}\" width=\"{bar}\" height=\"{h:.1}\" fill=\"#0969da\">\
+       <title>{day}: ${cost:.2}</title></rect>\n",
+      i as f64 * (bar + gap),
+      height - h,
+    ));
+  }
+  let (first, last) = (&days[0].0, &days[days.len() - 1].0);
+  out.push_str(&format!(
+    "<text x=\"0\" y=\"{y}\" font-size=\"11\">{first}</text>\n\
+     <text x=\"{width}\" y=\"{y}\" font-size=\"11\" text-anchor=\"end\">{last}</text>\n</svg>\n",
+    y = height + 15.0
+  ));
+}
+
+fn intents_section(out: &mut String, rows: &[ExportRow], locale: Locale) {
+  let t = |msg| locale.text(msg);
+  out.push_str(&format!(
+    "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th>\
+     <th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>\n",
+    t(Msg::Intents),
+    t(Msg::Intent),
+    t(Msg::Title),
+    t(Msg::Status),
+    t(Msg::Outcome),
+    t(Msg::Finished),
+    t(Msg::Duration),
+    t(Msg::Cost),
+  ));
+  for row in rows {
+    let class = row.outcome.as_ref().map(label).unwrap_or_default();
+    let outcome = row
+      .outcome
+      .as_ref()
+      .map(|o| locale.outcome_label(o))
+      .unwrap_or_default();
+    let mut title = escape(&row.title);
+    if let Some(reason) = &row.failure_reason {
+      title.push_str(&format!("<br><small>{}</small>", escape(reason)));
+    }
+    out.push_str(&format!(
+      "<tr><td>{}</td><td>{title}</td><td>{}</td><td class=\"{class}\">{outcome}</td>\
+       <td>{}</td><td class=\"num\">{}s</td><td class=\"num\">${:.2}</td></tr>\n",
+      escape(&row.intent_id),
+      locale.status_label(&row.status),
+      escape(row.finished_at.as_deref().unwrap_or("")),
+      row.duration_secs,
+      row.cost_usd,
+    ));
+  }
+  out.push_str("</table>\n");
+}
+
+fn steps_section(out: &mut String, stats: &HistoryStats, locale: Locale) {
+  if stats.steps.is_empty() {
+    return;
+  }
+  let t = |msg| locale.text(msg);
+  out.push_str(&format!(
+    "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th></tr>\n",
+    t(Msg::Steps),
+    t(Msg::Step),
+    t(Msg::RunCount),
+    t(Msg::Average),
+  ));
+  for step in &stats.steps {
+    out.push_str(&format!(
+      "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.0}s</td></tr>\n",
+      escape(&step.step),
+      step.runs,
+      step.avg_secs
+    ));
+  }
+  out.push_str("</table>\n");
+}
+
+/// The snake_case name serde uses for a unit enum variant, for CSS classes.
+fn label<T: serde::Serialize>(value: &T) -> String {
+  serde_json::to_value(value)
+    .ok()
+    .and_then(|v| v.as_str().map(String::from))
+    .unwrap_or_default()
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
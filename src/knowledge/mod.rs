@@ -1,3 +1,8 @@
+pub mod audit_report;
+pub mod export;
 pub mod history;
+pub mod html_report;
 pub mod observation;
+pub mod run_report;
+pub mod stats;
 pub mod summary;
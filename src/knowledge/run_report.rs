@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::history::Outcome;
+use crate::error::Result;
+
+/// End-of-run summary written by `pfl-forge run` and read by the Operator on launch.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RunReport {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub run_id: Option<String>,
+  pub finished_at: String,
+  #[serde(default)]
+  pub intents: Vec<IntentReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentReport {
+  pub intent_id: String,
+  pub title: String,
+  pub outcome: Outcome,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub failure_reason: Option<String>,
+  #[serde(default)]
+  pub cost_usd: f64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub next_action: Option<String>,
+}
+
+impl RunReport {
+  pub fn total_cost_usd(&self) -> f64 {
+    self.intents.iter().map(|i| i.cost_usd).sum()
+  }
+}
+
+fn report_path(repo_path: &Path) -> std::path::PathBuf {
+  repo_path.join(".forge").join("last-run.yaml")
+}
+
+pub fn write(repo_path: &Path, report: &RunReport) -> Result<()> {
+  let path = report_path(repo_path);
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+  let content = serde_yaml::to_string(report)?;
+  std::fs::write(path, content)?;
+  Ok(())
+}
+
+/// Load the last run report. Returns `None` if no run has been recorded yet.
+pub fn load(repo_path: &Path) -> Result<Option<RunReport>> {
+  let path = report_path(repo_path);
+  if !path.exists() {
+    return Ok(None);
+  }
+  let content = std::fs::read_to_string(&path)?;
+  let report: RunReport = serde_yaml::from_str(&content)?;
+  Ok(Some(report))
+}
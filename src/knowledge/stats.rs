@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::history::{HistoryEntry, Outcome};
+
+/// Aggregate over history entries, for `pfl-forge history`.
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+pub struct HistoryStats {
+  pub intents: usize,
+  pub success: usize,
+  pub failed: usize,
+  pub escalated: usize,
+  pub success_rate: f64,
+  /// Extra implement runs beyond the first (review rejections, reimplementation).
+  pub retries: usize,
+  pub total_cost_usd: f64,
+  pub steps: Vec<StepStats>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StepStats {
+  pub step: String,
+  pub runs: usize,
+  pub avg_secs: f64,
+}
+
+/// Summarize `entries`, keeping only those created at or after `since` when given.
+/// Entries without a parseable `created_at` are excluded from a windowed summary.
+pub fn summarize(entries: &[HistoryEntry], since: Option<DateTime<Utc>>) -> HistoryStats {
+  let entries: Vec<&HistoryEntry> = entries
+    .iter()
+    .filter(|e| match since {
+      Some(since) => e
+        .created_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .is_some_and(|t| t >= since),
+      None => true,
+    })
+    .collect();
+
+  let mut stats = HistoryStats {
+    intents: entries.len(),
+    ..Default::default()
+  };
+  let mut durations: Vec<(String, usize, u64)> = Vec::new();
+
+  for entry in &entries {
+    match entry.outcome {
+      Outcome::Success => stats.success += 1,
+      Outcome::Failed => stats.failed += 1,
+      Outcome::Escalated => stats.escalated += 1,
+    }
+    let implement_runs = entry
+      .step_results
+      .iter()
+      .filter(|s| s.step == "implement")
+      .count();
+    stats.retries += implement_runs.saturating_sub(1);
+
+    for step in &entry.step_results {
+      stats.total_cost_usd += step
+        .metadata
+        .as_ref()
+        .and_then(|m| m.cost_usd)
+        .unwrap_or(0.0);
+      // Keep steps in first-seen order so the output follows the flow
+      match durations.iter_mut().find(|(name, _, _)| *name == step.step) {
+        Some((_, runs, secs)) => {
+          *runs += 1;
+          *secs += step.duration_secs;
+        }
+        None => durations.push((step.step.clone(), 1, step.duration_secs)),
+      }
+    }
+  }
+
+  if stats.intents > 0 {
+    stats.success_rate = stats.success as f64 / stats.intents as f64 * 100.0;
+  }
+  stats.steps = durations
+    .into_iter()
+    .map(|(step, runs, secs)| StepStats {
+      step,
+      runs,
+      avg_secs: secs as f64 / runs as f64,
+    })
+    .collect();
+  stats
+}
@@ -2,6 +2,7 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent::analyze::CheckOverride;
 use crate::error::Result;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -28,6 +29,9 @@ pub struct TaskSummary {
   pub commits: Vec<String>,
   #[serde(default, skip_serializing_if = "Option::is_none")]
   pub review: Option<ReviewSummary>,
+  /// Check overrides the task ran with, if any.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub checks: Option<CheckOverride>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
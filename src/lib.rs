@@ -1,11 +1,17 @@
 pub mod agent;
 pub mod claude;
+pub mod completions;
 pub mod config;
+pub mod doctor;
 pub mod error;
 pub mod eval;
 pub mod git;
+pub mod i18n;
 pub mod intent;
 pub mod knowledge;
+pub mod logging;
+pub mod mcp;
+pub mod process;
 pub mod prompt;
 pub mod runner;
 pub mod task;
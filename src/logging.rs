@@ -0,0 +1,389 @@
+//! Log output: human-readable text (default) or one JSON object per line
+//! (`--log-format json`). JSON lines carry the fields of every enclosing span —
+//! `intent`, `run_id`, `repo` and `stage` — so the output of concurrent workers
+//! can be filtered per intent (e.g. `jq 'select(.intent == "fix-login")'`).
+//!
+//! With `log` in the config, the same lines are also appended to a file that is
+//! rotated by size and age, for daemons whose console output is not kept.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::Record;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::writer::MakeWriter;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+use crate::config::LogSettings;
+use crate::error::{ForgeError, Result};
+
+/// Install the global subscriber: the console filtered by `-v` / `-q`
+/// (`verbosity` above / below zero) or else `RUST_LOG` (default: info), plus
+/// the rotated log file at `file.level` when configured, plus `extra` (e.g. the
+/// `run --progress` view).
+pub fn init(
+  json: bool,
+  verbosity: i8,
+  file: Option<&LogSettings>,
+  extra: Option<Box<dyn Layer<Registry> + Send + Sync>>,
+) -> Result<()> {
+  let console = match verbosity_directive(verbosity) {
+    Some(directive) => EnvFilter::new(directive),
+    None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+  };
+  let mut layers = vec![layer(json, io::stdout, true, console)];
+  if let Some(settings) = file {
+    let filter = EnvFilter::try_new(&settings.level)
+      .map_err(|e| ForgeError::Config(format!("invalid log.level: {e}")))?;
+    layers.push(layer(json, RotatingFile::open(settings)?, false, filter));
+  }
+  layers.extend(extra);
+  tracing_subscriber::registry().with(layers).init();
+  Ok(())
+}
+
+/// `-q`: warnings only. `-v`: forge's own debug output (prompts and responses).
+/// `-vv`: forge at trace and dependencies at debug.
+fn verbosity_directive(verbosity: i8) -> Option<&'static str> {
+  match verbosity {
+    0 => None,
+    v if v < 0 => Some("warn"),
+    1 => Some("info,pfl_forge=debug"),
+    _ => Some("debug,pfl_forge=trace"),
+  }
+}
+
+fn layer<W>(
+  json: bool,
+  writer: W,
+  ansi: bool,
+  filter: EnvFilter,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+  W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+  let fmt = tracing_subscriber::fmt::layer()
+    .with_writer(writer)
+    .with_ansi(ansi);
+  if json {
+    fmt
+      .fmt_fields(JsonFields)
+      .event_format(JsonFormat)
+      .with_filter(filter)
+      .boxed()
+  } else {
+    fmt.with_filter(filter).boxed()
+  }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.insert(field.name().into(), value.into());
+  }
+
+  fn record_i64(&mut self, field: &Field, value: i64) {
+    self.0.insert(field.name().into(), value.into());
+  }
+
+  fn record_u64(&mut self, field: &Field, value: u64) {
+    self.0.insert(field.name().into(), value.into());
+  }
+
+  fn record_f64(&mut self, field: &Field, value: f64) {
+    self.0.insert(field.name().into(), value.into());
+  }
+
+  fn record_bool(&mut self, field: &Field, value: bool) {
+    self.0.insert(field.name().into(), value.into());
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    self
+      .0
+      .insert(field.name().into(), format!("{value:?}").into());
+  }
+}
+
+/// Stores span fields as a JSON object so [`JsonFormat`] can merge them.
+pub struct JsonFields;
+
+impl<'w> FormatFields<'w> for JsonFields {
+  fn format_fields<R: RecordFields>(&self, mut writer: Writer<'w>, fields: R) -> fmt::Result {
+    let mut map = Map::new();
+    fields.record(&mut JsonVisitor(&mut map));
+    write!(writer, "{}", Value::Object(map))
+  }
+
+  fn add_fields(&self, current: &'w mut FormattedFields<Self>, fields: &Record<'_>) -> fmt::Result {
+    let mut map: Map<String, Value> = serde_json::from_str(&current.fields).unwrap_or_default();
+    fields.record(&mut JsonVisitor(&mut map));
+    current.fields = Value::Object(map).to_string();
+    Ok(())
+  }
+}
+
+/// One JSON object per event: timestamp, level, target, the span fields from
+/// the root inward (inner spans win on conflicts), then the event's own fields.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+  N: for<'a> FormatFields<'a> + 'static,
+{
+  fn format_event(
+    &self,
+    ctx: &FmtContext<'_, S, N>,
+    mut writer: Writer<'_>,
+    event: &Event<'_>,
+  ) -> fmt::Result {
+    let metadata = event.metadata();
+    let mut line = Map::new();
+    line.insert("timestamp".into(), chrono::Utc::now().to_rfc3339().into());
+    line.insert("level".into(), metadata.level().as_str().into());
+    line.insert("target".into(), metadata.target().into());
+    if let Some(scope) = ctx.event_scope() {
+      for span in scope.from_root() {
+        let extensions = span.extensions();
+        let Some(fields) = extensions.get::<FormattedFields<N>>() else {
+          continue;
+        };
+        if let Ok(Value::Object(map)) = serde_json::from_str(&fields.fields) {
+          line.extend(map);
+        }
+      }
+    }
+    event.record(&mut JsonVisitor(&mut line));
+    writeln!(writer, "{}", Value::Object(line))
+  }
+}
+
+/// An append-only log file that is renamed to `<path>.1` (shifting older ones
+/// up to `<path>.<keep>`) once it exceeds `max_size_mb` or is older than
+/// `max_age_days`. Checked before each line, so lines are never split.
+pub struct RotatingFile {
+  path: PathBuf,
+  max_bytes: Option<u64>,
+  max_age: Option<Duration>,
+  keep: usize,
+  state: Mutex<FileState>,
+}
+
+struct FileState {
+  file: File,
+  size: u64,
+  created: SystemTime,
+}
+
+impl RotatingFile {
+  pub fn open(settings: &LogSettings) -> Result<Self> {
+    let path = PathBuf::from(&settings.path);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+      std::fs::create_dir_all(parent)?;
+    }
+    Ok(Self {
+      state: Mutex::new(FileState::open(&path)?),
+      path,
+      max_bytes: settings.max_size_mb.map(|mb| mb * 1024 * 1024),
+      max_age: settings
+        .max_age_days
+        .map(|days| Duration::from_secs(days * 24 * 60 * 60)),
+      keep: settings.keep,
+    })
+  }
+
+  fn due(&self, state: &FileState) -> bool {
+    let too_big = self.max_bytes.is_some_and(|max| state.size >= max);
+    let too_old = self.max_age.is_some_and(|max| {
+      SystemTime::now()
+        .duration_since(state.created)
+        .is_ok_and(|age| age >= max)
+    });
+    state.size > 0 && (too_big || too_old)
+  }
+
+  fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+    let rotated = |n: usize| PathBuf::from(format!("{}.{n}", self.path.display()));
+    if self.keep == 0 {
+      std::fs::remove_file(&self.path)?;
+    } else {
+      let _ = std::fs::remove_file(rotated(self.keep));
+      for n in (1..self.keep).rev() {
+        let _ = std::fs::rename(rotated(n), rotated(n + 1));
+      }
+      std::fs::rename(&self.path, rotated(1))?;
+    }
+    *state = FileState::open(&self.path)?;
+    Ok(())
+  }
+}
+
+impl FileState {
+  fn open(path: &Path) -> io::Result<Self> {
+    let file = std::fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)?;
+    let metadata = file.metadata()?;
+    Ok(Self {
+      size: metadata.len(),
+      created: metadata.created().unwrap_or_else(|_| SystemTime::now()),
+      file,
+    })
+  }
+}
+
+impl Write for &RotatingFile {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+    if self.due(&state) {
+      self.rotate(&mut state)?;
+    }
+    let written = state.file.write(buf)?;
+    state.size += written as u64;
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self
+      .state
+      .lock()
+      .unwrap_or_else(|e| e.into_inner())
+      .file
+      .flush()
+  }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+  type Writer = &'a RotatingFile;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+
+  fn settings(path: &Path) -> LogSettings {
+    LogSettings {
+      path: path.to_string_lossy().into_owned(),
+      max_size_mb: None,
+      max_age_days: None,
+      keep: 2,
+      level: "info".into(),
+    }
+  }
+
+  #[derive(Clone, Default)]
+  struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+  impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+      self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn json行に外側のspanのフィールドを含める() {
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+      .fmt_fields(JsonFields)
+      .event_format(JsonFormat)
+      .with_writer(move || writer.clone())
+      .finish();
+
+    tracing::subscriber::with_default(subscriber, || {
+      let intent = tracing::info_span!("intent", intent = "fix-login", run_id = "r-1");
+      let _intent = intent.enter();
+      let stage = tracing::info_span!("stage", stage = "implement");
+      let _stage = stage.enter();
+      tracing::info!(turns = 3, "implementing");
+    });
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let line: Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(line["intent"], "fix-login");
+    assert_eq!(line["run_id"], "r-1");
+    assert_eq!(line["stage"], "implement");
+    assert_eq!(line["message"], "implementing");
+    assert_eq!(line["turns"], 3);
+    assert_eq!(line["level"], "INFO");
+  }
+
+  #[test]
+  fn verbosityをフィルタに対応させる() {
+    assert_eq!(verbosity_directive(0), None);
+    assert_eq!(verbosity_directive(-1), Some("warn"));
+    assert_eq!(verbosity_directive(1), Some("info,pfl_forge=debug"));
+    assert_eq!(verbosity_directive(3), Some("debug,pfl_forge=trace"));
+  }
+
+  #[test]
+  fn サイズ上限を超えたらローテートしてkeep個まで残す() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logs").join("forge.log");
+    let file = RotatingFile::open(&settings(&path)).unwrap();
+    let file = RotatingFile {
+      max_bytes: Some(10),
+      ..file
+    };
+
+    for line in [
+      "first line\n",
+      "second line\n",
+      "third line\n",
+      "fourth line\n",
+    ] {
+      (&file).write_all(line.as_bytes()).unwrap();
+    }
+
+    let read = |p: &Path| std::fs::read_to_string(p).unwrap();
+    assert_eq!(read(&path), "fourth line\n");
+    assert_eq!(read(&dir.path().join("logs/forge.log.1")), "third line\n");
+    assert_eq!(read(&dir.path().join("logs/forge.log.2")), "second line\n");
+    assert!(!dir.path().join("logs/forge.log.3").exists());
+  }
+
+  #[test]
+  fn 作成から上限日数を過ぎたファイルはローテートする() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("forge.log");
+    let file = RotatingFile {
+      max_age: Some(Duration::from_secs(60)),
+      ..RotatingFile::open(&settings(&path)).unwrap()
+    };
+    (&file).write_all(b"old\n").unwrap();
+    file.state.lock().unwrap().created = SystemTime::now() - Duration::from_secs(120);
+
+    (&file).write_all(b"new\n").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+    assert_eq!(
+      std::fs::read_to_string(dir.path().join("forge.log.1")).unwrap(),
+      "old\n"
+    );
+  }
+}
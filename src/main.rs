@@ -1,29 +1,57 @@
 use std::os::unix::process::CommandExt;
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueHint};
 use self_update::cargo_crate_version;
 use tracing::{error, info, warn};
+use tracing_subscriber::Layer;
 
 use pfl_forge::agent;
 use pfl_forge::claude::runner::ClaudeRunner;
 use pfl_forge::config::Config;
-use pfl_forge::error::Result;
+use pfl_forge::error::{ForgeError, Result};
 use pfl_forge::git;
+use pfl_forge::i18n::{Locale, Msg};
 use pfl_forge::runner;
 
 #[derive(Parser)]
 #[command(
   name = "pfl-forge",
-  about = "Multi-agent task processor powered by Claude Code"
+  about = "Multi-agent task processor powered by Claude Code",
+  after_help = "Examples:
+  pfl-forge init                      Create pfl-forge.yaml and .forge/
+  pfl-forge doctor                    Check claude, git and state directories
+  pfl-forge approve fix-login         Approve an intent for the next run
+  pfl-forge run --progress            Process approved intents with live status
+  pfl-forge completions zsh           Print a shell completion script
+
+Run without a subcommand to start the operator session."
 )]
 struct Cli {
   #[command(subcommand)]
   command: Option<Commands>,
 
   /// Path to config file
-  #[arg(short, long, default_value = "pfl-forge.yaml")]
+  #[arg(short, long, default_value = "pfl-forge.yaml", value_hint = ValueHint::FilePath)]
   config: PathBuf,
+
+  /// Log format: text, or one JSON object per line with intent / run_id / stage fields
+  #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+  log_format: LogFormat,
+
+  /// More log output (-v: debug incl. claude prompts and responses, -vv: trace); overrides RUST_LOG
+  #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+  verbose: u8,
+
+  /// Only warnings and errors; overrides RUST_LOG
+  #[arg(short, long, global = true, conflicts_with = "verbose")]
+  quiet: bool,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum LogFormat {
+  Text,
+  Json,
 }
 
 #[derive(Subcommand)]
@@ -36,11 +64,44 @@ enum Commands {
     /// Run in background and return immediately
     #[arg(long)]
     background: bool,
+    /// With --dry-run: analyze and write a plan report (.json → JSON, otherwise Markdown)
+    #[arg(long, requires = "dry_run", conflicts_with = "background", value_hint = ValueHint::FilePath)]
+    report: Option<PathBuf>,
+    /// Record every claude call to this YAML fixture for replay in tests
+    #[arg(long, conflicts_with = "background", value_hint = ValueHint::FilePath)]
+    record: Option<PathBuf>,
+    /// Show one live line per in-flight intent (stage, elapsed, latest activity) on stderr;
+    /// console logs drop to warnings unless -v is given
+    #[arg(long, conflicts_with_all = ["background", "report"])]
+    progress: bool,
+    /// Only process intents with this tag (repeatable; replaces only_tags)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// Process just this intent now, whatever its status, tags or dependencies
+    #[arg(long, value_name = "ID", conflicts_with_all = ["dry_run", "tags"])]
+    intent: Option<String>,
+    /// Process the intents listed in this file (one ID per line, `-` for stdin),
+    /// like --intent for each
+    #[arg(
+      long,
+      value_name = "PATH",
+      value_hint = ValueHint::FilePath,
+      conflicts_with_all = ["dry_run", "tags", "intent", "background"]
+    )]
+    from_file: Option<PathBuf>,
   },
   /// Watch for new intents and process them periodically
-  Watch,
+  Watch {
+    /// Only process intents with this tag (repeatable; replaces only_tags)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+  },
   /// Show current processing status
-  Status,
+  Status {
+    /// Show what the given run (run_id from last-run.yaml or history) did instead
+    #[arg(long)]
+    run: Option<String>,
+  },
   /// Clean up worktrees for completed tasks
   Clean,
   /// Launch operator agent (interactive Claude Code session)
@@ -48,7 +109,12 @@ enum Commands {
     /// Claude model to use
     #[arg(long)]
     model: Option<String>,
+    /// Print a one-shot status report instead of starting an interactive session
+    #[arg(long)]
+    report: bool,
   },
+  /// Serve status, clarifications, answer and run as MCP tools over stdio (used by operator)
+  Mcp,
   /// Create a new intent draft in .forge/intent-drafts/
   Create {
     /// Intent title
@@ -59,6 +125,7 @@ enum Commands {
   /// Run codebase audit
   Audit {
     /// Target path (default: entire codebase)
+    #[arg(value_hint = ValueHint::AnyPath)]
     path: Option<String>,
   },
   /// Show inbox (intents awaiting human action)
@@ -68,6 +135,20 @@ enum Commands {
     /// Comma-separated intent IDs
     ids: String,
   },
+  /// Reject intents by ID (they are kept but never run)
+  Reject {
+    /// Comma-separated intent IDs
+    ids: String,
+  },
+  /// Pause processing: run and watch skip every intent until `enable`
+  Disable,
+  /// Resume processing after `disable`
+  Enable,
+  /// Show one intent in detail, with its tasks if analyzed
+  Show {
+    /// Intent ID
+    id: String,
+  },
   /// Answer a clarification question on a blocked intent
   Answer {
     /// Intent ID
@@ -77,6 +158,17 @@ enum Commands {
   },
   /// Initialize pfl-forge in the current directory
   Init,
+  /// Check the claude CLI, git, sandbox runtime and state directories
+  Doctor,
+  /// Print a shell completion script (intent IDs complete from .forge/intents/)
+  #[command(after_help = "Install:
+  bash: pfl-forge completions bash > ~/.local/share/bash-completion/completions/pfl-forge
+  zsh:  pfl-forge completions zsh > \"${fpath[1]}/_pfl-forge\"
+  fish: pfl-forge completions fish > ~/.config/fish/completions/pfl-forge.fish")]
+  Completions {
+    #[arg(value_enum)]
+    shell: pfl_forge::completions::Shell,
+  },
   /// Create an intent draft in .forge/intent-drafts/
   Draft {
     /// Intent title
@@ -84,6 +176,40 @@ enum Commands {
     /// Intent body (description)
     body: String,
   },
+  /// Show success rate, per-step durations, retries and cost from history
+  History {
+    /// Only include intents recorded in the last N days
+    #[arg(long)]
+    days: Option<i64>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+  },
+  /// Export every intent with its latest history as CSV or JSON
+  Export {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    format: ExportFormat,
+    /// Write to this file instead of stdout
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    output: Option<PathBuf>,
+  },
+  /// Render intents and history into a static HTML dashboard
+  Report {
+    /// Directory to write index.html into
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    html: PathBuf,
+  },
+  /// Inspect the configuration forge runs with
+  Config {
+    #[command(subcommand)]
+    command: ConfigCommand,
+  },
+  /// Inspect the system prompts the agents run with
+  Prompts {
+    #[command(subcommand)]
+    command: PromptsCommand,
+  },
   /// Run prompt evaluation fixtures
   Eval {
     /// Agent to evaluate (analyze, review)
@@ -94,6 +220,198 @@ enum Commands {
   },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommand {
+  /// Print the effective configuration as YAML (defaults applied, paths resolved)
+  Show,
+}
+
+#[derive(Subcommand)]
+enum PromptsCommand {
+  /// Show the effective system prompt of each overridable stage
+  Show {
+    /// Only this stage (analyze, implement, review)
+    stage: Option<String>,
+  },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+  Text,
+  Json,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+  Csv,
+  Json,
+}
+
+/// `run --intent` / `--from-file` run the listed intents, otherwise every approved one.
+fn run_selected(
+  config: &Config,
+  claude: &(impl pfl_forge::claude::runner::Claude + Sync),
+  repo_path: &std::path::Path,
+  dry_run: bool,
+  listed: Option<&[String]>,
+) -> pfl_forge::error::Result<Vec<(String, runner::IntentResult)>> {
+  match listed {
+    Some(ids) => runner::run_listed_intents(config, claude, repo_path, ids),
+    None => runner::run_intents(config, claude, repo_path, dry_run),
+  }
+}
+
+/// Intent IDs from `run --from-file`, reading stdin for `-`.
+fn read_intent_list(path: &std::path::Path) -> Result<Vec<String>> {
+  let text = if path == std::path::Path::new("-") {
+    std::io::read_to_string(std::io::stdin())?
+  } else {
+    std::fs::read_to_string(path)?
+  };
+  Ok(runner::parse_intent_list(&text))
+}
+
+/// Start `pfl-forge run` detached from this process, logging to `.forge/run.log`.
+fn spawn_background_run(
+  repo_path: &std::path::Path,
+  dry_run: bool,
+  tags: &[String],
+  intent: Option<&str>,
+) -> pfl_forge::error::Result<(u32, PathBuf)> {
+  let log_path = repo_path.join(".forge").join("run.log");
+  let log_file = std::fs::File::create(&log_path)?;
+  let mut cmd = std::process::Command::new(std::env::current_exe()?);
+  cmd.arg("run");
+  if dry_run {
+    cmd.arg("--dry-run");
+  }
+  for tag in tags {
+    cmd.args(["--tag", tag]);
+  }
+  if let Some(id) = intent {
+    cmd.args(["--intent", id]);
+  }
+  cmd.stdout(log_file.try_clone()?).stderr(log_file);
+  unsafe {
+    cmd.pre_exec(|| {
+      libc::setsid();
+      Ok(())
+    });
+  }
+  let child = cmd.spawn()?;
+  Ok((child.id(), log_path))
+}
+
+/// Unknown IDs given to `show` / `approve` / `reject` are a usage error (exit 2),
+/// reported after the known ones have been handled.
+fn intents_not_found(ids: &[&str]) -> Result<()> {
+  if ids.is_empty() {
+    return Ok(());
+  }
+  Err(ForgeError::Config(format!(
+    "intent not found: {}",
+    ids.join(", ")
+  )))
+}
+
+/// One `status` entry and, indented beneath it, its child intents. Blocked and
+/// error intents also show their tasks and why they failed.
+fn print_intent_status(
+  intent: &pfl_forge::intent::registry::Intent,
+  all: &[pfl_forge::intent::registry::Intent],
+  depth: usize,
+  repo_path: &std::path::Path,
+  locale: Locale,
+) -> Result<()> {
+  let indent = "  ".repeat(depth);
+  let marker = if depth == 0 { "" } else { "└ " };
+  let pad = format!("{indent}{}", if depth == 0 { "  " } else { "    " });
+  let status = locale.status_label(&intent.status);
+  println!(
+    "{indent}{marker}{id}  {status}  {title}",
+    id = intent.id(),
+    title = intent.title
+  );
+  if let Some(cp) = runner::checkpoint::read(repo_path, intent.id()) {
+    println!(
+      "{pad}task {}: {}",
+      cp.task_id,
+      locale.checkpoint(
+        &cp.step,
+        cp.commits,
+        cp.head.as_deref(),
+        cp.num_turns,
+        cp.elapsed_secs,
+        &cp.updated_at,
+      )
+    );
+  }
+  let failed = matches!(
+    intent.status,
+    pfl_forge::intent::registry::IntentStatus::Blocked
+      | pfl_forge::intent::registry::IntentStatus::Error
+      | pfl_forge::intent::registry::IntentStatus::SetupFailure
+  );
+  let children: Vec<&pfl_forge::intent::registry::Intent> = all
+    .iter()
+    .filter(|c| c.parent.as_deref() == Some(intent.id()))
+    .collect();
+  if failed && !children.is_empty() {
+    let rollup = pfl_forge::intent::registry::Intent::child_rollup(&children);
+    let unfinished: Vec<(&str, &pfl_forge::intent::registry::IntentStatus)> = rollup
+      .unfinished
+      .iter()
+      .map(|c| (c.id(), &c.status))
+      .collect();
+    println!(
+      "{pad}{}: {}",
+      locale.text(Msg::Reason),
+      locale.child_rollup(rollup.done, rollup.counted, &unfinished)
+    );
+  }
+  if failed && pfl_forge::task::tasks_exist(repo_path, intent.id()) {
+    let tasks = pfl_forge::task::read_all_tasks(repo_path, intent.id())?;
+    let breakdown = pfl_forge::task::breakdown(&tasks);
+    println!(
+      "{pad}{}",
+      locale.task_breakdown(breakdown.completed, breakdown.total, &breakdown.failed)
+    );
+    for task in &tasks {
+      println!(
+        "{pad}- {}  {}  {}",
+        task.id,
+        locale.work_status_label(&task.status),
+        task.title
+      );
+    }
+  }
+  if let Ok(entry) = pfl_forge::knowledge::history::load(repo_path, intent.id()) {
+    if let Some(reason) = entry.failure_reason.as_deref().filter(|_| failed) {
+      println!("{pad}{}: {reason}", locale.text(Msg::Reason));
+    }
+    let steps: Vec<String> = entry
+      .step_durations()
+      .iter()
+      .map(|(step, secs)| format!("{step} {}", format_duration(*secs)))
+      .collect();
+    if !steps.is_empty() {
+      println!("{pad}{}", steps.join(", "));
+    }
+  }
+  for child in children {
+    print_intent_status(child, all, depth + 1, repo_path, locale)?;
+  }
+  Ok(())
+}
+
+fn format_duration(secs: u64) -> String {
+  match secs {
+    0..=59 => format!("{secs}s"),
+    60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+    _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+  }
+}
+
 fn self_update() {
   let result = self_update::backends::github::Update::configure()
     .repo_owner("nesso-pfl")
@@ -118,18 +436,12 @@ fn self_update() {
 async fn main() {
   self_update();
 
-  tracing_subscriber::fmt()
-    .with_env_filter(
-      tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-    )
-    .init();
-
   let cli = Cli::parse();
 
   if let Err(e) = run(cli).await {
     error!("{e}");
-    std::process::exit(1);
+    eprintln!("{}", e.to_json());
+    std::process::exit(e.exit_code());
   }
 }
 
@@ -175,15 +487,68 @@ fn cmd_draft(title: &str, body: &str) -> Result<()> {
   Ok(())
 }
 
+fn cmd_doctor(config_path: &std::path::Path) -> Result<()> {
+  use pfl_forge::doctor::Status;
+
+  let checks = pfl_forge::doctor::run(config_path, &Config::repo_path());
+  for check in &checks {
+    println!(
+      "{}  {:<16} {}",
+      check.status.label(),
+      check.name,
+      check.detail
+    );
+    if let Some(hint) = &check.hint {
+      println!("      -> {hint}");
+    }
+  }
+  if checks.iter().any(|c| c.status == Status::Fail) {
+    std::process::exit(1);
+  }
+  Ok(())
+}
+
 async fn run(cli: Cli) -> Result<()> {
-  // init and draft don't need config
+  let json_logs = matches!(cli.log_format, LogFormat::Json);
+  let progress = matches!(cli.command, Some(Commands::Run { progress: true, .. }))
+    .then(runner::progress::ProgressView::start);
+  let verbosity = if cli.quiet || (progress.is_some() && cli.verbose == 0) {
+    -1
+  } else {
+    cli.verbose.min(2) as i8
+  };
+  let progress_layer = progress.as_ref().map(|view| view.layer().boxed());
+  // init, draft, doctor and completions don't need config
   match &cli.command {
-    Some(Commands::Init) => return cmd_init(),
-    Some(Commands::Draft { title, body }) => return cmd_draft(title, body),
+    Some(Commands::Init) => {
+      pfl_forge::logging::init(json_logs, verbosity, None, None)?;
+      return cmd_init();
+    }
+    Some(Commands::Draft { title, body }) => {
+      pfl_forge::logging::init(json_logs, verbosity, None, None)?;
+      return cmd_draft(title, body);
+    }
+    Some(Commands::Completions { shell }) => {
+      let cmd = Cli::command();
+      print!("{}", pfl_forge::completions::generate(*shell, &cmd));
+      return Ok(());
+    }
+    Some(Commands::Doctor) => {
+      pfl_forge::logging::init(json_logs, verbosity, None, None)?;
+      return cmd_doctor(&cli.config);
+    }
     _ => {}
   }
 
-  let config = Config::load(&cli.config)?;
+  let config = Config::load(&cli.config);
+  // Load errors are still logged to the console
+  let log_file = config.as_ref().ok().and_then(|c| c.log.as_ref());
+  pfl_forge::logging::init(json_logs, verbosity, log_file, progress_layer)?;
+  let mut config = config?;
+  pfl_forge::process::init(&config.worker_env);
+  runner::webhook::init(&config.webhooks);
+  let locale = config.locale;
+  let t = |msg: Msg| locale.text(msg);
 
   // No subcommand → launch operator
   let command = match cli.command {
@@ -198,62 +563,115 @@ async fn run(cli: Cli) -> Result<()> {
     Commands::Run {
       dry_run,
       background,
+      report,
+      record,
+      tags,
+      intent,
+      from_file,
+      ..
     } => {
+      if !tags.is_empty() {
+        config.only_tags = tags;
+      }
       if background {
         let repo_path = Config::repo_path();
-        let log_path = repo_path.join(".forge").join("run.log");
-        let log_file = std::fs::File::create(&log_path)?;
-        let mut cmd = std::process::Command::new(std::env::current_exe()?);
-        cmd.arg("run");
-        if dry_run {
-          cmd.arg("--dry-run");
-        }
-        cmd.stdout(log_file.try_clone()?).stderr(log_file);
-        unsafe {
-          cmd.pre_exec(|| {
-            libc::setsid();
-            Ok(())
-          });
-        }
-        let child = cmd.spawn()?;
-        println!("started in background (pid: {})", child.id());
-        println!("log: {}", log_path.display());
+        let (pid, log_path) =
+          spawn_background_run(&repo_path, dry_run, &config.only_tags, intent.as_deref())?;
+        println!("{} (pid: {pid})", t(Msg::StartedInBackground));
+        println!("{}: {}", t(Msg::Log), log_path.display());
         return Ok(());
       }
 
       let repo_path = Config::repo_path();
+      if runner::watch::is_disabled(&config, &repo_path) {
+        println!("{}", t(Msg::Disabled));
+        return Ok(());
+      }
+      let listed = match (intent, &from_file) {
+        (Some(id), _) => Some(vec![id]),
+        (None, Some(path)) => Some(read_intent_list(path)?),
+        (None, None) => None,
+      };
       let claude = ClaudeRunner::new(
         config.implement_tools.clone(),
         config.mcp_config.clone(),
         Some(&config.memory_server),
-      );
-      let results = runner::run_intents(&config, &claude, &repo_path, dry_run)?;
+      )
+      .with_cli(&config.claude);
+      if let Some(report_path) = report {
+        let report = runner::dry_run::plan(&config, &claude, &repo_path)?;
+        runner::dry_run::write(&report_path, &report, locale)?;
+        for intent in &report.intents {
+          println!("{}: {}", intent.intent_id, intent.outcome);
+        }
+        println!("{}: {}", t(Msg::Report), report_path.display());
+        return Ok(());
+      }
+      let results = match record {
+        Some(path) => {
+          let recorder = pfl_forge::claude::fixture::RecordingRunner::new(claude, path);
+          run_selected(&config, &recorder, &repo_path, dry_run, listed.as_deref())
+        }
+        None => run_selected(&config, &claude, &repo_path, dry_run, listed.as_deref()),
+      };
+      if let Some(view) = &progress {
+        view.finish();
+      }
+      let results = results?;
+      if !dry_run {
+        let report = runner::build_run_report(&repo_path, &results);
+        if let Err(e) = pfl_forge::knowledge::run_report::write(&repo_path, &report) {
+          warn!("failed to write run report: {e}");
+        }
+      }
       for (id, result) in &results {
-        let status = match &result.outcome {
-          pfl_forge::knowledge::history::Outcome::Success => "success",
-          pfl_forge::knowledge::history::Outcome::Failed => "failed",
-          pfl_forge::knowledge::history::Outcome::Escalated => "escalated",
-        };
-        println!("{id}: {status}");
+        println!("{id}: {}", locale.outcome_label(&result.outcome));
       }
       if results.is_empty() && !dry_run {
-        println!("no approved intents to process");
+        println!("{}", t(Msg::NoApprovedIntents));
       }
       Ok(())
     }
-    Commands::Watch => {
+    Commands::Watch { tags } => {
+      if !tags.is_empty() {
+        config.only_tags = tags;
+      }
       let repo_path = Config::repo_path();
       let claude = ClaudeRunner::new(
         config.implement_tools.clone(),
         config.mcp_config.clone(),
         Some(&config.memory_server),
+      )
+      .with_cli(&config.claude);
+      let mut schedule = runner::watch::PollSchedule::new(
+        std::time::Duration::from_secs(config.poll_interval_secs),
+        std::time::Duration::from_secs(config.max_poll_interval_secs),
       );
-      let interval = std::time::Duration::from_secs(config.poll_interval_secs);
 
-      info!("watch: polling every {}s", config.poll_interval_secs);
+      let cron = config
+        .schedule
+        .as_deref()
+        .map(runner::cron::CronSchedule::parse)
+        .transpose()?;
+
+      match &config.schedule {
+        Some(expr) => info!("watch: polling on schedule \"{expr}\" (local time)"),
+        None => info!(
+          "watch: polling every {}s (backing off up to {}s when idle)",
+          config.poll_interval_secs, config.max_poll_interval_secs
+        ),
+      }
+      if !config.only_tags.is_empty() {
+        info!("watch: only intents tagged {}", config.only_tags.join(", "));
+      }
+      if let Some(cron) = &cron {
+        // Scheduled mode: the first poll also waits for a scheduled time
+        runner::watch::sleep_until_scheduled(&repo_path, cron)?;
+      }
       loop {
         match runner::run_intents(&config, &claude, &repo_path, false) {
           Ok(results) => {
+            schedule.record(!results.is_empty());
             for (id, result) in &results {
               let status = match &result.outcome {
                 pfl_forge::knowledge::history::Outcome::Success => "success",
@@ -265,26 +683,51 @@ async fn run(cli: Cli) -> Result<()> {
           }
           Err(e) => {
             warn!("watch cycle error: {e}");
+            schedule.record(false);
           }
         }
-        std::thread::sleep(interval);
+        runner::digest::send_daily_if_due(&config, &repo_path);
+        if let Some(cron) = &cron {
+          runner::watch::sleep_until_scheduled(&repo_path, cron)?;
+          continue;
+        }
+        let delay = schedule.delay_with_jitter(runner::watch::random_unit());
+        info!("watch: next poll in {}s", delay.as_secs());
+        runner::watch::sleep_or_wake(&repo_path, delay);
+      }
+    }
+    Commands::Status { run: Some(run_id) } => {
+      let repo_path = Config::repo_path();
+      let entries = pfl_forge::knowledge::history::load_by_run(&repo_path, &run_id)?;
+      for e in &entries {
+        let outcome = locale.outcome_label(&e.outcome);
+        match &e.failure_reason {
+          Some(reason) => println!("{}  {outcome}  {}  ({reason})", e.intent_id, e.title),
+          None => println!("{}  {outcome}  {}", e.intent_id, e.title),
+        }
       }
+      println!("\n{}", locale.intent_count(entries.len()));
+      Ok(())
     }
-    Commands::Status => {
+    Commands::Status { run: None } => {
       let repo_path = Config::repo_path();
       let intents_dir = repo_path.join(".forge").join("intents");
       let intents = pfl_forge::intent::registry::Intent::fetch_all(&intents_dir)?;
 
       if intents.is_empty() {
-        println!("no intents");
+        println!("{}", t(Msg::NoIntents));
         return Ok(());
       }
 
-      for i in &intents {
-        let status = format!("{:?}", i.status).to_lowercase();
-        println!("{id}  {status}  {title}", id = i.id(), title = i.title);
+      // Children are printed under their parent; orphans stay at the top level
+      for i in intents.iter().filter(|i| {
+        i.parent
+          .as_deref()
+          .is_none_or(|p| !intents.iter().any(|o| o.id() == p))
+      }) {
+        print_intent_status(i, &intents, 0, &repo_path, locale)?;
       }
-      println!("\n{} intent(s)", intents.len());
+      println!("\n{}", locale.intent_count(intents.len()));
       Ok(())
     }
     Commands::Clean => {
@@ -298,7 +741,7 @@ async fn run(cli: Cli) -> Result<()> {
         .collect();
 
       if done_branches.is_empty() {
-        println!("no completed worktrees to clean");
+        println!("{}", t(Msg::NoCompletedWorktrees));
         return Ok(());
       }
 
@@ -308,20 +751,39 @@ async fn run(cli: Cli) -> Result<()> {
         if wt_path.exists() {
           match git::worktree::remove(&repo_path, &wt_path) {
             Ok(()) => {
-              println!("removed: {}", wt_path.display());
+              println!("{}: {}", t(Msg::Removed), wt_path.display());
               cleaned += 1;
             }
             Err(e) => eprintln!("failed to remove {}: {e}", wt_path.display()),
           }
         }
       }
-      println!("{cleaned} worktree(s) cleaned");
+      println!("{}", locale.worktrees_cleaned(cleaned));
       Ok(())
     }
-    Commands::Operator { model } => {
+    Commands::Operator { model, report } => {
       let repo_path = Config::repo_path();
+      if report {
+        let claude = ClaudeRunner::new(
+          config.analyze_tools.clone(),
+          config.mcp_config.clone(),
+          Some(&config.memory_server),
+        )
+        .with_cli(&config.claude);
+        let (result, _meta) =
+          agent::operator::report(&config, &claude, model.as_deref(), &repo_path)?;
+        println!("{}", result.report.trim_end());
+        return Ok(());
+      }
       agent::operator::launch(&config, model.as_deref(), &repo_path)
     }
+    Commands::Mcp => {
+      let repo_path = Config::repo_path();
+      pfl_forge::mcp::serve(&repo_path, &|| {
+        spawn_background_run(&repo_path, false, &[], None).map(|(pid, _)| pid)
+      })?;
+      Ok(())
+    }
     Commands::Create { title, body } => {
       let repo_path = Config::repo_path();
       let intents_dir = repo_path.join(".forge").join("intents");
@@ -337,7 +799,7 @@ async fn run(cli: Cli) -> Result<()> {
       let yaml =
         format!("title: \"{title}\"\nbody: |\n  {body}\nsource: human\nstatus: proposed\n");
       std::fs::write(&path, yaml)?;
-      println!("created: {id}");
+      println!("{}: {id}", t(Msg::Created));
       Ok(())
     }
     Commands::Audit { path } => {
@@ -346,7 +808,8 @@ async fn run(cli: Cli) -> Result<()> {
         config.analyze_tools.clone(),
         config.mcp_config.clone(),
         Some(&config.memory_server),
-      );
+      )
+      .with_cli(&config.claude);
 
       // Create internal audit intent
       let target = path.as_deref().unwrap_or(".");
@@ -363,9 +826,9 @@ async fn run(cli: Cli) -> Result<()> {
         .collect();
 
       if audit_obs.is_empty() {
-        println!("no observations found");
+        println!("{}", t(Msg::NoObservations));
       } else {
-        println!("{} observation(s):", audit_obs.len());
+        println!("{}", locale.observation_count(audit_obs.len()));
         for obs in &audit_obs {
           println!("  - {}", obs.content);
           for ev in &obs.evidence {
@@ -374,12 +837,7 @@ async fn run(cli: Cli) -> Result<()> {
         }
       }
 
-      let status = match &result.outcome {
-        pfl_forge::knowledge::history::Outcome::Success => "success",
-        pfl_forge::knowledge::history::Outcome::Failed => "failed",
-        pfl_forge::knowledge::history::Outcome::Escalated => "escalated",
-      };
-      println!("audit: {status}");
+      println!("audit: {}", locale.outcome_label(&result.outcome));
       Ok(())
     }
     Commands::Inbox => {
@@ -403,16 +861,16 @@ async fn run(cli: Cli) -> Result<()> {
         .collect();
 
       if inbox.is_empty() {
-        println!("inbox is empty");
+        println!("{}", t(Msg::InboxEmpty));
       } else {
         for i in &inbox {
           let risk = i.risk.as_deref().unwrap_or("-");
           let source = &i.source;
-          let status = format!("{:?}", i.status).to_lowercase();
+          let status = locale.status_label(&i.status);
           let clarification = if i.needs_clarification() {
-            " [needs clarification]"
+            format!(" {}", t(Msg::NeedsClarification))
           } else {
-            ""
+            String::new()
           };
           println!(
             "{id}  {status}  risk={risk}  source={source}{clarification}",
@@ -426,7 +884,44 @@ async fn run(cli: Cli) -> Result<()> {
             }
           }
         }
-        println!("\n{} item(s)", inbox.len());
+        println!("\n{}", locale.item_count(inbox.len()));
+      }
+      Ok(())
+    }
+    Commands::Config {
+      command: ConfigCommand::Show,
+    } => {
+      print!("{}", config.effective_yaml()?);
+      Ok(())
+    }
+    Commands::Prompts {
+      command: PromptsCommand::Show { stage },
+    } => {
+      let stages: Vec<&str> = match &stage {
+        Some(stage) if pfl_forge::prompt::builtin(stage).is_some() => vec![stage.as_str()],
+        Some(stage) => {
+          return Err(pfl_forge::error::ForgeError::Config(format!(
+            "unknown prompt stage: {stage} (expected one of {})",
+            pfl_forge::prompt::OVERRIDABLE.join(", ")
+          )))
+        }
+        None => pfl_forge::prompt::OVERRIDABLE.to_vec(),
+      };
+      for (i, stage) in stages.iter().enumerate() {
+        let source = if config.prompts.is_overridden(stage) {
+          format!(".forge/prompts/{stage}.md")
+        } else {
+          "built-in".to_string()
+        };
+        let prompt = match *stage {
+          "analyze" => agent::analyze::system_prompt(&config),
+          other => config.prompts.get(other).to_string(),
+        };
+        if i > 0 {
+          println!();
+        }
+        println!("==> {stage} ({source})");
+        println!("{}", prompt.trim_end());
       }
       Ok(())
     }
@@ -444,7 +939,8 @@ async fn run(cli: Cli) -> Result<()> {
         config.analyze_tools.clone(),
         config.mcp_config.clone(),
         Some(&config.memory_server),
-      );
+      )
+      .with_cli(&config.claude);
       let mut total = 0;
       let mut passed = 0;
 
@@ -483,6 +979,63 @@ async fn run(cli: Cli) -> Result<()> {
       }
       Ok(())
     }
+    Commands::History { days, format } => {
+      let repo_path = Config::repo_path();
+      let entries = pfl_forge::knowledge::history::load_all(&repo_path)?;
+      let since = days.map(|d| chrono::Utc::now() - chrono::Duration::days(d));
+      let stats = pfl_forge::knowledge::stats::summarize(&entries, since);
+
+      match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+        OutputFormat::Text => {
+          println!(
+            "{}",
+            locale.history_totals(
+              stats.intents,
+              stats.success,
+              stats.failed,
+              stats.escalated,
+              stats.success_rate
+            )
+          );
+          println!("{}: {}", t(Msg::Retries), stats.retries);
+          println!("{}: ${:.2}", t(Msg::TotalCost), stats.total_cost_usd);
+          if !stats.steps.is_empty() {
+            println!();
+            for step in &stats.steps {
+              println!(
+                "{:<10} {}",
+                step.step,
+                locale.step_runs(step.runs, step.avg_secs)
+              );
+            }
+          }
+        }
+      }
+      Ok(())
+    }
+    Commands::Report { html } => {
+      let repo_path = Config::repo_path();
+      let path = pfl_forge::knowledge::html_report::write(&repo_path, &html, locale)?;
+      println!("{}: {}", t(Msg::Created), path.display());
+      Ok(())
+    }
+    Commands::Export { format, output } => {
+      let repo_path = Config::repo_path();
+      let rows = pfl_forge::knowledge::export::rows(&repo_path)?;
+      let content = match format {
+        ExportFormat::Csv => pfl_forge::knowledge::export::to_csv(&rows),
+        ExportFormat::Json => pfl_forge::knowledge::export::to_json(&rows)?,
+      };
+      match output {
+        Some(path) => {
+          std::fs::write(&path, content)?;
+          println!("{}: {}", t(Msg::Created), path.display());
+        }
+        None => print!("{content}"),
+      }
+      Ok(())
+    }
     Commands::Answer { id, answer } => {
       let repo_path = Config::repo_path();
       let intents_dir = repo_path.join(".forge").join("intents");
@@ -491,38 +1044,30 @@ async fn run(cli: Cli) -> Result<()> {
       match intents.iter().find(|i| i.id() == id) {
         Some(intent) => {
           let mut updated = intent.clone();
-          // Find the first unanswered clarification and fill it
-          let unanswered = updated
-            .clarifications
-            .iter_mut()
-            .find(|c| c.answer.is_none());
-          match unanswered {
-            Some(c) => {
-              println!("Q: {}", c.question);
+          match updated.answer_next(answer.clone()) {
+            Some(question) => {
+              println!("Q: {question}");
               println!("A: {answer}");
-              c.answer = Some(answer);
 
-              // If all clarifications are now answered, auto-approve
+              // All clarifications answered → answer_next auto-approved
               if !updated.needs_clarification() {
-                updated.status = pfl_forge::intent::registry::IntentStatus::Approved;
-                println!("{id}: all clarifications answered, approved");
+                println!("{id}: {}", t(Msg::AllAnswered));
               } else {
-                let remaining = updated
-                  .clarifications
-                  .iter()
-                  .filter(|c| c.answer.is_none())
-                  .count();
-                println!("{id}: answered ({remaining} question(s) remaining)");
+                println!(
+                  "{id}: {}",
+                  locale.questions_remaining(updated.unanswered_count())
+                );
               }
               runner::update_intent_file(&repo_path, &updated)?;
+              runner::watch::request_wake(&repo_path);
             }
             None => {
-              println!("{id}: no unanswered clarifications");
+              println!("{id}: {}", t(Msg::NoUnansweredClarifications));
             }
           }
         }
         None => {
-          eprintln!("{id}: not found");
+          eprintln!("{id}: {}", t(Msg::NotFound));
         }
       }
       Ok(())
@@ -532,6 +1077,7 @@ async fn run(cli: Cli) -> Result<()> {
       let intents_dir = repo_path.join(".forge").join("intents");
       let intents = pfl_forge::intent::registry::Intent::fetch_all(&intents_dir)?;
 
+      let mut missing = Vec::new();
       for raw_id in ids.split(',') {
         let id = raw_id.trim();
         if id.is_empty() {
@@ -540,17 +1086,106 @@ async fn run(cli: Cli) -> Result<()> {
         match intents.iter().find(|i| i.id() == id) {
           Some(intent) => {
             let mut updated = intent.clone();
-            updated.status = pfl_forge::intent::registry::IntentStatus::Approved;
-            runner::update_intent_file(&repo_path, &updated)?;
-            println!("{id}: approved");
+            runner::approve_intent(&repo_path, &mut updated)?;
+            runner::watch::request_wake(&repo_path);
+            println!("{id}: {}", t(Msg::Approved));
           }
-          None => {
-            eprintln!("{id}: not found");
+          None => missing.push(id),
+        }
+      }
+      intents_not_found(&missing)
+    }
+    Commands::Reject { ids } => {
+      let repo_path = Config::repo_path();
+      let intents_dir = repo_path.join(".forge").join("intents");
+      let intents = pfl_forge::intent::registry::Intent::fetch_all(&intents_dir)?;
+
+      let mut missing = Vec::new();
+      for raw_id in ids.split(',') {
+        let id = raw_id.trim();
+        if id.is_empty() {
+          continue;
+        }
+        match intents.iter().find(|i| i.id() == id) {
+          Some(intent) => {
+            let mut updated = intent.clone();
+            updated.reject();
+            runner::update_intent_file(&repo_path, &updated)?;
+            println!("{id}: {}", t(Msg::Rejected));
           }
+          None => missing.push(id),
         }
       }
+      intents_not_found(&missing)
+    }
+    Commands::Disable => {
+      let repo_path = Config::repo_path();
+      runner::watch::set_disabled(&repo_path, true)?;
+      println!("{}", t(Msg::Disabled));
       Ok(())
     }
-    Commands::Init | Commands::Draft { .. } => unreachable!("handled before config load"),
+    Commands::Enable => {
+      let repo_path = Config::repo_path();
+      runner::watch::set_disabled(&repo_path, false)?;
+      if config.enabled {
+        println!("{}", t(Msg::Enabled));
+      } else {
+        println!("{}", t(Msg::DisabledInConfig));
+      }
+      Ok(())
+    }
+    Commands::Show { id } => {
+      let repo_path = Config::repo_path();
+      let intents_dir = repo_path.join(".forge").join("intents");
+      let intents = pfl_forge::intent::registry::Intent::fetch_all(&intents_dir)?;
+      let Some(intent) = intents.iter().find(|i| i.id() == id) else {
+        return intents_not_found(&[id.as_str()]);
+      };
+
+      println!("{} — {}", intent.id(), intent.title);
+      println!("status: {}", locale.status_label(&intent.status));
+      for (key, value) in [
+        ("type", intent.intent_type.as_deref()),
+        ("risk", intent.risk.as_deref()),
+        ("source", Some(intent.source.as_str())),
+        ("parent", intent.parent.as_deref()),
+        ("created_at", intent.created_at.as_deref()),
+        ("run_id", intent.run_id.as_deref()),
+      ] {
+        if let Some(value) = value {
+          println!("{key}: {value}");
+        }
+      }
+      if !intent.depends_on.is_empty() {
+        println!("depends_on: {}", intent.depends_on.join(", "));
+      }
+      if !intent.tags.is_empty() {
+        println!("tags: {}", intent.tags.join(", "));
+      }
+      println!("\n{}", intent.body.trim_end());
+      if !intent.clarifications.is_empty() {
+        println!();
+        for c in &intent.clarifications {
+          println!("Q: {}", c.question);
+          println!("A: {}", c.answer.as_deref().unwrap_or("-"));
+        }
+      }
+      if pfl_forge::task::tasks_exist(&repo_path, &id) {
+        println!();
+        for task in pfl_forge::task::read_all_tasks(&repo_path, &id)? {
+          println!(
+            "- [{}] {} ({}): {}",
+            locale.work_status_label(&task.status),
+            task.id,
+            task.complexity,
+            task.title
+          );
+        }
+      }
+      Ok(())
+    }
+    Commands::Init | Commands::Draft { .. } | Commands::Doctor | Commands::Completions { .. } => {
+      unreachable!("handled before config load")
+    }
   }
 }
@@ -0,0 +1,189 @@
+//! Minimal MCP server (JSON-RPC 2.0 over stdio) exposing forge operations as
+//! structured tools, so the Operator session does not have to build shell commands.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::intent::registry::{Intent, IntentStatus};
+use crate::runner;
+
+/// Name the Operator registers this server under; tools appear as `mcp__pfl-forge__<tool>`.
+pub const SERVER_NAME: &str = "pfl-forge";
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Serve requests from stdin until EOF. `trigger_run` starts a background
+/// `pfl-forge run` and returns its pid.
+pub fn serve(repo_path: &Path, trigger_run: &dyn Fn() -> Result<u32>) -> Result<()> {
+  let stdin = std::io::stdin();
+  let mut stdout = std::io::stdout();
+  for line in stdin.lock().lines() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let response = match serde_json::from_str::<Value>(&line) {
+      Ok(request) => handle(repo_path, &request, trigger_run),
+      Err(e) => Some(error_response(
+        Value::Null,
+        -32700,
+        &format!("parse error: {e}"),
+      )),
+    };
+    if let Some(response) = response {
+      writeln!(stdout, "{response}")?;
+      stdout.flush()?;
+    }
+  }
+  Ok(())
+}
+
+/// Handle one JSON-RPC message. Notifications (no `id`) get no response.
+pub fn handle(
+  repo_path: &Path,
+  request: &Value,
+  trigger_run: &dyn Fn() -> Result<u32>,
+) -> Option<Value> {
+  let id = request.get("id").cloned()?;
+  let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+  let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+  let result = match method {
+    "initialize" => json!({
+      "protocolVersion": params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .unwrap_or(PROTOCOL_VERSION),
+      "capabilities": { "tools": {} },
+      "serverInfo": { "name": SERVER_NAME, "version": env!("CARGO_PKG_VERSION") },
+    }),
+    "ping" => json!({}),
+    "tools/list" => json!({ "tools": tool_definitions() }),
+    "tools/call" => {
+      let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+      let args = params.get("arguments").cloned().unwrap_or(json!({}));
+      match call_tool(repo_path, name, &args, trigger_run) {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+        Err(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": true }),
+      }
+    }
+    _ => {
+      return Some(error_response(
+        id,
+        -32601,
+        &format!("method not found: {method}"),
+      ))
+    }
+  };
+  Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+  json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tool_definitions() -> Value {
+  json!([
+    {
+      "name": "status",
+      "description": "List every intent with its ID, status and title.",
+      "inputSchema": { "type": "object", "properties": {} },
+    },
+    {
+      "name": "clarifications",
+      "description": "List intents with unanswered clarification questions, in order.",
+      "inputSchema": { "type": "object", "properties": {} },
+    },
+    {
+      "name": "answer",
+      "description": "Answer the next unanswered clarification of an intent. The intent is approved once all questions are answered. Multi-line answers are passed as-is.",
+      "inputSchema": {
+        "type": "object",
+        "properties": {
+          "id": { "type": "string", "description": "Intent ID" },
+          "answer": { "type": "string", "description": "Answer text" },
+        },
+        "required": ["id", "answer"],
+      },
+    },
+    {
+      "name": "run",
+      "description": "Start `pfl-forge run` in the background to process approved intents. Returns immediately.",
+      "inputSchema": { "type": "object", "properties": {} },
+    },
+  ])
+}
+
+fn call_tool(
+  repo_path: &Path,
+  name: &str,
+  args: &Value,
+  trigger_run: &dyn Fn() -> Result<u32>,
+) -> std::result::Result<String, String> {
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let load = || Intent::fetch_all(&intents_dir).map_err(|e| e.to_string());
+
+  match name {
+    "status" => {
+      let intents = load()?;
+      let rows: Vec<Value> = intents
+        .iter()
+        .map(|i| json!({ "id": i.id(), "status": i.status, "title": i.title }))
+        .collect();
+      Ok(Value::Array(rows).to_string())
+    }
+    "clarifications" => {
+      let intents = load()?;
+      let rows: Vec<Value> = intents
+        .iter()
+        .filter(|i| i.needs_clarification())
+        .map(|i| {
+          let questions: Vec<&str> = i
+            .clarifications
+            .iter()
+            .filter(|c| c.answer.is_none())
+            .map(|c| c.question.as_str())
+            .collect();
+          json!({ "id": i.id(), "title": i.title, "questions": questions })
+        })
+        .collect();
+      Ok(Value::Array(rows).to_string())
+    }
+    "answer" => {
+      let id = args
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or("missing argument: id")?;
+      let answer = args
+        .get("answer")
+        .and_then(Value::as_str)
+        .ok_or("missing argument: answer")?;
+      let mut intent = load()?
+        .into_iter()
+        .find(|i| i.id() == id)
+        .ok_or_else(|| format!("intent not found: {id}"))?;
+      let question = intent
+        .answer_next(answer.to_string())
+        .ok_or_else(|| format!("{id} has no unanswered clarifications"))?;
+      runner::update_intent_file(repo_path, &intent).map_err(|e| e.to_string())?;
+      runner::watch::request_wake(repo_path);
+      Ok(
+        json!({
+          "id": id,
+          "question": question,
+          "remaining": intent.unanswered_count(),
+          "approved": intent.status == IntentStatus::Approved,
+        })
+        .to_string(),
+      )
+    }
+    "run" => {
+      let pid = trigger_run().map_err(|e| e.to_string())?;
+      Ok(json!({ "started": true, "pid": pid }).to_string())
+    }
+    _ => Err(format!("unknown tool: {name}")),
+  }
+}
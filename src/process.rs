@@ -0,0 +1,189 @@
+//! Child processes with the `worker_env` policy applied.
+//!
+//! Every process forge spawns for a worker — the `claude` CLI, git, setup and
+//! check commands — is created through [`command`] / [`async_command`], so
+//! variables outside `worker_env.allow` (or matching `worker_env.deny`) never
+//! reach an agent-visible shell. The policy is installed once at startup with
+//! [`init`]; until then the environment is inherited unchanged. Variables
+//! added with [`scoped_env`] go to every process the current thread spawns.
+
+use std::cell::RefCell;
+use std::ffi::{OsStr, OsString};
+use std::sync::OnceLock;
+
+use crate::config::WorkerEnv;
+
+static POLICY: OnceLock<WorkerEnv> = OnceLock::new();
+
+thread_local! {
+  static SCOPED_ENV: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Install the policy for the rest of the process. Later calls are ignored.
+pub fn init(policy: &WorkerEnv) {
+  let _ = POLICY.set(policy.clone());
+}
+
+pub fn command(program: impl AsRef<OsStr>) -> std::process::Command {
+  let mut cmd = std::process::Command::new(program);
+  if let Some(policy) = active_policy() {
+    cmd
+      .env_clear()
+      .envs(filtered_env(policy, std::env::vars_os()));
+  }
+  SCOPED_ENV.with_borrow(|vars| cmd.envs(vars.iter().map(|(k, v)| (k, v))));
+  cmd
+}
+
+pub fn async_command(program: impl AsRef<OsStr>) -> tokio::process::Command {
+  let mut cmd = tokio::process::Command::new(program);
+  if let Some(policy) = active_policy() {
+    cmd
+      .env_clear()
+      .envs(filtered_env(policy, std::env::vars_os()));
+  }
+  SCOPED_ENV.with_borrow(|vars| cmd.envs(vars.iter().map(|(k, v)| (k, v))));
+  cmd
+}
+
+/// Add `vars` to every process spawned on this thread until the guard drops.
+/// Used for settings that belong to one intent's worker rather than the whole process.
+pub fn scoped_env(vars: Vec<(String, String)>) -> ScopedEnv {
+  let previous = SCOPED_ENV.with_borrow_mut(|current| {
+    let previous = current.clone();
+    current.extend(vars);
+    previous
+  });
+  ScopedEnv { previous }
+}
+
+/// Restores the thread's previous [`scoped_env`] variables on drop.
+pub struct ScopedEnv {
+  previous: Vec<(String, String)>,
+}
+
+impl Drop for ScopedEnv {
+  fn drop(&mut self) {
+    let previous = std::mem::take(&mut self.previous);
+    SCOPED_ENV.with_borrow_mut(|current| *current = previous);
+  }
+}
+
+fn active_policy() -> Option<&'static WorkerEnv> {
+  POLICY.get().filter(|policy| !policy.is_inherit_all())
+}
+
+/// The variables of `vars` a worker may see: those matching `allow` (all when
+/// unset) and not matching `deny`. Names that are not valid Unicode match no
+/// pattern, so they pass only when `allow` is unset.
+pub fn filtered_env(
+  policy: &WorkerEnv,
+  vars: impl IntoIterator<Item = (OsString, OsString)>,
+) -> Vec<(OsString, OsString)> {
+  vars
+    .into_iter()
+    .filter(|(name, _)| match name.to_str() {
+      Some(name) => {
+        let allowed = match &policy.allow {
+          Some(allow) => allow.iter().any(|p| matches(p, name)),
+          None => true,
+        };
+        allowed && !policy.deny.iter().any(|p| matches(p, name))
+      }
+      None => policy.allow.is_none(),
+    })
+    .collect()
+}
+
+/// An exact name, or a prefix followed by `*` (`AWS_*`).
+fn matches(pattern: &str, name: &str) -> bool {
+  match pattern.strip_suffix('*') {
+    Some(prefix) => name.starts_with(prefix),
+    None => pattern == name,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn vars(names: &[&str]) -> Vec<(OsString, OsString)> {
+    names.iter().map(|n| (n.into(), "v".into())).collect()
+  }
+
+  fn names(env: Vec<(OsString, OsString)>) -> Vec<OsString> {
+    env.into_iter().map(|(name, _)| name).collect()
+  }
+
+  #[test]
+  fn allowに一致する変数だけを残す() {
+    let policy = WorkerEnv {
+      allow: Some(vec!["PATH".into(), "LC_*".into()]),
+      deny: vec![],
+    };
+    let env = filtered_env(&policy, vars(&["PATH", "LC_ALL", "AWS_SECRET_ACCESS_KEY"]));
+    assert_eq!(names(env), vec!["PATH", "LC_ALL"]);
+  }
+
+  #[test]
+  fn denyはallowより優先する() {
+    let policy = WorkerEnv {
+      allow: None,
+      deny: vec!["AWS_*".into(), "GITHUB_TOKEN".into()],
+    };
+    let env = filtered_env(
+      &policy,
+      vars(&[
+        "HOME",
+        "AWS_ACCESS_KEY_ID",
+        "GITHUB_TOKEN",
+        "GITHUB_ACTIONS",
+      ]),
+    );
+    assert_eq!(names(env), vec!["HOME", "GITHUB_ACTIONS"]);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn unicodeでない変数名と値があってもpanicしない() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let odd_name = OsString::from_vec(vec![b'X', 0xff]);
+    let odd_value = OsString::from_vec(vec![0xfe]);
+    let env = vec![
+      (odd_name.clone(), "v".into()),
+      ("PATH".into(), odd_value.clone()),
+    ];
+
+    let inherit = WorkerEnv {
+      allow: None,
+      deny: vec!["AWS_*".into()],
+    };
+    assert_eq!(
+      filtered_env(&inherit, env.clone()),
+      vec![(odd_name, "v".into()), ("PATH".into(), odd_value.clone())]
+    );
+
+    let allow = WorkerEnv {
+      allow: Some(vec!["PATH".into()]),
+      deny: vec![],
+    };
+    assert_eq!(filtered_env(&allow, env), vec![("PATH".into(), odd_value)]);
+  }
+
+  #[test]
+  fn scoped_envはguardが生きている間だけ子プロセスに渡る() {
+    let echo = || {
+      let output = command("sh")
+        .args(["-c", "printf %s \"$FORGE_SCOPED_TEST\""])
+        .output()
+        .unwrap();
+      String::from_utf8(output.stdout).unwrap()
+    };
+    {
+      let _guard = scoped_env(vec![("FORGE_SCOPED_TEST".into(), "set".into())]);
+      assert_eq!(echo(), "set");
+    }
+    assert_eq!(echo(), "");
+  }
+}
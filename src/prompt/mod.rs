@@ -1,8 +1,86 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
 pub const ANALYZE: &str = include_str!("analyze.md");
 pub const IMPLEMENT: &str = include_str!("implement.md");
 pub const REVIEW: &str = include_str!("review.md");
 pub const AUDIT: &str = include_str!("audit.md");
+pub const CONFLICT: &str = include_str!("conflict.md");
+pub const JSON_REPAIR: &str = include_str!("json_repair.md");
+pub const COMMIT_MESSAGE: &str = include_str!("commit_message.md");
 pub const REFLECT: &str = include_str!("reflect.md");
 pub const OPERATOR: &str = include_str!("operator.md");
+pub const OPERATOR_REPORT: &str = include_str!("operator_report.md");
 pub const SKILL_OBSERVE: &str = include_str!("skill_observe.md");
 pub const SKILL_ABSTRACT: &str = include_str!("skill_abstract.md");
+
+/// Stages whose system prompt a repo can replace with `.forge/prompts/<stage>.md`.
+pub const OVERRIDABLE: &[&str] = &["analyze", "implement", "review"];
+
+/// The built-in system prompt of an overridable stage.
+pub fn builtin(stage: &str) -> Option<&'static str> {
+  match stage {
+    "analyze" => Some(ANALYZE),
+    "implement" => Some(IMPLEMENT),
+    "review" => Some(REVIEW),
+    _ => None,
+  }
+}
+
+pub fn overrides_dir(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("prompts")
+}
+
+/// System prompts read from the target repo's `.forge/prompts/`, by stage.
+#[derive(Debug, Clone, Default)]
+pub struct Overrides(BTreeMap<String, String>);
+
+impl Overrides {
+  pub fn load(repo_path: &Path) -> Result<Self> {
+    let dir = overrides_dir(repo_path);
+    let mut prompts = BTreeMap::new();
+    for stage in OVERRIDABLE {
+      let path = dir.join(format!("{stage}.md"));
+      if path.is_file() {
+        prompts.insert(stage.to_string(), std::fs::read_to_string(path)?);
+      }
+    }
+    Ok(Self(prompts))
+  }
+
+  pub fn set(&mut self, stage: &str, prompt: impl Into<String>) {
+    self.0.insert(stage.to_string(), prompt.into());
+  }
+
+  pub fn is_overridden(&self, stage: &str) -> bool {
+    self.0.contains_key(stage)
+  }
+
+  /// The effective system prompt: the repo's override, else the built-in one.
+  pub fn get(&self, stage: &str) -> &str {
+    match self.0.get(stage) {
+      Some(prompt) => prompt,
+      None => builtin(stage).unwrap_or_default(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn リポジトリのプロンプトファイルで組み込みプロンプトを置き換える() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(overrides_dir(dir.path())).unwrap();
+    std::fs::write(overrides_dir(dir.path()).join("review.md"), "custom review").unwrap();
+    std::fs::write(overrides_dir(dir.path()).join("audit.md"), "ignored").unwrap();
+
+    let overrides = Overrides::load(dir.path()).unwrap();
+    assert_eq!(overrides.get("review"), "custom review");
+    assert_eq!(overrides.get("analyze"), ANALYZE);
+    assert!(!overrides.is_overridden("audit"));
+  }
+}
@@ -0,0 +1,538 @@
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use crate::agent::implement::Feedback;
+use crate::error::{ForgeError, Result};
+use crate::process;
+
+/// Result of a verification command run in the worktree after implement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckOutcome {
+  Passed,
+  /// Lint command exited non-zero. Holds the (truncated) command output.
+  LintFailure(String),
+  /// Coverage fell below `min_coverage`.
+  CoverageFailure {
+    coverage: f64,
+    min_coverage: f64,
+    output: String,
+  },
+}
+
+impl CheckOutcome {
+  /// Feedback for the corrective implement pass. `None` when the check passed.
+  pub fn feedback(&self) -> Option<Feedback<'_>> {
+    match self {
+      CheckOutcome::Passed => None,
+      CheckOutcome::LintFailure(output) => Some(Feedback::Lint(output)),
+      CheckOutcome::CoverageFailure {
+        coverage,
+        min_coverage,
+        output,
+      } => Some(Feedback::Coverage {
+        coverage: *coverage,
+        min_coverage: *min_coverage,
+        output,
+      }),
+    }
+  }
+}
+
+/// Maximum number of output bytes fed back to the Implement Agent.
+const MAX_FEEDBACK_LEN: usize = 8000;
+
+/// Directory holding the full output of each check run for one task of an intent.
+pub fn log_dir(repo_path: &Path, intent_id: &str, task_id: &str) -> PathBuf {
+  repo_path
+    .join(".forge")
+    .join("logs")
+    .join(intent_id)
+    .join(task_id)
+}
+
+/// Write the full output of a check to `{log_dir}/{name}-{attempt}.log`, numbering
+/// attempts from 1 so earlier runs (before a corrective pass, or in an earlier
+/// `run`) are kept.
+fn save_log(log_dir: &Path, name: &str, output: &str) {
+  let attempt = log_attempts(log_dir, name).last().map_or(1, |(n, _)| n + 1);
+  let result = std::fs::create_dir_all(log_dir)
+    .and_then(|()| std::fs::write(log_dir.join(format!("{name}-{attempt}.log")), output));
+  if let Err(e) = result {
+    warn!("failed to save {name} log: {e}");
+  }
+}
+
+/// The saved logs of check `name`, ordered by attempt.
+fn log_attempts(log_dir: &Path, name: &str) -> Vec<(u32, PathBuf)> {
+  let Ok(entries) = std::fs::read_dir(log_dir) else {
+    return Vec::new();
+  };
+  let mut attempts: Vec<(u32, PathBuf)> = entries
+    .filter_map(|e| e.ok())
+    .filter_map(|e| {
+      let file_name = e.file_name().to_string_lossy().into_owned();
+      let attempt = file_name
+        .strip_suffix(".log")?
+        .strip_prefix(name)?
+        .strip_prefix('-')?
+        .parse()
+        .ok()?;
+      Some((attempt, e.path()))
+    })
+    .collect();
+  attempts.sort();
+  attempts
+}
+
+/// The most recent log of check `name`.
+pub fn latest_log(log_dir: &Path, name: &str) -> Option<PathBuf> {
+  log_attempts(log_dir, name).pop().map(|(_, path)| path)
+}
+
+/// The most recent log of every check in `log_dir`, ordered by check name.
+pub fn latest_logs(log_dir: &Path) -> Vec<PathBuf> {
+  let Ok(entries) = std::fs::read_dir(log_dir) else {
+    return Vec::new();
+  };
+  let mut names: Vec<String> = entries
+    .filter_map(|e| e.ok())
+    .filter_map(|e| {
+      let file_name = e.file_name().to_string_lossy().into_owned();
+      let (name, _) = file_name.strip_suffix(".log")?.rsplit_once('-')?;
+      Some(name.to_string())
+    })
+    .collect();
+  names.sort();
+  names.dedup();
+  names
+    .iter()
+    .filter_map(|name| latest_log(log_dir, name))
+    .collect()
+}
+
+pub fn lint(worktree_path: &Path, command: &str, log_dir: &Path) -> Result<CheckOutcome> {
+  info!("lint: {command}");
+  let output = process::command("sh")
+    .args(["-c", command])
+    .current_dir(worktree_path)
+    .output()?;
+
+  let combined = format!(
+    "{}{}",
+    String::from_utf8_lossy(&output.stdout),
+    String::from_utf8_lossy(&output.stderr)
+  );
+  save_log(log_dir, "lint", &combined);
+
+  if output.status.success() {
+    return Ok(CheckOutcome::Passed);
+  }
+  Ok(CheckOutcome::LintFailure(excerpt(
+    &combined,
+    MAX_FEEDBACK_LEN,
+  )))
+}
+
+/// Run `coverage_command` and compare the reported percentage against `min_coverage`.
+/// The last `NN.N%` figure in the output is taken as the total.
+pub fn coverage(
+  worktree_path: &Path,
+  command: &str,
+  min_coverage: f64,
+  log_dir: &Path,
+) -> Result<CheckOutcome> {
+  info!("coverage: {command}");
+  let output = process::command("sh")
+    .args(["-c", command])
+    .current_dir(worktree_path)
+    .output()?;
+
+  let combined = format!(
+    "{}{}",
+    String::from_utf8_lossy(&output.stdout),
+    String::from_utf8_lossy(&output.stderr)
+  );
+  save_log(log_dir, "coverage", &combined);
+  if !output.status.success() {
+    return Err(ForgeError::Check(format!(
+      "coverage command failed: {command}: {}",
+      tail(&combined, MAX_FEEDBACK_LEN)
+    )));
+  }
+
+  let coverage = parse_coverage(&combined).ok_or_else(|| {
+    ForgeError::Check(format!(
+      "no coverage percentage found in output of: {command}"
+    ))
+  })?;
+  info!("coverage: {coverage:.1}% (min {min_coverage:.1}%)");
+
+  if coverage < min_coverage {
+    return Ok(CheckOutcome::CoverageFailure {
+      coverage,
+      min_coverage,
+      output: excerpt(&combined, MAX_FEEDBACK_LEN),
+    });
+  }
+  Ok(CheckOutcome::Passed)
+}
+
+fn parse_coverage(output: &str) -> Option<f64> {
+  output
+    .rsplit(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+    .filter_map(|token| token.strip_suffix('%'))
+    .find_map(|number| number.parse::<f64>().ok())
+}
+
+/// Benchmark figures for the base branch and HEAD. Lower is better.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchComparison {
+  pub base: f64,
+  pub head: f64,
+}
+
+impl BenchComparison {
+  /// How much slower HEAD is than the base, in percent. Negative when faster.
+  pub fn regression_pct(&self) -> f64 {
+    if self.base <= 0.0 {
+      return 0.0;
+    }
+    (self.head - self.base) / self.base * 100.0
+  }
+
+  pub fn summary(&self) -> String {
+    format!(
+      "base {:.3}, head {:.3} ({:+.1}%)",
+      self.base,
+      self.head,
+      self.regression_pct()
+    )
+  }
+}
+
+/// Run `bench_command` on HEAD and on a detached checkout of `origin/<base_branch>`.
+/// `subdir` is the task's `checks.dir`, applied to both checkouts. Each run is wrapped
+/// in its own `check_setup` / `check_teardown`, so both sides are measured the same way.
+/// Output is saved per attempt as `bench-head-N.log` and `bench-base-N.log`.
+#[allow(clippy::too_many_arguments)]
+pub fn bench(
+  worktree_path: &Path,
+  subdir: Option<&str>,
+  base_branch: &str,
+  command: &str,
+  setup_commands: &[String],
+  teardown_commands: &[String],
+  log_dir: &Path,
+) -> Result<BenchComparison> {
+  let bench_in = |root: &Path, log_name: &str| {
+    let result = setup(root, setup_commands)
+      .and_then(|()| check_dir(root, subdir))
+      .and_then(|dir| run_bench(&dir, command, log_dir, log_name));
+    teardown(root, teardown_commands);
+    result
+  };
+
+  let head = bench_in(worktree_path, "bench-head")?;
+
+  // A checkout left behind by an interrupted run would make `worktree add` fail
+  let base_path = bench_base_path(worktree_path);
+  remove_bench_worktree(worktree_path, &base_path);
+  let output = process::command("git")
+    .args(["worktree", "add", "--detach"])
+    .arg(&base_path)
+    .arg(format!("origin/{base_branch}"))
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    return Err(ForgeError::Git(format!(
+      "bench base worktree add failed: {}",
+      String::from_utf8_lossy(&output.stderr)
+    )));
+  }
+
+  let base = bench_in(&base_path, "bench-base");
+  remove_bench_worktree(worktree_path, &base_path);
+
+  let comparison = BenchComparison { base: base?, head };
+  info!("bench: {}", comparison.summary());
+  Ok(comparison)
+}
+
+/// Remove the base checkout, including one git no longer knows about.
+fn remove_bench_worktree(worktree_path: &Path, base_path: &Path) {
+  let _ = process::command("git")
+    .args(["worktree", "remove", "--force"])
+    .arg(base_path)
+    .current_dir(worktree_path)
+    .output();
+  if base_path.exists() {
+    if let Err(e) = std::fs::remove_dir_all(base_path) {
+      warn!(
+        "failed to remove bench base worktree {}: {e}",
+        base_path.display()
+      );
+    }
+  }
+  let _ = process::command("git")
+    .args(["worktree", "prune"])
+    .current_dir(worktree_path)
+    .output();
+}
+
+/// `root`, or the task's `checks.dir` under it. The directory must exist and
+/// stay inside `root` once symlinks and `..` are resolved; a path leading
+/// outside is a policy violation.
+pub fn check_dir(root: &Path, dir: Option<&str>) -> Result<PathBuf> {
+  let Some(dir) = dir else {
+    return Ok(root.to_path_buf());
+  };
+  if Path::new(dir).is_absolute() {
+    return Err(ForgeError::PolicyViolation(format!(
+      "checks.dir must be relative to the worktree: {dir}"
+    )));
+  }
+  let resolved = root
+    .join(dir)
+    .canonicalize()
+    .map_err(|_| ForgeError::Check(format!("check dir not found: {dir}")))?;
+  if !resolved.starts_with(root.canonicalize()?) {
+    return Err(ForgeError::PolicyViolation(format!(
+      "checks.dir escapes the worktree: {dir}"
+    )));
+  }
+  if !resolved.is_dir() {
+    return Err(ForgeError::Check(format!(
+      "checks.dir is not a directory: {dir}"
+    )));
+  }
+  Ok(resolved)
+}
+
+/// The lint command to run: the task's `checks.lint_command` when `allowed`
+/// lists it exactly, else the repository's. The Runner runs it with `sh -c` on
+/// the host, so any other agent-chosen command is a policy violation.
+pub fn lint_command<'a>(
+  repo_command: Option<&'a String>,
+  task_command: Option<&'a String>,
+  allowed: &[String],
+) -> Result<Option<&'a String>> {
+  match task_command {
+    Some(command) if allowed.contains(command) => Ok(Some(command)),
+    Some(command) => Err(ForgeError::PolicyViolation(format!(
+      "checks.lint_command is not listed in lint_commands: {command}"
+    ))),
+    None => Ok(repo_command),
+  }
+}
+
+fn bench_base_path(worktree_path: &Path) -> PathBuf {
+  let name = worktree_path
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  worktree_path.with_file_name(format!("{name}.bench-base"))
+}
+
+fn run_bench(dir: &Path, command: &str, log_dir: &Path, log_name: &str) -> Result<f64> {
+  info!("bench: {command} in {}", dir.display());
+  let output = process::command("sh")
+    .args(["-c", command])
+    .current_dir(dir)
+    .output()?;
+
+  let combined = format!(
+    "{}{}",
+    String::from_utf8_lossy(&output.stdout),
+    String::from_utf8_lossy(&output.stderr)
+  );
+  save_log(log_dir, log_name, &combined);
+  if !output.status.success() {
+    return Err(ForgeError::Check(format!(
+      "bench command failed: {command}: {}",
+      tail(&combined, MAX_FEEDBACK_LEN)
+    )));
+  }
+
+  parse_bench_value(&combined)
+    .ok_or_else(|| ForgeError::Check(format!("no benchmark figure found in output of: {command}")))
+}
+
+/// The last number in the output, ignoring a trailing unit such as `ms` or `ns/iter`.
+fn parse_bench_value(output: &str) -> Option<f64> {
+  output
+    .rsplit(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ':')
+    .map(|token| {
+      token
+        .trim_end_matches(|c: char| !c.is_ascii_digit())
+        .replace(',', "")
+    })
+    .find_map(|number| number.parse::<f64>().ok())
+}
+
+/// Run `format_command` in the worktree. Errors if the formatter exits non-zero.
+pub fn format(worktree_path: &Path, command: &str) -> Result<()> {
+  info!("format: {command}");
+  let output = process::command("sh")
+    .args(["-c", command])
+    .current_dir(worktree_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Check(format!(
+      "format command failed: {command}: {}",
+      tail(&stderr, MAX_FEEDBACK_LEN)
+    )));
+  }
+  Ok(())
+}
+
+/// Run `check_setup` commands in order, stopping at the first failure.
+pub fn setup(worktree_path: &Path, commands: &[String]) -> Result<()> {
+  for cmd in commands {
+    info!("check setup: {cmd}");
+    let output = process::command("sh")
+      .args(["-c", cmd])
+      .current_dir(worktree_path)
+      .output()?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(ForgeError::Check(format!(
+        "setup command failed: {cmd}: {}",
+        tail(&stderr, MAX_FEEDBACK_LEN)
+      )));
+    }
+  }
+  Ok(())
+}
+
+/// Run `check_teardown` commands. Failures are logged and do not stop later commands.
+pub fn teardown(worktree_path: &Path, commands: &[String]) {
+  for cmd in commands {
+    info!("check teardown: {cmd}");
+    let result = process::command("sh")
+      .args(["-c", cmd])
+      .current_dir(worktree_path)
+      .output();
+    match result {
+      Ok(output) if output.status.success() => {}
+      Ok(output) => warn!(
+        "teardown command failed: {cmd}: {}",
+        String::from_utf8_lossy(&output.stderr)
+      ),
+      Err(e) => warn!("teardown command failed: {cmd}: {e}"),
+    }
+  }
+}
+
+/// Shorten output for a prompt: keep the first quarter (the first compiler error
+/// is usually there) and the rest from the end (test summaries), marking the gap.
+pub(super) fn excerpt(text: &str, max_len: usize) -> String {
+  if text.len() <= max_len {
+    return text.to_string();
+  }
+  let mut head_end = max_len / 4;
+  while !text.is_char_boundary(head_end) {
+    head_end -= 1;
+  }
+  let tail = tail(text, max_len - head_end);
+  let omitted = text.len() - head_end - tail.len();
+  format!(
+    "{}\n... [{omitted} bytes omitted] ...\n{tail}",
+    &text[..head_end]
+  )
+}
+
+/// Keep the last `max_len` bytes — lint and test tools print the summary at the end.
+fn tail(text: &str, max_len: usize) -> &str {
+  if text.len() <= max_len {
+    return text;
+  }
+  let mut start = text.len() - max_len;
+  while !text.is_char_boundary(start) {
+    start += 1;
+  }
+  &text[start..]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 検証ログを試行ごとに残し最新を試行番号で選ぶ() {
+    let dir = tempfile::tempdir().unwrap();
+    for i in 1..=10 {
+      save_log(dir.path(), "lint", &format!("run {i}"));
+    }
+    save_log(dir.path(), "coverage", "cov");
+
+    let latest = latest_log(dir.path(), "lint").unwrap();
+    assert!(latest.ends_with("lint-10.log"));
+    assert!(dir.path().join("lint-1.log").exists());
+    let names: Vec<String> = latest_logs(dir.path())
+      .iter()
+      .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+      .collect();
+    assert_eq!(names, vec!["coverage-1.log", "lint-10.log"]);
+  }
+
+  #[test]
+  fn 最後のパーセンテージをカバレッジとして読む() {
+    let output = "src/lib.rs 50.00%\nTOTAL 1234 100 (81.25%)\n";
+    assert_eq!(parse_coverage(output), Some(81.25));
+  }
+
+  #[test]
+  fn パーセンテージがなければnone() {
+    assert_eq!(parse_coverage("no numbers here"), None);
+  }
+
+  #[test]
+  fn 最後の数値をベンチマーク値として読む() {
+    assert_eq!(parse_bench_value("total: 12.5ms\n"), Some(12.5));
+    let output = "running 1 bench\ntest parse ... bench: 1,234 ns/iter\n";
+    assert_eq!(parse_bench_value(output), Some(1234.0));
+  }
+
+  #[test]
+  fn ベースより遅ければ正の悪化率() {
+    let comparison = BenchComparison {
+      base: 100.0,
+      head: 125.0,
+    };
+    assert_eq!(comparison.regression_pct(), 25.0);
+    assert_eq!(comparison.summary(), "base 100.000, head 125.000 (+25.0%)");
+  }
+
+  #[test]
+  fn 短い出力はそのまま返す() {
+    assert_eq!(tail("error: x", 100), "error: x");
+  }
+
+  #[test]
+  fn 長い出力は末尾を残す() {
+    let text = format!("{}summary", "a".repeat(100));
+    assert_eq!(tail(&text, 7), "summary");
+  }
+
+  #[test]
+  fn 長い出力は先頭と末尾を残して省略を明示する() {
+    let text = format!("error[E0308]{}test result: FAILED", "x".repeat(1000));
+    let result = excerpt(&text, 80);
+    assert!(result.starts_with("error[E0308]"));
+    assert!(result.ends_with("test result: FAILED"));
+    assert!(result.contains("bytes omitted"));
+  }
+
+  #[test]
+  fn 短い出力は省略しない() {
+    assert_eq!(excerpt("ok", 80), "ok");
+  }
+
+  #[test]
+  fn マルチバイト境界で切らない() {
+    let text = "あいう";
+    assert_eq!(tail(text, 4), "う");
+  }
+}
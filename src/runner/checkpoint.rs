@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::Result;
+use crate::git;
+
+/// Where a long-running worker was at its last snapshot. Written to
+/// `.forge/checkpoints/<intent>.yaml` while the step runs, so a crash leaves it behind.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Checkpoint {
+  pub task_id: String,
+  pub step: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub head: Option<String>,
+  pub commits: u32,
+  pub elapsed_secs: u64,
+  /// Known only once the Claude process has exited.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub num_turns: Option<u64>,
+  /// Set by the final snapshot once the Claude process has returned, whether it
+  /// succeeded, failed or timed out. Absent only when the step was cut off.
+  #[serde(default)]
+  pub finished: bool,
+  pub updated_at: String,
+}
+
+fn path(repo_path: &Path, intent_id: &str) -> PathBuf {
+  repo_path
+    .join(".forge")
+    .join("checkpoints")
+    .join(format!("{intent_id}.yaml"))
+}
+
+pub fn write(repo_path: &Path, intent_id: &str, checkpoint: &Checkpoint) -> Result<()> {
+  let path = path(repo_path, intent_id);
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, serde_yaml::to_string(checkpoint)?)?;
+  Ok(())
+}
+
+pub fn read(repo_path: &Path, intent_id: &str) -> Option<Checkpoint> {
+  let content = std::fs::read_to_string(path(repo_path, intent_id)).ok()?;
+  serde_yaml::from_str(&content).ok()
+}
+
+pub fn clear(repo_path: &Path, intent_id: &str) {
+  let _ = std::fs::remove_file(path(repo_path, intent_id));
+}
+
+/// The task whose implement step was cut off and can be continued in its session:
+/// the step never wrote its final snapshot and the worktree is still at the
+/// snapshot's HEAD. Otherwise the step restarts.
+pub fn interrupted_task<'a>(
+  checkpoint: &'a Checkpoint,
+  worktree_head: Option<&str>,
+) -> Option<&'a str> {
+  let interrupted = checkpoint.step == "implement" && !checkpoint.finished;
+  let intact = checkpoint.head.is_some() && checkpoint.head.as_deref() == worktree_head;
+  (interrupted && intact).then_some(checkpoint.task_id.as_str())
+}
+
+/// Periodically snapshots one worker step (HEAD, commits ahead of base, elapsed).
+pub struct Tracker<'a> {
+  repo_path: &'a Path,
+  intent_id: &'a str,
+  task_id: &'a str,
+  step: &'a str,
+  worktree_path: &'a Path,
+  base_branch: &'a str,
+  interval: Duration,
+  started: Instant,
+}
+
+impl<'a> Tracker<'a> {
+  pub fn new(
+    repo_path: &'a Path,
+    intent_id: &'a str,
+    task_id: &'a str,
+    step: &'a str,
+    worktree_path: &'a Path,
+    base_branch: &'a str,
+    interval: Duration,
+  ) -> Self {
+    Self {
+      repo_path,
+      intent_id,
+      task_id,
+      step,
+      worktree_path,
+      base_branch,
+      interval: interval.max(Duration::from_secs(1)),
+      started: Instant::now(),
+    }
+  }
+
+  /// Run `f`, writing a checkpoint immediately and then every `interval` until it returns.
+  pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+    let (stop, stopped) = mpsc::channel::<()>();
+    std::thread::scope(|s| {
+      s.spawn(move || loop {
+        self.save(None, false);
+        if !matches!(
+          stopped.recv_timeout(self.interval),
+          Err(RecvTimeoutError::Timeout)
+        ) {
+          break;
+        }
+      });
+      let result = f();
+      drop(stop);
+      result
+    })
+  }
+
+  /// Write the final snapshot once the step has returned, successfully or not.
+  pub fn finish(&self, num_turns: Option<u64>) {
+    self.save(num_turns, true);
+  }
+
+  fn save(&self, num_turns: Option<u64>, finished: bool) {
+    let checkpoint = Checkpoint {
+      task_id: self.task_id.to_string(),
+      step: self.step.to_string(),
+      head: git::branch::head_commit(self.worktree_path).ok(),
+      commits: git::branch::commit_count(self.worktree_path, self.base_branch, "HEAD").unwrap_or(0),
+      elapsed_secs: self.started.elapsed().as_secs(),
+      num_turns,
+      finished,
+      updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = write(self.repo_path, self.intent_id, &checkpoint) {
+      warn!("failed to write checkpoint for {}: {e}", self.intent_id);
+    }
+  }
+}
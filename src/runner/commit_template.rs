@@ -0,0 +1,116 @@
+use regex::Regex;
+
+use crate::intent::registry::Intent;
+
+/// Rewrite a commit subject to follow `commit_template`. Returns `None` when the
+/// subject already conforms, so the pass is idempotent.
+///
+/// Placeholders: `{intent_id}`, `{type}` and `{subject}` (the worker's subject).
+/// `{type}` keeps the conventional-commit type the worker wrote (`fix: `, `feat(x)!: `),
+/// moved out of the subject; only when there is none is it filled with the intent type
+/// (`chore` if unset).
+pub fn apply(template: &str, intent: &Intent, subject: &str) -> Option<String> {
+  if conforms(template, intent, subject) {
+    return None;
+  }
+
+  let (kind, subject) = match split_type_prefix(subject) {
+    Some((kind, rest)) if template.contains("{type}") => (kind, rest),
+    _ => (intent.intent_type.as_deref().unwrap_or("chore"), subject),
+  };
+  Some(
+    template
+      .replace("{intent_id}", intent.id())
+      .replace("{type}", kind)
+      .replace("{subject}", subject),
+  )
+}
+
+/// Whether `subject` already reads as the template rendered with some type.
+fn conforms(template: &str, intent: &Intent, subject: &str) -> bool {
+  let mut pattern = String::from("^");
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    pattern.push_str(&regex::escape(&rest[..start]));
+    let after = &rest[start..];
+    let (piece, len) = if after.starts_with("{intent_id}") {
+      (regex::escape(intent.id()), "{intent_id}".len())
+    } else if after.starts_with("{type}") {
+      (TYPE_PATTERN.to_string(), "{type}".len())
+    } else if after.starts_with("{subject}") {
+      (".+".to_string(), "{subject}".len())
+    } else {
+      (regex::escape("{"), 1)
+    };
+    pattern.push_str(&piece);
+    rest = &after[len..];
+  }
+  pattern.push_str(&regex::escape(rest));
+  pattern.push('$');
+  Regex::new(&pattern).is_ok_and(|re| re.is_match(subject))
+}
+
+/// A conventional-commit type with optional scope and breaking-change marker.
+const TYPE_PATTERN: &str = r"[a-z]+(\([^)]*\))?!?";
+
+/// Split `feat(x)!: rest` into `("feat(x)!", "rest")`.
+fn split_type_prefix(subject: &str) -> Option<(&str, &str)> {
+  let (head, rest) = subject.split_once(": ")?;
+  let re = Regex::new(&format!("^{TYPE_PATTERN}$")).ok()?;
+  re.is_match(head).then_some((head, rest))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn intent(intent_type: Option<&str>) -> Intent {
+    let mut intent = Intent::synthetic("title", "body");
+    intent.intent_type = intent_type.map(String::from);
+    intent
+  }
+
+  #[test]
+  fn テンプレートに合わせて件名を書き換える() {
+    let result = apply(
+      "[{intent_id}] {type}: {subject}",
+      &intent(Some("fix")),
+      "validate email",
+    );
+    assert_eq!(
+      result.as_deref(),
+      Some("[eval-fixture] fix: validate email")
+    );
+  }
+
+  #[test]
+  fn workerが書いたtypeを残す() {
+    let result = apply(
+      "[{intent_id}] {type}: {subject}",
+      &intent(Some("fix")),
+      "feat(auth)!: add check",
+    );
+    assert_eq!(
+      result.as_deref(),
+      Some("[eval-fixture] feat(auth)!: add check")
+    );
+  }
+
+  #[test]
+  fn typeのないintentではchoreで補う() {
+    let result = apply("{type}: {subject}", &intent(None), "add retry");
+    assert_eq!(result.as_deref(), Some("chore: add retry"));
+  }
+
+  #[test]
+  fn 適合済みの件名は書き換えない() {
+    let template = "[{intent_id}] {type}: {subject}";
+    let i = intent(None);
+    let first = apply(template, &i, "add retry").unwrap();
+    assert_eq!(apply(template, &i, &first), None);
+    assert_eq!(
+      apply(template, &i, "[eval-fixture] feat(x): add retry"),
+      None
+    );
+  }
+}
@@ -0,0 +1,177 @@
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
+
+use crate::error::{ForgeError, Result};
+
+/// A five-field cron expression (`minute hour day-of-month month day-of-week`),
+/// used by `watch` to poll only at scheduled times. Each field accepts `*`,
+/// numbers, ranges (`1-5`), steps (`*/2`, `8-18/2`) and comma lists.
+/// Day-of-week is 0-7 with both 0 and 7 meaning Sunday.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+  minutes: Vec<bool>,
+  hours: Vec<bool>,
+  days: Vec<bool>,
+  months: Vec<bool>,
+  weekdays: Vec<bool>,
+  days_restricted: bool,
+  weekdays_restricted: bool,
+}
+
+/// How far ahead `next_after` searches before giving up (covers leap years).
+const SEARCH_MINUTES: i64 = 366 * 24 * 60 * 4;
+
+impl CronSchedule {
+  pub fn parse(expr: &str) -> Result<Self> {
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+      return Err(ForgeError::Config(format!(
+        "invalid schedule \"{expr}\": expected 5 fields (minute hour day month weekday)"
+      )));
+    };
+    let invalid = |e: String| ForgeError::Config(format!("invalid schedule \"{expr}\": {e}"));
+
+    let mut weekdays = parse_field(weekday, 0, 7).map_err(invalid)?;
+    if weekdays[7] {
+      weekdays[0] = true;
+    }
+    Ok(Self {
+      minutes: parse_field(minute, 0, 59).map_err(invalid)?,
+      hours: parse_field(hour, 0, 23).map_err(invalid)?,
+      days: parse_field(day, 1, 31).map_err(invalid)?,
+      months: parse_field(month, 1, 12).map_err(invalid)?,
+      weekdays,
+      days_restricted: day != "*",
+      weekdays_restricted: weekday != "*",
+    })
+  }
+
+  pub fn matches(&self, t: NaiveDateTime) -> bool {
+    let day = self.days[t.day() as usize];
+    let weekday = self.weekdays[t.weekday().num_days_from_sunday() as usize];
+    // Standard cron: when both day fields are restricted, either may match
+    let day_ok = match (self.days_restricted, self.weekdays_restricted) {
+      (true, true) => day || weekday,
+      _ => day && weekday,
+    };
+    self.minutes[t.minute() as usize]
+      && self.hours[t.hour() as usize]
+      && self.months[t.month() as usize]
+      && day_ok
+  }
+
+  /// The first matching minute strictly after `t`.
+  pub fn next_after(&self, t: NaiveDateTime) -> Option<NaiveDateTime> {
+    let start = t.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+    (0..SEARCH_MINUTES)
+      .map(|m| start + Duration::minutes(m))
+      .find(|candidate| self.matches(*candidate))
+  }
+}
+
+/// Parse one field into a lookup table indexed by value (`0..=max`).
+fn parse_field(field: &str, min: u32, max: u32) -> std::result::Result<Vec<bool>, String> {
+  let mut allowed = vec![false; max as usize + 1];
+  for part in field.split(',') {
+    let (range, step) = match part.split_once('/') {
+      Some((range, step)) => {
+        let step: u32 = step
+          .parse()
+          .map_err(|_| format!("invalid step in \"{part}\""))?;
+        if step == 0 {
+          return Err(format!("step must be positive in \"{part}\""));
+        }
+        (range, step)
+      }
+      None => (part, 1),
+    };
+    let (lo, hi) = if range == "*" {
+      (min, max)
+    } else if let Some((lo, hi)) = range.split_once('-') {
+      (parse_value(lo, part)?, parse_value(hi, part)?)
+    } else {
+      let value = parse_value(range, part)?;
+      // `5/15` means "from 5 to the end, every 15"
+      (value, if step > 1 { max } else { value })
+    };
+    if lo < min || hi > max || lo > hi {
+      return Err(format!("\"{part}\" is outside {min}-{max}"));
+    }
+    for value in (lo..=hi).step_by(step as usize) {
+      allowed[value as usize] = true;
+    }
+  }
+  Ok(allowed)
+}
+
+fn parse_value(value: &str, part: &str) -> std::result::Result<u32, String> {
+  value
+    .parse()
+    .map_err(|_| format!("invalid value in \"{part}\""))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(s: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+  }
+
+  #[test]
+  fn 平日の2時間おきにだけ一致する() {
+    let s = CronSchedule::parse("0 */2 * * 1-5").unwrap();
+    // 2026-10-15 is a Thursday
+    assert!(s.matches(at("2026-10-15 10:00")));
+    assert!(!s.matches(at("2026-10-15 11:00")));
+    assert!(!s.matches(at("2026-10-15 10:30")));
+    // Saturday
+    assert!(!s.matches(at("2026-10-17 10:00")));
+  }
+
+  #[test]
+  fn 次の実行時刻は週末を飛ばす() {
+    let s = CronSchedule::parse("0 9-18 * * 1-5").unwrap();
+    // Friday evening → Monday morning
+    assert_eq!(
+      s.next_after(at("2026-10-16 18:30")),
+      Some(at("2026-10-19 09:00"))
+    );
+    // Strictly after: an exact match moves to the next slot
+    assert_eq!(
+      s.next_after(at("2026-10-15 09:00")),
+      Some(at("2026-10-15 10:00"))
+    );
+  }
+
+  #[test]
+  fn リストと日曜の7を受け付ける() {
+    let s = CronSchedule::parse("15,45 8 * * 7").unwrap();
+    // 2026-10-18 is a Sunday
+    assert!(s.matches(at("2026-10-18 08:45")));
+    assert!(!s.matches(at("2026-10-18 08:30")));
+  }
+
+  #[test]
+  fn 日と曜日の両方を指定するとどちらかで一致する() {
+    let s = CronSchedule::parse("0 0 1 * 1").unwrap();
+    // 1st of the month (Thursday) and any Monday both match
+    assert!(s.matches(at("2026-10-01 00:00")));
+    assert!(s.matches(at("2026-10-05 00:00")));
+    assert!(!s.matches(at("2026-10-06 00:00")));
+  }
+
+  #[test]
+  fn 不正な式はエラーにする() {
+    assert!(CronSchedule::parse("0 */2 * *").is_err());
+    assert!(CronSchedule::parse("60 * * * *").is_err());
+    assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    assert!(CronSchedule::parse("a * * * *").is_err());
+    assert!(CronSchedule::parse("0 18-9 * * *").is_err());
+  }
+
+  #[test]
+  fn 一致しない日付ならnoneを返す() {
+    let s = CronSchedule::parse("0 0 30 2 *").unwrap();
+    assert_eq!(s.next_after(at("2026-01-01 00:00")), None);
+  }
+}
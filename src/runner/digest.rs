@@ -0,0 +1,366 @@
+//! Email digest (`notifications.email`): what finished, what failed and which
+//! intents wait for an answer, mailed after each run or once a day from
+//! `watch`. Mail goes out over SMTP through `curl`, which handles TLS and AUTH.
+//! The last message is kept in `.forge/digest.eml`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, warn};
+
+use super::notify;
+use crate::config::{Config, DigestSchedule, EmailSettings};
+use crate::error::{ForgeError, Result};
+use crate::intent::registry::Intent;
+use crate::knowledge::history::{self, Outcome};
+use crate::knowledge::run_report::IntentReport;
+use crate::process;
+
+fn message_path(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("digest.eml")
+}
+
+/// When the last daily digest went out.
+fn sent_path(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("digest-sent")
+}
+
+/// Mail the digest of a finished run (`digest: run`). Failures are logged only.
+pub fn after_run(config: &Config, repo_path: &Path, entries: &[IntentReport]) {
+  let Some(email) = &config.notifications.email else {
+    return;
+  };
+  if email.digest != DigestSchedule::Run || entries.is_empty() {
+    return;
+  }
+  if let Err(e) = send(repo_path, email, "this run", entries) {
+    warn!("failed to send digest email: {e}");
+  }
+}
+
+/// Mail the daily digest (`digest: daily`) once 24 hours have passed since the
+/// previous one. The first call only starts the window.
+pub fn send_daily_if_due(config: &Config, repo_path: &Path) {
+  let Some(email) = &config.notifications.email else {
+    return;
+  };
+  if email.digest != DigestSchedule::Daily {
+    return;
+  }
+  let now = Utc::now();
+  let last = std::fs::read_to_string(sent_path(repo_path))
+    .ok()
+    .and_then(|s| DateTime::parse_from_rfc3339(s.trim()).ok())
+    .map(|t| t.with_timezone(&Utc));
+  let Some(last) = last else {
+    mark_sent(repo_path, now);
+    return;
+  };
+  if now - last < Duration::hours(24) {
+    return;
+  }
+  let entries = match finished_since(repo_path, last) {
+    Ok(entries) => entries,
+    Err(e) => {
+      warn!("failed to read history for digest: {e}");
+      return;
+    }
+  };
+  let period = format!("since {}", last.format("%Y-%m-%d %H:%M UTC"));
+  match send(repo_path, email, &period, &entries) {
+    // Retried on the next watch cycle when sending fails
+    Ok(()) => mark_sent(repo_path, now),
+    Err(e) => warn!("failed to send digest email: {e}"),
+  }
+}
+
+fn mark_sent(repo_path: &Path, at: DateTime<Utc>) {
+  if let Err(e) = std::fs::write(sent_path(repo_path), at.to_rfc3339()) {
+    warn!("failed to record digest time: {e}");
+  }
+}
+
+/// History entries recorded after `since`, as report rows.
+fn finished_since(repo_path: &Path, since: DateTime<Utc>) -> Result<Vec<IntentReport>> {
+  let mut entries: Vec<_> = history::load_all(repo_path)?
+    .into_iter()
+    .filter_map(|e| {
+      let at = DateTime::parse_from_rfc3339(e.created_at.as_deref()?).ok()?;
+      (at > since).then_some((at, e))
+    })
+    .collect();
+  entries.sort_by_key(|(at, _)| *at);
+  Ok(
+    entries
+      .into_iter()
+      .map(|(_, e)| IntentReport {
+        cost_usd: e.cost_usd(),
+        intent_id: e.intent_id,
+        title: e.title,
+        outcome: e.outcome,
+        failure_reason: e.failure_reason,
+        next_action: None,
+      })
+      .collect(),
+  )
+}
+
+fn send(
+  repo_path: &Path,
+  email: &EmailSettings,
+  period: &str,
+  entries: &[IntentReport],
+) -> Result<()> {
+  // Credentials go through stdin (`-K -`) so they stay out of the process list
+  let credentials = match &email.username {
+    Some(username) => {
+      let password = std::env::var(&email.password_env).map_err(|_| {
+        ForgeError::Config(format!(
+          "{} is not set (SMTP password for notifications.email.username)",
+          email.password_env
+        ))
+      })?;
+      notify::curl_config_line("user", &format!("{username}:{password}"))
+    }
+    None => String::new(),
+  };
+  let intents = Intent::fetch_all(&repo_path.join(".forge").join("intents")).unwrap_or_default();
+  let (subject, body) = render(&super::repo_name(repo_path), period, entries, &intents);
+  let message = format!(
+    "From: {from}\nTo: {to}\nSubject: {subject}\nDate: {date}\n\
+     MIME-Version: 1.0\nContent-Type: text/plain; charset=utf-8\n\n{body}",
+    from = email.from,
+    to = email.to.join(", "),
+    subject = encode_header(&subject),
+    date = Utc::now().to_rfc2822(),
+  );
+  let path = message_path(repo_path);
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+  std::fs::write(&path, message)?;
+
+  let mut cmd = process::command("curl");
+  cmd.args([
+    "--silent",
+    "--show-error",
+    "--crlf",
+    "--url",
+    &email.smtp_url,
+  ]);
+  cmd.args(["--mail-from", &email.from]);
+  for to in &email.to {
+    cmd.args(["--mail-rcpt", to]);
+  }
+  cmd.arg("--upload-file").arg(&path);
+  if email.username.is_some() && email.smtp_url.starts_with("smtp://") {
+    cmd.arg("--ssl-reqd");
+  }
+  cmd.args(["-K", "-"]);
+  let mut child = cmd
+    .stdin(Stdio::piped())
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(credentials.as_bytes())?;
+  }
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(
+      std::io::Error::other(format!(
+        "curl failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+      ))
+      .into(),
+    );
+  }
+  info!("digest email sent to {}", email.to.join(", "));
+  Ok(())
+}
+
+/// A header value as RFC 2047 `Q` encoded-words when it is not plain ASCII.
+/// Words stay within 75 characters, are never split inside a character and
+/// are folded onto continuation lines.
+fn encode_header(text: &str) -> String {
+  const PREFIX: &str = "=?UTF-8?Q?";
+  const MAX_WORD: usize = 75 - PREFIX.len() - "?=".len();
+  if text.is_ascii() {
+    return text.to_string();
+  }
+  let mut words = vec![String::new()];
+  for ch in text.chars() {
+    let mut encoded = String::new();
+    for byte in ch.to_string().bytes() {
+      match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'!' | b'*' | b'+' | b'-' | b'/' => {
+          encoded.push(byte as char)
+        }
+        b' ' => encoded.push('_'),
+        _ => encoded.push_str(&format!("={byte:02X}")),
+      }
+    }
+    if words
+      .last()
+      .is_some_and(|w| w.len() + encoded.len() > MAX_WORD)
+    {
+      words.push(String::new());
+    }
+    words.last_mut().unwrap().push_str(&encoded);
+  }
+  words
+    .iter()
+    .map(|w| format!("{PREFIX}{w}?="))
+    .collect::<Vec<_>>()
+    .join("\n ")
+}
+
+/// Subject and plain-text body of the digest.
+fn render(
+  repo: &str,
+  period: &str,
+  entries: &[IntentReport],
+  intents: &[Intent],
+) -> (String, String) {
+  let pending: Vec<&Intent> = intents.iter().filter(|i| i.needs_clarification()).collect();
+  let mut summary = if entries.is_empty() {
+    "nothing finished".to_string()
+  } else {
+    notify::run_summary(entries.iter().map(|e| &e.outcome))
+  };
+  if !pending.is_empty() {
+    summary.push_str(&format!(", {} waiting for clarification", pending.len()));
+  }
+  let subject = format!("[pfl-forge] {repo}: {summary}");
+
+  let mut body = format!("pfl-forge digest for {repo} ({period})\n");
+  let section = |body: &mut String, heading: &str, outcome: Outcome| {
+    let rows: Vec<&IntentReport> = entries.iter().filter(|e| e.outcome == outcome).collect();
+    if rows.is_empty() {
+      return;
+    }
+    body.push_str(&format!("\n{heading}\n"));
+    for e in rows {
+      body.push_str(&format!("- {}: {}\n", e.intent_id, e.title));
+      match outcome {
+        Outcome::Success => {
+          if let Some(intent) = intents.iter().find(|i| i.id() == e.intent_id) {
+            body.push_str(&format!("  branch: {}\n", intent.branch_name()));
+          }
+        }
+        _ => {
+          if let Some(reason) = &e.failure_reason {
+            body.push_str(&format!("  reason: {reason}\n"));
+          }
+        }
+      }
+    }
+  };
+  section(&mut body, "Succeeded", Outcome::Success);
+  section(&mut body, "Failed", Outcome::Failed);
+  section(&mut body, "Escalated", Outcome::Escalated);
+  if !pending.is_empty() {
+    body.push_str("\nWaiting for clarification\n");
+    for intent in pending {
+      body.push_str(&format!("- {}: {}\n", intent.id(), intent.title));
+      for c in intent.clarifications.iter().filter(|c| c.answer.is_none()) {
+        body.push_str(&format!("  ? {}\n", c.question));
+      }
+      body.push_str(&format!(
+        "  answer with: pfl-forge answer {}\n",
+        intent.id()
+      ));
+    }
+  }
+  (subject, body)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn report(id: &str, outcome: Outcome, reason: Option<&str>) -> IntentReport {
+    IntentReport {
+      intent_id: id.into(),
+      title: format!("{id} title"),
+      outcome,
+      failure_reason: reason.map(Into::into),
+      cost_usd: 0.0,
+      next_action: None,
+    }
+  }
+
+  #[test]
+  fn 非asciiの件名はrfc2047でエンコードする() {
+    assert_eq!(
+      encode_header("forge digest: 3 done"),
+      "forge digest: 3 done"
+    );
+    assert_eq!(
+      encode_header("ログイン修正"),
+      "=?UTF-8?Q?=E3=83=AD=E3=82=B0=E3=82=A4=E3=83=B3=E4=BF=AE=E6=AD=A3?="
+    );
+
+    let long = encode_header(&"あ".repeat(30));
+    let words: Vec<_> = long.split("\n ").collect();
+    assert!(words.len() > 1);
+    for word in &words {
+      assert!(word.len() <= 75);
+      assert!(word.starts_with("=?UTF-8?Q?") && word.ends_with("?="));
+      // "あ" is =E3=81=82; a word never ends mid-character
+      assert_eq!((word.len() - "=?UTF-8?Q??=".len()) % 9, 0);
+    }
+  }
+
+  #[test]
+  fn usernameがあってパスワードの環境変数が未設定ならconfigエラーにする() {
+    let dir = tempfile::tempdir().unwrap();
+    let email = EmailSettings {
+      smtp_url: "smtps://127.0.0.1:1".into(),
+      from: "forge@example.com".into(),
+      to: vec!["team@example.com".into()],
+      username: Some("forge".into()),
+      password_env: "FORGE_TEST_DIGEST_PASSWORD_UNSET".into(),
+      digest: DigestSchedule::Run,
+    };
+    let err = send(dir.path(), &email, "run", &[]).unwrap_err();
+    assert!(matches!(err, ForgeError::Config(_)));
+    assert!(err.to_string().contains("FORGE_TEST_DIGEST_PASSWORD_UNSET"));
+    assert!(!message_path(dir.path()).exists());
+  }
+
+  #[test]
+  fn ダイジェストに結果ごとの節と質問待ちを並べる() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+      dir.path().join("done.yaml"),
+      "title: done title\nbody: b\nsource: human\nstatus: done\n",
+    )
+    .unwrap();
+    std::fs::write(
+      dir.path().join("ask.yaml"),
+      "title: ask title\nbody: b\nsource: human\nstatus: approved\nclarifications:\n  - question: Which API?\n    answer: null\n",
+    )
+    .unwrap();
+    let intents = Intent::fetch_all(dir.path()).unwrap();
+    let entries = vec![
+      report("done", Outcome::Success, None),
+      report("broken", Outcome::Failed, Some("tests failed")),
+    ];
+
+    let (subject, body) = render("repo", "this run", &entries, &intents);
+
+    assert_eq!(
+      subject,
+      "[pfl-forge] repo: 1 succeeded, 1 failed, 1 waiting for clarification"
+    );
+    assert!(body.contains("Succeeded\n- done: done title\n  branch: forge/done\n"));
+    assert!(body.contains("Failed\n- broken: broken title\n  reason: tests failed\n"));
+    assert!(!body.contains("Escalated"));
+    assert!(
+      body.contains("- ask: ask title\n  ? Which API?\n  answer with: pfl-forge answer ask\n")
+    );
+  }
+}
@@ -0,0 +1,184 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::agent::analyze::{self, AnalysisOutcome};
+use crate::claude::runner::{Claude, SessionMode};
+use crate::config::Config;
+use crate::error::Result;
+use crate::i18n::{Locale, Msg};
+
+/// Analyze-only preview of what `run` would do, for `run --dry-run --report`.
+/// Nothing is written to intents or tasks.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DryRunReport {
+  pub generated_at: String,
+  pub intents: Vec<IntentPlan>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IntentPlan {
+  pub intent_id: String,
+  pub title: String,
+  /// `tasks`, `child_intents`, `needs_clarification` or `error`.
+  pub outcome: String,
+  /// Highest task complexity; a rough effort estimate for planning.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub effort: Option<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tasks: Vec<TaskPlan>,
+  /// Child intent titles or clarification questions, depending on `outcome`.
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub notes: Vec<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TaskPlan {
+  pub title: String,
+  pub complexity: String,
+  pub plan: String,
+  pub relevant_files: Vec<String>,
+  pub steps: usize,
+}
+
+/// Run Analyze on every intent `run` would pick up and collect the results.
+pub fn plan(config: &Config, claude: &impl Claude, repo_path: &Path) -> Result<DryRunReport> {
+  let targets = super::approved_targets(config, repo_path)?;
+  let mut intents = Vec::new();
+  for intent in &targets {
+    info!("[dry-run] analyzing: {intent}");
+    let active = super::gather_active_intents(repo_path, intent.id());
+    let mut entry = IntentPlan {
+      intent_id: intent.id().to_string(),
+      title: intent.title.clone(),
+      outcome: String::new(),
+      effort: None,
+      tasks: Vec::new(),
+      notes: Vec::new(),
+      error: None,
+      cost_usd: 0.0,
+    };
+    match analyze::analyze(
+      intent,
+      config,
+      claude,
+      repo_path,
+      &active,
+      &SessionMode::None,
+    ) {
+      Ok((outcome, metadata, _, _)) => {
+        entry.cost_usd = metadata.cost_usd.unwrap_or(0.0);
+        match outcome {
+          AnalysisOutcome::Tasks(specs) => {
+            entry.outcome = "tasks".into();
+            entry.effort = specs
+              .iter()
+              .max_by_key(|s| complexity_rank(&s.complexity))
+              .map(|s| s.complexity.clone());
+            entry.tasks = specs
+              .into_iter()
+              .map(|s| TaskPlan {
+                title: if s.title.is_empty() {
+                  intent.title.clone()
+                } else {
+                  s.title
+                },
+                complexity: s.complexity,
+                plan: s.plan,
+                relevant_files: s.relevant_files,
+                steps: s.implementation_steps.len(),
+              })
+              .collect();
+          }
+          AnalysisOutcome::ChildIntents(children) => {
+            entry.outcome = "child_intents".into();
+            entry.notes = children.into_iter().map(|c| c.title).collect();
+          }
+          AnalysisOutcome::NeedsClarification { clarifications } => {
+            entry.outcome = "needs_clarification".into();
+            entry.notes = clarifications;
+          }
+        }
+      }
+      Err(e) => {
+        warn!("[dry-run] {}: analyze failed: {e}", intent.id());
+        entry.outcome = "error".into();
+        entry.error = Some(e.to_string());
+      }
+    }
+    intents.push(entry);
+  }
+  Ok(DryRunReport {
+    generated_at: chrono::Utc::now().to_rfc3339(),
+    intents,
+  })
+}
+
+fn complexity_rank(complexity: &str) -> u8 {
+  match complexity {
+    "high" => 2,
+    "medium" => 1,
+    _ => 0,
+  }
+}
+
+/// Write the report as JSON when `path` ends in `.json`, Markdown otherwise.
+pub fn write(path: &Path, report: &DryRunReport, locale: Locale) -> Result<()> {
+  let content = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+    format!("{}\n", serde_json::to_string_pretty(report)?)
+  } else {
+    to_markdown(report, locale)
+  };
+  if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, content)?;
+  Ok(())
+}
+
+/// Markdown with headings and labels in `locale`; the JSON form stays in English.
+pub fn to_markdown(report: &DryRunReport, locale: Locale) -> String {
+  let t = |msg| locale.text(msg);
+  let total_cost = report
+    .intents
+    .iter()
+    .fold(0.0, |total, i| total + i.cost_usd);
+  let mut out = format!(
+    "# {}\n\n{}\n",
+    t(Msg::DryRunReport),
+    locale.dry_run_summary(&report.generated_at, report.intents.len(), total_cost),
+  );
+  for intent in &report.intents {
+    out.push_str(&format!("\n## {} — {}\n\n", intent.intent_id, intent.title));
+    out.push_str(&format!("- {}: {}\n", t(Msg::Outcome), intent.outcome));
+    if let Some(effort) = &intent.effort {
+      out.push_str(&format!("- {}: {effort}\n", t(Msg::Effort)));
+    }
+    if let Some(error) = &intent.error {
+      out.push_str(&format!("- {}: {error}\n", t(Msg::Error)));
+    }
+    for note in &intent.notes {
+      out.push_str(&format!("- {note}\n"));
+    }
+    for task in &intent.tasks {
+      out.push_str(&format!(
+        "\n### {} ({}, {})\n\n{}\n",
+        task.title,
+        task.complexity,
+        locale.step_count(task.steps),
+        task.plan
+      ));
+      if !task.relevant_files.is_empty() {
+        out.push_str(&format!("\n{}:\n\n", t(Msg::Files)));
+        for file in &task.relevant_files {
+          out.push_str(&format!("- `{file}`\n"));
+        }
+      }
+    }
+  }
+  out
+}
@@ -0,0 +1,175 @@
+//! Handoff bundle for tasks escalated after a failed conflict reimplementation.
+//!
+//! The bundle collects what a human needs to pick the task up — branch, plan,
+//! conflicting files, the conflict agent's attempts, errors and check output —
+//! in `.forge/escalations/<id>.md`. With `escalation_command`, the command is
+//! run with the bundle path in `FORGE_ESCALATION_FILE` to post it elsewhere.
+
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use super::check;
+use crate::claude::runner::ClaudeResponse;
+use crate::config::Config;
+use crate::error::{ForgeError, Result};
+use crate::intent::registry::Intent;
+use crate::process;
+use crate::task::Task;
+
+/// Bytes of each check log included in the bundle.
+const MAX_LOG_LEN: usize = 4000;
+
+/// One conflict agent run during a rebase.
+#[derive(Debug, Clone)]
+pub struct ConflictAttempt {
+  pub files: Vec<String>,
+  /// The agent's final response, or the error it failed with.
+  pub response: String,
+}
+
+impl ConflictAttempt {
+  pub fn new(files: Vec<String>, result: &Result<String>) -> Self {
+    let response = match result {
+      Ok(raw) => ClaudeResponse::parse(raw)
+        .map(|r| r.result)
+        .unwrap_or_else(|_| raw.clone()),
+      Err(e) => format!("error: {e}"),
+    };
+    Self { files, response }
+  }
+}
+
+pub fn dir(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("escalations")
+}
+
+/// Write the bundle and post it with `escalation_command`. Failures are logged only.
+pub fn escalate(
+  repo_path: &Path,
+  config: &Config,
+  intent: &Intent,
+  task: &Task,
+  reason: &str,
+  attempts: &[ConflictAttempt],
+) {
+  let name = if task.id.is_empty() || task.id == intent.id() {
+    intent.id().to_string()
+  } else {
+    format!("{}-{}", intent.id(), task.id)
+  };
+  let path = dir(repo_path).join(format!("{name}.md"));
+  let bundle = render(repo_path, config, intent, task, reason, attempts);
+  let written =
+    std::fs::create_dir_all(dir(repo_path)).and_then(|()| std::fs::write(&path, bundle));
+  if let Err(e) = written {
+    warn!("failed to write escalation {}: {e}", path.display());
+    return;
+  }
+  info!("escalation written: {}", path.display());
+
+  if let Some(command) = &config.escalation_command {
+    if let Err(e) = notify(repo_path, command, intent, &path) {
+      warn!("{e}");
+    }
+  }
+}
+
+pub fn render(
+  repo_path: &Path,
+  config: &Config,
+  intent: &Intent,
+  task: &Task,
+  reason: &str,
+  attempts: &[ConflictAttempt],
+) -> String {
+  let mut out = format!(
+    "# Escalation: {title}\n\n\
+     - Intent: {id}\n\
+     - Task: {task_title}\n\
+     - Branch: {branch}\n\
+     - Base branch: {base}\n\
+     - Reason: {reason}\n\n\
+     ## Plan\n\n{plan}\n",
+    title = intent.title,
+    id = intent.id(),
+    task_title = task.title,
+    branch = intent.branch_name(),
+    base = config.base_branch,
+    plan = task.plan,
+  );
+
+  let mut files: Vec<&str> = attempts
+    .iter()
+    .flat_map(|a| a.files.iter().map(String::as_str))
+    .collect();
+  files.sort_unstable();
+  files.dedup();
+  if !files.is_empty() {
+    out.push_str("\n## Conflicting Files\n\n");
+    for file in files {
+      out.push_str(&format!("- {file}\n"));
+    }
+  }
+
+  if !attempts.is_empty() {
+    out.push_str("\n## Resolution Attempts\n");
+    for (i, attempt) in attempts.iter().enumerate() {
+      out.push_str(&format!(
+        "\n### Attempt {} ({})\n\n{}\n",
+        i + 1,
+        attempt.files.join(", "),
+        attempt.response.trim()
+      ));
+    }
+  }
+
+  if !task.errors.is_empty() {
+    out.push_str("\n## Errors\n\n");
+    for error in &task.errors {
+      out.push_str(&format!("- {error}\n"));
+    }
+  }
+
+  let logs = check_logs(&check::log_dir(repo_path, intent.id(), &task.id));
+  if !logs.is_empty() {
+    out.push_str("\n## Test Output\n");
+    for (name, content) in logs {
+      out.push_str(&format!(
+        "\n### {name}\n\n```\n{}\n```\n",
+        check::excerpt(content.trim_end(), MAX_LOG_LEN)
+      ));
+    }
+  }
+  out
+}
+
+/// The latest attempt of each check for the task.
+fn check_logs(log_dir: &Path) -> Vec<(String, String)> {
+  check::latest_logs(log_dir)
+    .into_iter()
+    .filter_map(|path| {
+      let content = std::fs::read_to_string(&path).ok()?;
+      let name = path.file_name()?.to_string_lossy().into_owned();
+      Some((name, content))
+    })
+    .collect()
+}
+
+fn notify(repo_path: &Path, command: &str, intent: &Intent, path: &Path) -> Result<()> {
+  info!("escalation command: {command}");
+  let output = process::command("sh")
+    .args(["-c", command])
+    .env("FORGE_ESCALATION_FILE", path)
+    .env("FORGE_INTENT_ID", intent.id())
+    .current_dir(repo_path)
+    .output()?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(ForgeError::Check(format!(
+      "escalation command failed: {command}: {}",
+      stderr.trim_end()
+    )));
+  }
+  Ok(())
+}
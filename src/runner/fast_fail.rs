@@ -0,0 +1,119 @@
+//! Detect runs where every intent fails the same way.
+//!
+//! A missing `claude` binary or an expired login fails each intent at the same
+//! step with the same error. Once `fast_fail_after` intents share one failure
+//! signature and none succeeded, the rest of the queue is left approved and the
+//! run ends with [`ForgeError::RunAborted`](crate::error::ForgeError::RunAborted).
+
+use crate::error::ForgeError;
+use crate::knowledge::history::{Outcome, StepStatus};
+
+use super::IntentResult;
+
+/// Where and how an intent failed, with its ID masked so the same error on
+/// different intents compares equal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+  /// The first failed step; `None` when the intent errored out before recording one.
+  pub step: Option<String>,
+  pub reason: String,
+}
+
+/// Signature of a processed intent. `None` for successes and for failures that
+/// are not errors of a step (e.g. blocked on a dependency), which never fast-fail.
+pub fn signature(id: &str, result: &Result<IntentResult, ForgeError>) -> Option<Signature> {
+  match result {
+    Ok(r) if r.outcome == Outcome::Success => None,
+    Ok(r) => {
+      let step = r
+        .step_results
+        .iter()
+        .find(|s| s.status == StepStatus::Failed)?;
+      Some(Signature {
+        step: Some(step.step.clone()),
+        reason: r
+          .failure_reason
+          .as_deref()
+          .unwrap_or_default()
+          .replace(id, "<intent>"),
+      })
+    }
+    Err(e) => Some(Signature {
+      step: None,
+      reason: e.to_string().replace(id, "<intent>"),
+    }),
+  }
+}
+
+/// Diagnosis when at least `threshold` intents were processed and all share one
+/// failure signature. A threshold of 0 disables the check.
+pub fn common_failure(signatures: &[Option<Signature>], threshold: u32) -> Option<String> {
+  if threshold == 0 || signatures.len() < threshold as usize {
+    return None;
+  }
+  let first = signatures.first()?.as_ref()?;
+  if signatures.iter().any(|s| s.as_ref() != Some(first)) {
+    return None;
+  }
+  let at = match &first.step {
+    Some(step) => format!(" at {step}"),
+    None => String::new(),
+  };
+  let mut diagnosis = format!(
+    "all {} intent(s) failed{at} with the same error: {}",
+    signatures.len(),
+    first.reason
+  );
+  if let Some(hint) = hint(&first.reason) {
+    diagnosis.push_str(&format!(" ({hint})"));
+  }
+  Some(diagnosis)
+}
+
+fn hint(reason: &str) -> Option<&'static str> {
+  let lower = reason.to_lowercase();
+  if lower.contains("no such file or directory") || lower.contains("not found") {
+    Some("check that the claude CLI is installed, or set claude.bin")
+  } else if ["auth", "login", "401", "api key"]
+    .iter()
+    .any(|k| lower.contains(k))
+  {
+    Some("the claude CLI may be logged out; run `claude login`")
+  } else if lower.contains("rate limit") || lower.contains("429") {
+    Some("the API is rate limiting; retry later")
+  } else {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn failed(step: &str, reason: &str) -> Option<Signature> {
+    Some(Signature {
+      step: Some(step.into()),
+      reason: reason.into(),
+    })
+  }
+
+  #[test]
+  fn 同じシグネチャが閾値以上なら診断を返す() {
+    let sigs = vec![
+      failed("analyze", "auth expired"),
+      failed("analyze", "auth expired"),
+    ];
+    let diagnosis = common_failure(&sigs, 2).unwrap();
+    assert!(diagnosis.starts_with("all 2 intent(s) failed at analyze"));
+    assert!(diagnosis.contains("claude login"));
+  }
+
+  #[test]
+  fn 成功や異なる失敗が混ざれば診断しない() {
+    let same = failed("analyze", "auth expired");
+    assert!(common_failure(&[same.clone(), None], 2).is_none());
+    assert!(common_failure(&[same.clone(), failed("review", "x")], 2).is_none());
+    assert!(common_failure(std::slice::from_ref(&same), 2).is_none());
+    assert!(common_failure(&[same.clone(), same], 0).is_none());
+  }
+}
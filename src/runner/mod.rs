@@ -1,21 +1,42 @@
+mod check;
+pub mod checkpoint;
+mod commit_template;
+pub mod cron;
+pub mod digest;
+pub mod dry_run;
+pub mod escalation;
+pub mod fast_fail;
+mod notify;
+pub mod progress;
+pub mod setup;
+pub mod watch;
+pub mod webhook;
+
+use std::collections::BTreeMap;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use tracing::{info, warn};
+use tracing::{info, info_span, warn};
 
 use crate::agent::analyze::{ActiveIntentContext, AnalysisOutcome};
 use crate::agent::review::ReviewResult;
-use crate::agent::{analyze, audit, implement, reflect, review, skill};
+use crate::agent::{
+  analyze, audit, commit_message, conflict, custom_step, implement, reflect, review, skill,
+};
+use crate::claude::model;
 use crate::claude::runner::{parse_metadata, Claude, SessionMode};
-use crate::config::Config;
-use crate::error::Result;
+use crate::config::{Config, CustomStep};
+use crate::error::{ForgeError, Result};
 use crate::git;
-use crate::intent::registry::{Intent, IntentStatus};
-use crate::knowledge::history::{self, HistoryEntry, Outcome, StepResult};
+use crate::intent::registry::{Intent, IntentStatus, PlanApproval};
+use crate::knowledge::history::{self, HistoryEntry, Outcome, StepResult, StepStatus};
+use crate::knowledge::run_report::{self, IntentReport, RunReport};
 use crate::knowledge::summary::{
   self, AnalyzeSummary, ExecutionSummary, ReviewSummary, TaskSummary,
 };
+use crate::process;
 use crate::task::{self, Task, WorkStatus};
+use check::CheckOutcome;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Step {
@@ -28,10 +49,12 @@ pub enum Step {
   Observe,
   Abstract,
   Record,
+  /// A `steps.<name>` entry from config.
+  Custom(String),
 }
 
 impl Step {
-  fn name(&self) -> &'static str {
+  fn name(&self) -> &str {
     match self {
       Step::Analyze => "analyze",
       Step::Implement => "implement",
@@ -42,6 +65,7 @@ impl Step {
       Step::Observe => "observe",
       Step::Abstract => "abstract",
       Step::Record => "record",
+      Step::Custom(name) => name,
     }
   }
 }
@@ -54,6 +78,56 @@ pub fn default_flow(intent_type: Option<&str>) -> Vec<Step> {
   }
 }
 
+/// The flow for an intent type: `flows.<type>`, else `flows.default`, else
+/// `default_flow`. Audit and skill extraction keep their dedicated flows.
+pub fn flow_for(config: &Config, intent_type: Option<&str>) -> Result<Vec<Step>> {
+  let base = default_flow(intent_type);
+  if !base.contains(&Step::Analyze) {
+    return Ok(base);
+  }
+  let names = intent_type
+    .and_then(|t| config.flows.get(t))
+    .or_else(|| config.flows.get("default"));
+  match names {
+    Some(names) => parse_flow(names, &config.steps).map_err(ForgeError::Config),
+    None => Ok(base),
+  }
+}
+
+/// Parse a configured step list. It must be `analyze, implement`, then any
+/// custom steps, optionally ending with `review`.
+pub fn parse_flow(
+  names: &[String],
+  steps: &BTreeMap<String, CustomStep>,
+) -> std::result::Result<Vec<Step>, String> {
+  let flow: Vec<Step> = names
+    .iter()
+    .map(|name| match name.as_str() {
+      "analyze" => Ok(Step::Analyze),
+      "implement" => Ok(Step::Implement),
+      "review" => Ok(Step::Review),
+      _ if steps.contains_key(name) => Ok(Step::Custom(name.clone())),
+      _ => Err(format!("unknown step: {name}")),
+    })
+    .collect::<std::result::Result<_, _>>()?;
+
+  if flow.get(..2) != Some(&[Step::Analyze, Step::Implement][..]) {
+    return Err("flow must start with analyze, implement".into());
+  }
+  let rest = &flow[2..];
+  let review_at = rest.iter().position(|s| *s == Step::Review);
+  if review_at.is_some_and(|i| i != rest.len() - 1) {
+    return Err("review must be the last step".into());
+  }
+  if rest[..review_at.unwrap_or(rest.len())]
+    .iter()
+    .any(|s| !matches!(s, Step::Custom(_)))
+  {
+    return Err("only custom steps may follow implement".into());
+  }
+  Ok(flow)
+}
+
 #[derive(Debug)]
 pub struct IntentResult {
   pub flow: Vec<String>,
@@ -68,31 +142,24 @@ pub fn run_intents(
   repo_path: &Path,
   dry_run: bool,
 ) -> Result<Vec<(String, IntentResult)>> {
-  // Convert any pending drafts before loading intents
-  let converted = crate::intent::draft::convert_drafts(repo_path)?;
-  if !converted.is_empty() {
-    info!("converted {} draft(s): {:?}", converted.len(), converted);
+  if watch::is_disabled(config, repo_path) {
+    info!("processing is disabled; run `pfl-forge enable` to resume");
+    return Ok(Vec::new());
+  }
+  let run_id = new_run_id();
+  let blocked = if dry_run {
+    Vec::new()
+  } else {
+    block_on_failed_dependencies(repo_path, &run_id)?
+  };
+  let targets = approved_targets(config, repo_path)?;
+  if !dry_run {
+    settle_parents(repo_path)?;
   }
-
-  let intents_dir = repo_path.join(".forge").join("intents");
-  let all_intents = Intent::fetch_all(&intents_dir)?;
-  let mut targets: Vec<Intent> = all_intents
-    .iter()
-    .filter(|i| i.status == IntentStatus::Approved)
-    .filter(|i| {
-      i.depends_on.is_empty()
-        || i.depends_on.iter().all(|dep| {
-          all_intents
-            .iter()
-            .any(|other| other.id() == dep && other.status == IntentStatus::Done)
-        })
-    })
-    .cloned()
-    .collect();
 
   if targets.is_empty() {
     info!("no approved intents found");
-    return Ok(Vec::new());
+    return Ok(blocked);
   }
 
   if dry_run {
@@ -102,16 +169,124 @@ pub fn run_intents(
     return Ok(Vec::new());
   }
 
+  process_targets(config, claude, repo_path, &run_id, targets, blocked)
+}
+
+/// Run one intent now (`run --intent`), whatever its status, tags or
+/// dependencies. It is approved first, so it is recorded as a normal run.
+pub fn run_intent(
+  config: &Config,
+  claude: &(impl Claude + Sync),
+  repo_path: &Path,
+  id: &str,
+) -> Result<Vec<(String, IntentResult)>> {
+  run_listed_intents(config, claude, repo_path, &[id.to_string()])
+}
+
+/// [`run_intent`] for a curated batch (`run --from-file`), in the given order.
+/// Every ID is checked before anything runs.
+pub fn run_listed_intents(
+  config: &Config,
+  claude: &(impl Claude + Sync),
+  repo_path: &Path,
+  ids: &[String],
+) -> Result<Vec<(String, IntentResult)>> {
+  if watch::is_disabled(config, repo_path) {
+    info!("processing is disabled; run `pfl-forge enable` to resume");
+    return Ok(Vec::new());
+  }
+  if ids.is_empty() {
+    info!("no intents listed");
+    return Ok(Vec::new());
+  }
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let all = Intent::fetch_all(&intents_dir)?;
+  let mut targets = Vec::new();
+  let mut missing = Vec::new();
+  for id in ids {
+    match all.iter().find(|i| i.id() == id) {
+      Some(intent) => targets.push(intent.clone()),
+      None => missing.push(id.as_str()),
+    }
+  }
+  if !missing.is_empty() {
+    return Err(ForgeError::Config(format!(
+      "intent not found: {}",
+      missing.join(", ")
+    )));
+  }
+  for intent in &mut targets {
+    if !matches!(
+      intent.status,
+      IntentStatus::Approved | IntentStatus::SetupFailure
+    ) {
+      info!(
+        "{}: {:?} → approved for this run",
+        intent.id(),
+        intent.status
+      );
+      intent.approve();
+      task::reset_retries(repo_path, intent.id())?;
+    }
+  }
+  process_targets(
+    config,
+    claude,
+    repo_path,
+    &new_run_id(),
+    targets,
+    Vec::new(),
+  )
+}
+
+/// Intent IDs from a `run --from-file` list: one per line, blank lines and
+/// `#` comments skipped, duplicates dropped.
+pub fn parse_intent_list(text: &str) -> Vec<String> {
+  let mut ids: Vec<String> = Vec::new();
+  for line in text.lines() {
+    let id = line.split('#').next().unwrap_or_default().trim();
+    if !id.is_empty() && !ids.iter().any(|i| i == id) {
+      ids.push(id.to_string());
+    }
+  }
+  ids
+}
+
+/// Process `targets` in batches of `parallel_workers` under `run_id`, then
+/// settle parents and send the run notifications.
+fn process_targets(
+  config: &Config,
+  claude: &(impl Claude + Sync),
+  repo_path: &Path,
+  run_id: &str,
+  mut targets: Vec<Intent>,
+  blocked: Vec<(String, IntentResult)>,
+) -> Result<Vec<(String, IntentResult)>> {
+  info!("run {run_id}: {} intent(s)", targets.len());
+  for intent in &mut targets {
+    intent.run_id = Some(run_id.to_string());
+    update_intent_file(repo_path, intent)?;
+  }
+
   let batch_size = config.parallel_workers.max(1);
-  let mut results = Vec::new();
+  let total = targets.len();
+  let mut results = blocked;
+  let mut signatures = Vec::new();
 
-  for batch in targets.chunks_mut(batch_size) {
+  for (i, batch) in targets.chunks_mut(batch_size).enumerate() {
     let batch_results: Vec<_> = std::thread::scope(|s| {
       let handles: Vec<_> = batch
         .iter_mut()
         .map(|intent| {
           s.spawn(|| {
             let id = intent.id().to_string();
+            let span = info_span!(
+              "intent",
+              repo = %repo_name(repo_path),
+              intent = %id,
+              run_id = %run_id
+            );
+            let _span = span.enter();
             let result = process_intent(intent, config, claude, repo_path);
             (id, result)
           })
@@ -122,6 +297,7 @@ pub fn run_intents(
     });
 
     for (id, result) in batch_results {
+      signatures.push(fast_fail::signature(&id, &result));
       match result {
         Ok(r) => {
           info!("{}: {:?}", id, r.outcome);
@@ -129,24 +305,360 @@ pub fn run_intents(
         }
         Err(e) => {
           warn!("{}: error: {e}", id);
+          // Still reported, so the run summary counts it as a failure
+          results.push((
+            id,
+            IntentResult {
+              flow: vec![],
+              step_results: vec![],
+              outcome: Outcome::Failed,
+              failure_reason: Some(e.to_string()),
+            },
+          ));
         }
       }
     }
+
+    let remaining = total.saturating_sub((i + 1) * batch_size);
+    if remaining == 0 {
+      continue;
+    }
+    if let Some(diagnosis) = fast_fail::common_failure(&signatures, config.fast_fail_after) {
+      settle_parents(repo_path)?;
+      let report = build_run_report(repo_path, &results);
+      if let Err(e) = run_report::write(repo_path, &report) {
+        warn!("failed to write run report: {e}");
+      }
+      return Err(ForgeError::RunAborted(format!(
+        "{diagnosis}; {remaining} intent(s) left approved"
+      )));
+    }
+  }
+  settle_parents(repo_path)?;
+  if !results.is_empty() {
+    let report = build_run_report(repo_path, &results);
+    notify_run_finished(config, repo_path, run_id, &report.intents);
+    digest::after_run(config, repo_path, &report.intents);
   }
   Ok(results)
 }
 
+fn notify_run_finished(config: &Config, repo_path: &Path, run_id: &str, intents: &[IntentReport]) {
+  let all = Intent::fetch_all(&repo_path.join(".forge").join("intents")).unwrap_or_default();
+  for report in intents {
+    let intent = all.iter().find(|i| i.id() == report.intent_id);
+    // Already notified as a clarification event
+    if intent.is_some_and(Intent::needs_clarification) {
+      continue;
+    }
+    let branch = intent.map(Intent::branch_name).unwrap_or_default();
+    notify::dispatch(config, &notify::Event::IntentFinished { report, branch });
+  }
+  notify::dispatch(config, &notify::Event::RunFinished { run_id, intents });
+}
+
+/// Give every waiting parent intent the aggregate status of its children once
+/// none of them can still run: all done → done, all error → error, otherwise
+/// blocked. Rejected children are left out. Returns the IDs of settled parents.
+pub fn settle_parents(repo_path: &Path) -> Result<Vec<String>> {
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let all_intents = Intent::fetch_all(&intents_dir)?;
+  let mut settled = Vec::new();
+
+  for parent in all_intents
+    .iter()
+    .filter(|i| i.status == IntentStatus::Approved)
+  {
+    let children: Vec<&Intent> = all_intents
+      .iter()
+      .filter(|c| c.parent.as_deref() == Some(parent.id()))
+      .collect();
+    if children.is_empty()
+      || !children.iter().all(|c| {
+        matches!(
+          c.status,
+          IntentStatus::Done | IntentStatus::Error | IntentStatus::Rejected
+        )
+      })
+    {
+      continue;
+    }
+
+    let counted: Vec<&&Intent> = children
+      .iter()
+      .filter(|c| c.status != IntentStatus::Rejected)
+      .collect();
+    let status = if counted.is_empty() {
+      IntentStatus::Rejected
+    } else if counted.iter().all(|c| c.status == IntentStatus::Done) {
+      IntentStatus::Done
+    } else if counted.iter().all(|c| c.status == IntentStatus::Error) {
+      IntentStatus::Error
+    } else {
+      IntentStatus::Blocked
+    };
+    info!(
+      "parent intent {} settled from children: {status:?}",
+      parent.id()
+    );
+    let mut parent = parent.clone();
+    parent.status = status;
+    update_intent_file(repo_path, &parent)?;
+    settled.push(parent.id().to_string());
+  }
+  Ok(settled)
+}
+
+/// The first `depends_on` entry that can no longer become done (error, rejected,
+/// or itself blocked on a failed dependency).
+fn failed_dependency(intent: &Intent, all_intents: &[Intent]) -> Option<String> {
+  intent.depends_on.iter().find_map(|dep| {
+    let other = all_intents.iter().find(|o| o.id() == dep)?;
+    matches!(
+      other.status,
+      IntentStatus::Error | IntentStatus::Rejected | IntentStatus::Blocked
+    )
+    .then(|| {
+      format!(
+        "dependency {dep} is {}",
+        format!("{:?}", other.status).to_lowercase()
+      )
+    })
+  })
+}
+
+/// Block approved intents whose dependency failed instead of leaving them
+/// waiting forever, recording the reason in history under `run_id`. Repeats
+/// until nothing changes, so a chain of dependents is blocked in one pass.
+fn block_on_failed_dependencies(
+  repo_path: &Path,
+  run_id: &str,
+) -> Result<Vec<(String, IntentResult)>> {
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let mut all_intents = Intent::fetch_all(&intents_dir)?;
+  let mut blocked = Vec::new();
+  loop {
+    let next = all_intents.iter().enumerate().find_map(|(index, i)| {
+      (i.status == IntentStatus::Approved)
+        .then(|| failed_dependency(i, &all_intents))
+        .flatten()
+        .map(|reason| (index, reason))
+    });
+    let Some((index, reason)) = next else {
+      return Ok(blocked);
+    };
+    let intent = &mut all_intents[index];
+    intent.run_id = Some(run_id.to_string());
+    blocked.push((
+      intent.id().to_string(),
+      block_on_dependency(intent, repo_path, Vec::new(), Vec::new(), reason)?,
+    ));
+  }
+}
+
+fn block_on_dependency(
+  intent: &mut Intent,
+  repo_path: &Path,
+  flow: Vec<String>,
+  step_results: Vec<StepResult>,
+  reason: String,
+) -> Result<IntentResult> {
+  warn!("intent {} blocked: {reason}", intent.id());
+  intent.status = IntentStatus::Blocked;
+  update_intent_file(repo_path, intent)?;
+  let entry = HistoryEntry {
+    intent_id: intent.id().to_string(),
+    intent_type: intent.intent_type.clone(),
+    intent_risk: intent.risk.clone(),
+    title: intent.title.clone(),
+    flow: flow.clone(),
+    step_results: step_results.clone(),
+    outcome: Outcome::Failed,
+    failure_reason: Some(reason.clone()),
+    observations: vec![],
+    created_at: Some(chrono::Utc::now().to_rfc3339()),
+    run_id: intent.run_id.clone(),
+  };
+  if let Err(e) = history::write(repo_path, &entry) {
+    warn!("failed to write history: {e}");
+  }
+  Ok(IntentResult {
+    flow,
+    step_results,
+    outcome: Outcome::Failed,
+    failure_reason: Some(reason),
+  })
+}
+
+/// Approved intents whose dependencies are all done, after converting pending drafts.
+fn approved_targets(config: &Config, repo_path: &Path) -> Result<Vec<Intent>> {
+  let converted = crate::intent::draft::convert_drafts(repo_path)?;
+  if !converted.is_empty() {
+    info!("converted {} draft(s): {:?}", converted.len(), converted);
+  }
+
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let all_intents = Intent::fetch_all(&intents_dir)?;
+  Ok(
+    all_intents
+      .iter()
+      .filter(|i| {
+        matches!(
+          i.status,
+          IntentStatus::Approved | IntentStatus::SetupFailure
+        )
+      })
+      // Parents wait for their children; `settle_parents` finishes them
+      .filter(|i| {
+        !all_intents
+          .iter()
+          .any(|c| c.parent.as_deref() == Some(i.id()))
+      })
+      .filter(|i| {
+        i.depends_on.is_empty()
+          || i.depends_on.iter().all(|dep| {
+            all_intents
+              .iter()
+              .any(|other| other.id() == dep && other.status == IntentStatus::Done)
+          })
+      })
+      .filter(|i| i.matches_tags(&config.only_tags))
+      .cloned()
+      .collect(),
+  )
+}
+
+/// The repository directory name, for log spans.
+fn repo_name(repo_path: &Path) -> String {
+  repo_path
+    .canonicalize()
+    .ok()
+    .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+    .unwrap_or_else(|| repo_path.display().to_string())
+}
+
+/// Identifier shared by every intent one `run_intents` call processes: the start time, UTC,
+/// plus a random suffix so two runs started in the same second stay apart.
+pub fn new_run_id() -> String {
+  let suffix = uuid::Uuid::new_v4().simple().to_string();
+  format!(
+    "{}-{}",
+    chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+    &suffix[..8]
+  )
+}
+
+/// Build the end-of-run report from `run_intents` results.
+/// Titles and next actions are derived from the intent files as they stand after the run.
+pub fn build_run_report(repo_path: &Path, results: &[(String, IntentResult)]) -> RunReport {
+  let intents_dir = repo_path.join(".forge").join("intents");
+  let intents = Intent::fetch_all(&intents_dir).unwrap_or_default();
+
+  let entries = results
+    .iter()
+    .map(|(id, result)| {
+      let intent = intents.iter().find(|i| i.id() == id);
+      let cost_usd = result
+        .step_results
+        .iter()
+        .filter_map(|s| s.metadata.as_ref().and_then(|m| m.cost_usd))
+        .sum();
+      IntentReport {
+        intent_id: id.clone(),
+        title: intent.map(|i| i.title.clone()).unwrap_or_default(),
+        outcome: result.outcome.clone(),
+        failure_reason: result.failure_reason.clone(),
+        cost_usd,
+        next_action: intent.and_then(|i| next_action(i, &intents)),
+      }
+    })
+    .collect();
+
+  // Every intent in one run shares the same run_id
+  let run_id = results
+    .iter()
+    .find_map(|(id, _)| intents.iter().find(|i| i.id() == id)?.run_id.clone());
+
+  RunReport {
+    run_id,
+    finished_at: chrono::Utc::now().to_rfc3339(),
+    intents: entries,
+  }
+}
+
+fn next_action(intent: &Intent, intents: &[Intent]) -> Option<String> {
+  if intent.needs_clarification() {
+    return Some(format!(
+      "answer clarification: pfl-forge answer {} \"<answer>\"",
+      intent.id()
+    ));
+  }
+  if intent.awaiting_plan_approval() {
+    return Some(format!(
+      "review the plan in .forge/tasks/{id}.yaml, then: pfl-forge approve {id}",
+      id = intent.id()
+    ));
+  }
+  match intent.status {
+    IntentStatus::Blocked | IntentStatus::Error => Some(format!(
+      "investigate failure, then re-approve: pfl-forge approve {}",
+      intent.id()
+    )),
+    IntentStatus::Approved
+      if intents
+        .iter()
+        .any(|c| c.parent.as_deref() == Some(intent.id())) =>
+    {
+      Some("waiting on child intents; settles when they finish".into())
+    }
+    IntentStatus::Approved => Some("waiting on depends_on; picked up on next run".into()),
+    IntentStatus::SetupFailure => Some("worktree setup failed; retried on next run".into()),
+    IntentStatus::Proposed | IntentStatus::Done | IntentStatus::Rejected => None,
+  }
+}
+
+fn plan_needs_review(config: &Config, intent: &Intent) -> bool {
+  config.require_plan_approval && intent.plan_approval != Some(PlanApproval::Approved)
+}
+
+/// Stop after Analyze so a human can review the tasks before any code is written.
+/// `pfl-forge approve` resumes from the saved tasks.
+fn hold_for_plan_approval(
+  intent: &mut Intent,
+  repo_path: &Path,
+  flow: Vec<String>,
+  step_results: Vec<StepResult>,
+) -> Result<IntentResult> {
+  info!("intent {} waiting for plan approval", intent.id());
+  intent.status = IntentStatus::Blocked;
+  intent.plan_approval = Some(PlanApproval::Pending);
+  update_intent_file(repo_path, intent)?;
+  Ok(IntentResult {
+    flow,
+    step_results,
+    outcome: Outcome::Failed,
+    failure_reason: Some("awaiting plan approval".into()),
+  })
+}
+
 pub fn process_intent(
   intent: &mut Intent,
   config: &Config,
   claude: &impl Claude,
   repo_path: &Path,
 ) -> Result<IntentResult> {
-  let flow = default_flow(intent.intent_type.as_deref());
+  let flow = flow_for(config, intent.intent_type.as_deref())?;
   let flow_names: Vec<String> = flow.iter().map(|s| s.name().to_string()).collect();
 
   info!("processing intent {}: flow={:?}", intent, flow_names);
 
+  // Every commit made for this intent, by the worker or the Runner, uses the configured identity
+  let _identity = process::scoped_env(git::worktree::identity_env(
+    config.git_author.as_deref(),
+    config.git_email.as_deref(),
+    config.sign_commits,
+  ));
+
   if flow.contains(&Step::Audit) {
     return run_audit_report_flow(intent, config, claude, repo_path, flow_names);
   }
@@ -177,20 +689,36 @@ pub fn process_intent(
   let (mut tasks, worktree_path) = if can_resume_tasks {
     // Resume: read tasks from main repo, skip analyze, reuse existing worktree
     info!("resuming from analyze: reading tasks from main repo");
-    let tasks = task::read_all_tasks(repo_path, intent.id())?;
+    let mut tasks = task::read_all_tasks(repo_path, intent.id())?;
+    task::reset_interrupted(&mut tasks);
     (tasks, worktree_path_for_resume)
   } else if can_resume_from_tasks {
+    if plan_needs_review(config, intent) {
+      return hold_for_plan_approval(intent, repo_path, flow_names, step_results);
+    }
     // Tasks exist but worktree is gone: recreate worktree, skip analyze
     info!("resuming from tasks: recreating worktree");
-    let tasks = task::read_all_tasks(repo_path, intent.id())?;
+    let mut tasks = task::read_all_tasks(repo_path, intent.id())?;
+    task::reset_interrupted(&mut tasks);
     let worktree_path = git::worktree::create(
       repo_path,
       &config.worktree_dir,
       &intent.branch_name(),
       &config.base_branch,
+      &sparse_paths(config, &tasks),
     )?;
     git::worktree::ensure_gitignore_forge(&worktree_path)?;
-    run_worktree_setup(&worktree_path, &config.worktree_setup)?;
+    prepare_worktree(config, &worktree_path)?;
+    if let Err(e) = setup::run(config, repo_path, &worktree_path) {
+      return fail_setup(
+        intent,
+        repo_path,
+        &worktree_path,
+        flow_names,
+        step_results,
+        e,
+      );
+    }
     (tasks, worktree_path)
   } else {
     // Normal or clarification resume: run analyze
@@ -228,7 +756,9 @@ pub fn process_intent(
       )?;
     step_results.push(StepResult {
       step: "analyze".into(),
+      status: StepStatus::Success,
       duration_secs: start.elapsed().as_secs(),
+      model: Some(model::resolve(&config.models.analyze).into()),
       metadata: Some(analyze_meta.clone()),
     });
 
@@ -245,6 +775,9 @@ pub fn process_intent(
           .any(|other| other.id() == dep && other.status == IntentStatus::Done)
       });
 
+      if let Some(reason) = failed_dependency(intent, &all_intents) {
+        return block_on_dependency(intent, repo_path, flow_names, step_results, reason);
+      }
       if !deps_satisfied {
         info!(
           "intent {} waiting on depends_on: {:?}",
@@ -301,6 +834,13 @@ pub fn process_intent(
             });
         }
         update_intent_file(repo_path, intent)?;
+        notify::dispatch(
+          config,
+          &notify::Event::Clarification {
+            intent,
+            questions: &clarifications,
+          },
+        );
         return Ok(IntentResult {
           flow: flow_names,
           step_results,
@@ -320,7 +860,8 @@ pub fn process_intent(
           );
           std::fs::write(intents_dir.join(format!("{child_id}.yaml")), &yaml)?;
         }
-        intent.status = IntentStatus::Done;
+        // Stays approved: the children run first and `settle_parents`
+        // sets the parent's final status from theirs
         update_intent_file(repo_path, intent)?;
         return Ok(IntentResult {
           flow: flow_names,
@@ -352,6 +893,12 @@ pub fn process_intent(
 
     // Persist tasks to main repo (before worktree creation, crash-safe)
     task::write_all_tasks(repo_path, intent.id(), &tasks)?;
+    // An earlier approval covered a different plan
+    intent.plan_approval = None;
+
+    if plan_needs_review(config, intent) {
+      return hold_for_plan_approval(intent, repo_path, flow_names, step_results);
+    }
 
     // Worktree setup (shared by all tasks)
     let worktree_path = git::worktree::create(
@@ -359,22 +906,38 @@ pub fn process_intent(
       &config.worktree_dir,
       &intent.branch_name(),
       &config.base_branch,
+      &sparse_paths(config, &tasks),
     )?;
     git::worktree::ensure_gitignore_forge(&worktree_path)?;
-    run_worktree_setup(&worktree_path, &config.worktree_setup)?;
+    prepare_worktree(config, &worktree_path)?;
+    if let Err(e) = setup::run(config, repo_path, &worktree_path) {
+      return fail_setup(
+        intent,
+        repo_path,
+        &worktree_path,
+        flow_names,
+        step_results,
+        e,
+      );
+    }
 
     update_intent_file(repo_path, intent)?;
 
     (tasks, worktree_path)
   };
 
-  // Run tasks in dependency order
-  let resume_session = if can_resume_tasks || can_resume_from_tasks {
-    intent
-      .sessions
-      .implement
-      .as_ref()
-      .map(|sid| SessionMode::Resume(sid.clone()))
+  // Continue the implement session only for the task the last checkpoint shows
+  // was cut off, and only while the worktree still holds its work; otherwise the
+  // task restarts in a new session
+  let resume_session = if can_resume_tasks {
+    let head = git::branch::head_commit(&worktree_path).ok();
+    checkpoint::read(repo_path, intent.id())
+      .and_then(|cp| checkpoint::interrupted_task(&cp, head.as_deref()).map(String::from))
+      .zip(intent.sessions.implement.clone())
+      .map(|(task_id, sid)| {
+        info!("task {task_id}: continuing interrupted implement session {sid}");
+        (task_id, SessionMode::Resume(sid))
+      })
   } else {
     None
   };
@@ -387,8 +950,9 @@ pub fn process_intent(
     repo_path,
     &worktree_path,
     timeout,
+    &flow,
     &mut step_results,
-    resume_session.as_ref(),
+    resume_session.as_ref().map(|(id, s)| (id.as_str(), s)),
     &mut exec_summary,
   );
 
@@ -397,6 +961,9 @@ pub fn process_intent(
 
   intent.status = intent_status;
   update_intent_file(repo_path, intent)?;
+  if intent.status == IntentStatus::Done {
+    checkpoint::clear(repo_path, intent.id());
+  }
 
   // Record history
   let entry = HistoryEntry {
@@ -410,6 +977,7 @@ pub fn process_intent(
     failure_reason: failure_reason.clone(),
     observations: vec![],
     created_at: Some(chrono::Utc::now().to_rfc3339()),
+    run_id: intent.run_id.clone(),
   };
   if let Err(e) = history::write(repo_path, &entry) {
     warn!("failed to write history: {e}");
@@ -430,13 +998,16 @@ pub fn process_intent(
     let start = Instant::now();
     let reflect_result = reflect::reflect(intent, config, claude, repo_path, &reflect_session);
     let reflect_meta = reflect_result.as_ref().ok().map(|(_, m)| m.clone());
+    let reflect_status = StepStatus::of(&reflect_result);
     match reflect_result {
       Ok((r, _)) => info!("reflect: generated {} intents", r.intents.len()),
       Err(e) => warn!("reflect failed: {e}"),
     }
     step_results.push(StepResult {
       step: "reflect".into(),
+      status: reflect_status,
       duration_secs: start.elapsed().as_secs(),
+      model: Some(model::resolve(&config.models.reflect).into()),
       metadata: reflect_meta,
     });
   }
@@ -458,6 +1029,7 @@ enum TaskOutcome {
   Escalated(String),
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_tasks_in_order(
   intent: &mut Intent,
   tasks: &mut [Task],
@@ -466,19 +1038,25 @@ fn run_tasks_in_order(
   repo_path: &Path,
   worktree_path: &Path,
   timeout: std::time::Duration,
+  flow: &[Step],
   step_results: &mut Vec<StepResult>,
-  resume_session: Option<&SessionMode>,
+  resume_session: Option<(&str, &SessionMode)>,
   exec_summary: &mut ExecutionSummary,
 ) -> Vec<TaskOutcome> {
   let task_ids: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
   let mut outcomes: Vec<Option<TaskOutcome>> = vec![None; tasks.len()];
-  let mut done_ids: Vec<String> = Vec::new();
+  // Tasks completed in an earlier run are not repeated
+  let mut done_ids: Vec<String> = tasks
+    .iter()
+    .filter(|t| t.status == WorkStatus::Completed)
+    .map(|t| t.id.clone())
+    .collect();
   let mut failed_ids: Vec<String> = Vec::new();
 
   loop {
-    // Find next runnable task: pending, all depends_on satisfied
+    // Find next runnable task: pending (or resumed after implement), all depends_on satisfied
     let next = tasks.iter().position(|t| {
-      t.status == WorkStatus::Pending
+      matches!(t.status, WorkStatus::Pending | WorkStatus::Implemented)
         && t
           .depends_on
           .iter()
@@ -504,13 +1082,10 @@ fn run_tasks_in_order(
     let task = &mut tasks[idx];
     let selected_model = task.complexity().select_model(&config.models);
 
-    // Use resume session only for the first task; new session otherwise
-    let session = if idx == 0 {
-      resume_session
-        .cloned()
-        .unwrap_or_else(SessionMode::new_session)
-    } else {
-      SessionMode::new_session()
+    // Use the resume session only for the interrupted task; new session otherwise
+    let session = match resume_session {
+      Some((id, session)) if id == task.id => session.clone(),
+      _ => SessionMode::new_session(),
     };
     // Write session_id to intent before spawning
     if let Some(sid) = session.session_id() {
@@ -526,10 +1101,15 @@ fn run_tasks_in_order(
       worktree_path,
       selected_model,
       timeout,
+      flow,
       step_results,
       &session,
     );
 
+    if let Err(e) = task::save_task(repo_path, task) {
+      warn!("failed to persist task {}: {e}", task.id);
+    }
+
     // Record task summary
     let commits =
       git::branch::commit_messages(worktree_path, &config.base_branch).unwrap_or_default();
@@ -542,6 +1122,7 @@ fn run_tasks_in_order(
       task_id: task.id.clone(),
       commits,
       review: review_summary,
+      checks: task.checks.clone(),
     });
 
     match &outcome {
@@ -603,6 +1184,7 @@ fn aggregate_task_outcomes(
   }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_implement_review_cycle(
   intent: &mut Intent,
   task: &mut Task,
@@ -612,6 +1194,7 @@ fn run_implement_review_cycle(
   worktree_path: &Path,
   selected_model: &str,
   timeout: std::time::Duration,
+  flow: &[Step],
   step_results: &mut Vec<StepResult>,
   initial_session: &SessionMode,
 ) -> (TaskOutcome, Option<ReviewResult>) {
@@ -632,120 +1215,278 @@ fn run_implement_review_cycle(
       s
     };
 
-    // Implement
-    task.status = WorkStatus::Implementing;
-    let start = Instant::now();
-    let impl_result = implement::run(
-      intent,
-      task,
-      claude,
-      selected_model,
-      worktree_path,
-      Some(timeout),
-      review_feedback.as_ref(),
-      &session,
-    );
-    let impl_meta = impl_result.as_ref().ok().map(|raw| parse_metadata(raw));
-    step_results.push(StepResult {
-      step: "implement".into(),
-      duration_secs: start.elapsed().as_secs(),
-      metadata: impl_meta,
-    });
-
-    if let Err(e) = impl_result {
-      task.status = WorkStatus::Failed;
-      return (TaskOutcome::Failed(format!("implement failed: {e}")), None);
-    }
-
-    update_intent_file(repo_path, intent).ok();
+    // Resume: commits from an earlier run's implement are already on the branch
+    let resume_after_implement = attempt == 0
+      && task.status == WorkStatus::Implemented
+      && git::branch::commit_count(worktree_path, &config.base_branch, "HEAD").unwrap_or(0) > 0;
 
-    // Rebase
-    let start = Instant::now();
-    let rebase_ok =
-      git::branch::try_rebase(worktree_path, &config.base_branch, intent.id()).unwrap_or(false);
-    step_results.push(StepResult {
-      step: "rebase".into(),
-      duration_secs: start.elapsed().as_secs(),
-      metadata: None,
-    });
+    // The rejected attempt's HEAD, so the next review sees what the retry changed
+    let previous_head = review_feedback
+      .as_ref()
+      .and_then(|_| git::branch::head_commit(worktree_path).ok());
+    let mut remediation: Option<String> = None;
 
-    if !rebase_ok {
-      info!(
-        "rebase conflict for {}, attempting reimplementation",
-        intent.id()
-      );
-      // Remove worktree, delete old branch, and recreate from updated main
-      if let Err(e) = git::worktree::remove(repo_path, worktree_path) {
-        warn!("failed to remove worktree: {e}");
+    if resume_after_implement {
+      info!("task {}: resuming after implement", task.id);
+    } else {
+      // Implement
+      task.status = WorkStatus::Implementing;
+      if let Err(e) = task::save_task(repo_path, task) {
+        warn!("failed to persist task {}: {e}", task.id);
       }
-      let _ = git::branch::delete(repo_path, &intent.branch_name());
-      let new_wt = match git::worktree::create(
+      let implemented = run_implement_with_retries(
+        intent,
+        task,
+        config,
+        claude,
         repo_path,
-        &config.worktree_dir,
-        &intent.branch_name(),
-        &config.base_branch,
-      ) {
-        Ok(p) => p,
-        Err(e) => {
+        worktree_path,
+        selected_model,
+        timeout,
+        review_feedback.as_ref().map(implement::Feedback::Review),
+        session,
+        step_results,
+      );
+      match implemented {
+        Ok(raw) => remediation = implement::remediation_notes(&raw),
+        Err(()) => {
           return (
-            TaskOutcome::Escalated(format!("worktree recreation failed: {e}")),
+            TaskOutcome::Failed(format!("implement failed: {}", task.errors.join("; "))),
             None,
           )
         }
-      };
-      let _ = git::worktree::ensure_gitignore_forge(&new_wt);
+      }
 
-      // Reimplementation attempt
-      let reimpl_session = SessionMode::new_session();
+      task.status = WorkStatus::Implemented;
+      if let Err(e) = task::save_task(repo_path, task) {
+        warn!("failed to persist task {}: {e}", task.id);
+      }
+      update_intent_file(repo_path, intent).ok();
+    }
+
+    if let Some(outcome) = run_custom_steps(
+      flow,
+      intent,
+      task,
+      config,
+      claude,
+      worktree_path,
+      timeout,
+      step_results,
+    ) {
+      task.status = WorkStatus::Failed;
+      return (outcome, None);
+    }
+
+    // Format + checks (lint)
+    if let Some(outcome) = run_checks(
+      intent,
+      task,
+      config,
+      claude,
+      repo_path,
+      worktree_path,
+      selected_model,
+      timeout,
+      step_results,
+    ) {
+      task.status = WorkStatus::Failed;
+      return (outcome, None);
+    }
+    if let Some(outcome) = run_identity_step(config, worktree_path) {
+      task.status = WorkStatus::Failed;
+      return (outcome, None);
+    }
+    run_commit_template_step(config, intent, worktree_path);
+    if let Some(outcome) = run_commit_lint_step(intent, config, claude, worktree_path, step_results)
+    {
+      task.status = WorkStatus::Failed;
+      return (outcome, None);
+    }
+
+    // Rebase; conflicts go to the conflict agent first, and each unresolved one
+    // reimplements from the updated base, up to max_rebase_retries
+    let mut conflict_attempts = Vec::new();
+    let mut rebase = run_rebase_step(
+      intent,
+      task,
+      config,
+      claude,
+      worktree_path,
+      timeout,
+      &mut conflict_attempts,
+      step_results,
+    );
+    while rebase == RebaseResult::Conflict {
+      task.rebase_conflicts += 1;
+      task
+        .errors
+        .push(format!("rebase conflict with {}", config.base_branch));
+      if task.rebase_conflicts > config.max_rebase_retries {
+        task.status = WorkStatus::Failed;
+        task.retries_exhausted = true;
+        let reason = if config.max_rebase_retries == 0 {
+          "rebase conflict".to_string()
+        } else {
+          let reason = format!(
+            "rebase conflict persists after reimplementation ({} conflict(s))",
+            task.rebase_conflicts
+          );
+          escalation::escalate(repo_path, config, intent, task, &reason, &conflict_attempts);
+          reason
+        };
+        return (TaskOutcome::Escalated(reason), None);
+      }
+      info!(
+        "rebase conflict for {}, attempting reimplementation ({}/{})",
+        intent.id(),
+        task.rebase_conflicts,
+        config.max_rebase_retries
+      );
+      // Remove worktree, delete old branch, and recreate from updated main
+      if let Err(e) = git::worktree::remove(repo_path, worktree_path) {
+        warn!("failed to remove worktree: {e}");
+      }
+      let _ = git::branch::delete(repo_path, &intent.branch_name());
+      let new_wt = match git::worktree::create(
+        repo_path,
+        &config.worktree_dir,
+        &intent.branch_name(),
+        &config.base_branch,
+        &sparse_paths(config, std::slice::from_ref(task)),
+      ) {
+        Ok(p) => p,
+        Err(e) => {
+          return (
+            TaskOutcome::Escalated(format!("worktree recreation failed: {e}")),
+            None,
+          )
+        }
+      };
+      let _ = git::worktree::ensure_gitignore_forge(&new_wt);
+      if let Err(e) = prepare_worktree(config, &new_wt) {
+        return (
+          TaskOutcome::Escalated(format!("worktree recreation failed: {e}")),
+          None,
+        );
+      }
+
+      // Reimplementation attempt
+      let reimpl_session = SessionMode::new_session();
       if let Some(sid) = reimpl_session.session_id() {
         intent.sessions.implement = Some(sid.to_string());
         update_intent_file(repo_path, intent).ok();
       }
-      let start = Instant::now();
-      let reimpl = implement::run(
+      let reimpl = run_implement_with_retries(
         intent,
         task,
+        config,
         claude,
-        selected_model,
+        repo_path,
         &new_wt,
-        Some(timeout),
+        selected_model,
+        timeout,
         None,
-        &reimpl_session,
+        reimpl_session,
+        step_results,
       );
-      let reimpl_meta = reimpl.as_ref().ok().map(|raw| parse_metadata(raw));
-      step_results.push(StepResult {
-        step: "implement".into(),
-        duration_secs: start.elapsed().as_secs(),
-        metadata: reimpl_meta,
-      });
-
       if reimpl.is_err() {
-        task.status = WorkStatus::Failed;
-        return (
-          TaskOutcome::Escalated("reimplementation failed after rebase conflict".into()),
-          None,
+        let reason = format!(
+          "reimplementation failed after rebase conflict: {}",
+          task.errors.join("; ")
         );
+        escalation::escalate(repo_path, config, intent, task, &reason, &conflict_attempts);
+        return (TaskOutcome::Escalated(reason), None);
+      }
+      task.status = WorkStatus::Implemented;
+      if let Err(e) = task::save_task(repo_path, task) {
+        warn!("failed to persist task {}: {e}", task.id);
+      }
+
+      if let Some(outcome) = run_custom_steps(
+        flow,
+        intent,
+        task,
+        config,
+        claude,
+        &new_wt,
+        timeout,
+        step_results,
+      ) {
+        task.status = WorkStatus::Failed;
+        return (outcome, None);
+      }
+      if let Some(outcome) = run_checks(
+        intent,
+        task,
+        config,
+        claude,
+        repo_path,
+        &new_wt,
+        selected_model,
+        timeout,
+        step_results,
+      ) {
+        task.status = WorkStatus::Failed;
+        return (outcome, None);
+      }
+      if let Some(outcome) = run_identity_step(config, &new_wt) {
+        task.status = WorkStatus::Failed;
+        return (outcome, None);
+      }
+      run_commit_template_step(config, intent, &new_wt);
+      if let Some(outcome) = run_commit_lint_step(intent, config, claude, &new_wt, step_results) {
+        task.status = WorkStatus::Failed;
+        return (outcome, None);
       }
 
       // Rebase again after reimplementation
-      let start = Instant::now();
-      let rebase_ok2 =
-        git::branch::try_rebase(&new_wt, &config.base_branch, intent.id()).unwrap_or(false);
-      step_results.push(StepResult {
-        step: "rebase".into(),
-        duration_secs: start.elapsed().as_secs(),
-        metadata: None,
-      });
+      rebase = run_rebase_step(
+        intent,
+        task,
+        config,
+        claude,
+        &new_wt,
+        timeout,
+        &mut conflict_attempts,
+        step_results,
+      );
+    }
 
-      if !rebase_ok2 {
+    // The conflict agent's edits have not been through format / lint / tests
+    if rebase == RebaseResult::Resolved {
+      if let Some(outcome) = run_checks(
+        intent,
+        task,
+        config,
+        claude,
+        repo_path,
+        worktree_path,
+        selected_model,
+        timeout,
+        step_results,
+      ) {
         task.status = WorkStatus::Failed;
-        return (
-          TaskOutcome::Escalated("rebase conflict persists after reimplementation".into()),
-          None,
-        );
+        return (outcome, None);
       }
     }
 
+    // Benchmark against the rebased base
+    let log_dir = check::log_dir(repo_path, intent.id(), &task.id);
+    let bench_report = match run_bench_step(task, config, worktree_path, &log_dir, step_results) {
+      Ok(report) => report,
+      Err(outcome) => {
+        task.status = WorkStatus::Failed;
+        return (outcome, None);
+      }
+    };
+
+    // Flows without review finish once checks and bench pass
+    if !flow.contains(&Step::Review) {
+      task.status = WorkStatus::Completed;
+      return (TaskOutcome::Done, None);
+    }
+
     // Review
     let review_session = SessionMode::new_session();
     if let Some(sid) = review_session.session_id() {
@@ -753,15 +1494,39 @@ fn run_implement_review_cycle(
       update_intent_file(repo_path, intent).ok();
     }
     let start = Instant::now();
-    let mut review_result = review::review(
-      intent,
-      task,
-      config,
-      claude,
-      worktree_path,
-      &config.base_branch,
-      &review_session,
-    );
+    let mut review_result = match &review_feedback {
+      Some(previous) => {
+        let changes = previous_head
+          .as_deref()
+          .and_then(|head| git::branch::diff_since(worktree_path, head).ok());
+        let follow_up = review::FollowUp {
+          previous,
+          changes: changes.as_deref(),
+          remediation: remediation.as_deref(),
+        };
+        review::re_review(
+          intent,
+          task,
+          config,
+          claude,
+          worktree_path,
+          &config.base_branch,
+          bench_report.as_deref(),
+          &follow_up,
+          &review_session,
+        )
+      }
+      None => review::review(
+        intent,
+        task,
+        config,
+        claude,
+        worktree_path,
+        &config.base_branch,
+        bench_report.as_deref(),
+        &review_session,
+      ),
+    };
     let review_meta = review_result.as_ref().ok().map(|(_, m)| m.clone());
     let review_sid = review_session.session_id().map(String::from);
     // Attach session_id to ReviewResult for debugging
@@ -770,7 +1535,9 @@ fn run_implement_review_cycle(
     }
     step_results.push(StepResult {
       step: "review".into(),
+      status: StepStatus::of(&review_result),
       duration_secs: start.elapsed().as_secs(),
+      model: Some(model::resolve(&config.models.review).into()),
       metadata: review_meta,
     });
 
@@ -832,6 +1599,592 @@ fn run_implement_review_cycle(
   unreachable!()
 }
 
+/// Run Implement, retrying with a fresh session when the agent itself errors.
+/// Returns the raw output of the successful run.
+/// Failures are counted on the task and logged in `task.errors`; once more than
+/// `max_implement_retries` have failed the task is marked failed for good.
+#[allow(clippy::too_many_arguments)]
+fn run_implement_with_retries(
+  intent: &mut Intent,
+  task: &mut Task,
+  config: &Config,
+  claude: &impl Claude,
+  repo_path: &Path,
+  worktree_path: &Path,
+  selected_model: &str,
+  timeout: std::time::Duration,
+  feedback: Option<implement::Feedback>,
+  mut session: SessionMode,
+  step_results: &mut Vec<StepResult>,
+) -> std::result::Result<String, ()> {
+  loop {
+    let start = Instant::now();
+    let tracker = checkpoint::Tracker::new(
+      repo_path,
+      intent.id(),
+      &task.id,
+      "implement",
+      worktree_path,
+      &config.base_branch,
+      Duration::from_secs(config.checkpoint_interval_secs),
+    );
+    let impl_result = tracker.run(|| {
+      implement::run(
+        intent,
+        task,
+        claude,
+        config.prompts.get("implement"),
+        selected_model,
+        worktree_path,
+        Some(timeout),
+        feedback,
+        &session,
+      )
+    });
+    let impl_meta = impl_result.as_ref().ok().map(|raw| parse_metadata(raw));
+    tracker.finish(impl_meta.as_ref().and_then(|m| m.num_turns));
+    step_results.push(StepResult {
+      step: "implement".into(),
+      status: StepStatus::of(&impl_result),
+      duration_secs: start.elapsed().as_secs(),
+      model: Some(selected_model.into()),
+      metadata: impl_meta,
+    });
+
+    let e = match impl_result {
+      Ok(raw) => {
+        commit_sandboxed_changes(config, worktree_path, &task.title);
+        return Ok(raw);
+      }
+      Err(e) => e,
+    };
+    task.implement_failures += 1;
+    task.errors.push(format!("implement: {e}"));
+    if task.implement_failures > config.max_implement_retries {
+      task.status = WorkStatus::Failed;
+      task.retries_exhausted = true;
+      if let Err(e) = task::save_task(repo_path, task) {
+        warn!("failed to persist task {}: {e}", task.id);
+      }
+      return Err(());
+    }
+    warn!(
+      "task {}: implement failed, retrying ({}/{}): {e}",
+      task.id, task.implement_failures, config.max_implement_retries
+    );
+    if let Err(e) = task::save_task(repo_path, task) {
+      warn!("failed to persist task {}: {e}", task.id);
+    }
+    session = SessionMode::new_session();
+    if let Some(sid) = session.session_id() {
+      intent.sessions.implement = Some(sid.to_string());
+      update_intent_file(repo_path, intent).ok();
+    }
+  }
+}
+
+/// How `run_rebase_step` ended.
+#[derive(Debug, PartialEq)]
+enum RebaseResult {
+  /// Rebased without conflicts.
+  Clean,
+  /// Rebased after the conflict agent resolved conflicts; its edits have not been checked.
+  Resolved,
+  /// The rebase failed or a conflict was left unresolved; the rebase was aborted.
+  Conflict,
+}
+
+/// Rebase onto the base branch, letting the conflict agent resolve each stopped
+/// commit in place, and record the steps.
+#[allow(clippy::too_many_arguments)]
+fn run_rebase_step(
+  intent: &Intent,
+  task: &Task,
+  config: &Config,
+  claude: &impl Claude,
+  worktree_path: &Path,
+  timeout: std::time::Duration,
+  attempts: &mut Vec<escalation::ConflictAttempt>,
+  step_results: &mut Vec<StepResult>,
+) -> RebaseResult {
+  let start = Instant::now();
+  info!("rebasing {} onto {}", intent.id(), config.base_branch);
+  let mut state = git::branch::start_rebase(worktree_path, &config.base_branch);
+  step_results.push(StepResult {
+    step: "rebase".into(),
+    status: if matches!(state, Ok(git::branch::RebaseState::Done)) {
+      StepStatus::Success
+    } else {
+      StepStatus::Failed
+    },
+    duration_secs: start.elapsed().as_secs(),
+    model: None,
+    metadata: None,
+  });
+
+  let mut resolved_any = false;
+  loop {
+    let conflicts = match state {
+      Ok(git::branch::RebaseState::Done) if resolved_any => return RebaseResult::Resolved,
+      Ok(git::branch::RebaseState::Done) => return RebaseResult::Clean,
+      Ok(git::branch::RebaseState::Conflicted(files)) => files,
+      Err(e) => {
+        warn!("rebase failed for {}: {e}", intent.id());
+        return RebaseResult::Conflict;
+      }
+    };
+    warn!(
+      "rebase conflict for {}: {}",
+      intent.id(),
+      conflicts.join(", ")
+    );
+
+    let start = Instant::now();
+    let result = conflict::resolve(
+      intent,
+      task,
+      config,
+      claude,
+      worktree_path,
+      &conflicts,
+      Some(timeout),
+    );
+    if result.is_ok() && config.claude.sandbox.is_some() {
+      conflict::stage_resolved(worktree_path, &conflicts);
+    }
+    let resolved = result.is_ok() && conflict::is_resolved(worktree_path, &conflicts);
+    step_results.push(StepResult {
+      step: "resolve_conflicts".into(),
+      status: if resolved {
+        StepStatus::Success
+      } else {
+        StepStatus::Failed
+      },
+      duration_secs: start.elapsed().as_secs(),
+      model: Some(model::resolve(&config.models.conflict).into()),
+      metadata: result.as_ref().ok().map(|raw| parse_metadata(raw)),
+    });
+    attempts.push(escalation::ConflictAttempt::new(conflicts, &result));
+    if !resolved {
+      warn!("conflict resolution failed for {}", intent.id());
+      git::branch::abort_rebase(worktree_path);
+      return RebaseResult::Conflict;
+    }
+    resolved_any = true;
+    state = git::branch::continue_rebase(worktree_path);
+  }
+}
+
+/// Run the flow's custom steps (`steps.<name>`) in order on the worktree.
+/// Returns `None` when all succeed, or the outcome to fail the task with.
+#[allow(clippy::too_many_arguments)]
+fn run_custom_steps(
+  flow: &[Step],
+  intent: &Intent,
+  task: &Task,
+  config: &Config,
+  claude: &impl Claude,
+  worktree_path: &Path,
+  timeout: std::time::Duration,
+  step_results: &mut Vec<StepResult>,
+) -> Option<TaskOutcome> {
+  for step in flow {
+    let Step::Custom(name) = step else { continue };
+    let Some(custom) = config.steps.get(name) else {
+      return Some(TaskOutcome::Failed(format!("unknown step: {name}")));
+    };
+    let start = Instant::now();
+    let result = custom_step::run(
+      name,
+      custom,
+      intent,
+      task,
+      config,
+      claude,
+      worktree_path,
+      Some(timeout),
+    );
+    step_results.push(StepResult {
+      step: name.clone(),
+      status: StepStatus::of(&result),
+      duration_secs: start.elapsed().as_secs(),
+      model: Some(
+        model::resolve(custom.model.as_deref().unwrap_or(&config.models.implement)).into(),
+      ),
+      metadata: result.as_ref().ok().map(|raw| parse_metadata(raw)),
+    });
+    if let Err(e) = result {
+      return Some(TaskOutcome::Failed(format!("{name} failed: {e}")));
+    }
+    commit_sandboxed_changes(config, worktree_path, &format!("chore: {name}"));
+  }
+  None
+}
+
+/// A sandboxed agent cannot write to the read-only git directory, so its
+/// changes are committed from the host.
+fn commit_sandboxed_changes(config: &Config, worktree_path: &Path, message: &str) {
+  if config.claude.sandbox.is_none() {
+    return;
+  }
+  if let Err(e) = git::branch::commit_all(worktree_path, message) {
+    warn!("failed to commit sandboxed changes: {e}");
+  }
+}
+
+/// Apply `format_command`, then run the configured verification commands in
+/// the worktree (or the task's `checks.dir`), wrapped in `check_setup` / `check_teardown`. Returns `None`
+/// when all checks pass (or none are configured), or the outcome to fail the task with.
+#[allow(clippy::too_many_arguments)]
+fn run_checks(
+  intent: &mut Intent,
+  task: &Task,
+  config: &Config,
+  claude: &impl Claude,
+  repo_path: &Path,
+  worktree_path: &Path,
+  selected_model: &str,
+  timeout: std::time::Duration,
+  step_results: &mut Vec<StepResult>,
+) -> Option<TaskOutcome> {
+  if let Some(command) = &config.format_command {
+    run_format_step(worktree_path, command, step_results);
+  }
+
+  let overrides = task.checks.clone().unwrap_or_default();
+  let lint_command = match check::lint_command(
+    config.lint_command.as_ref(),
+    overrides.lint_command.as_ref(),
+    &config.lint_commands,
+  ) {
+    Ok(command) => command,
+    Err(e) => return Some(TaskOutcome::Failed(e.to_string())),
+  };
+  if lint_command.is_none() && config.coverage_command.is_none() {
+    return None;
+  }
+
+  let check_dir = match check::check_dir(worktree_path, overrides.dir.as_deref()) {
+    Ok(dir) => dir,
+    Err(e) => return Some(TaskOutcome::Failed(e.to_string())),
+  };
+
+  let log_dir = check::log_dir(repo_path, intent.id(), &task.id);
+  if let Err(e) = check::setup(worktree_path, &config.check_setup) {
+    check::teardown(worktree_path, &config.check_teardown);
+    return Some(TaskOutcome::Failed(format!("{e}")));
+  }
+
+  let mut outcome = None;
+  if let Some(command) = lint_command {
+    outcome = run_check_step(
+      intent,
+      task,
+      config,
+      claude,
+      repo_path,
+      worktree_path,
+      selected_model,
+      timeout,
+      step_results,
+      "lint",
+      || check::lint(&check_dir, command, &log_dir),
+    );
+  }
+  if outcome.is_none() {
+    if let Some(command) = &config.coverage_command {
+      outcome = run_check_step(
+        intent,
+        task,
+        config,
+        claude,
+        repo_path,
+        worktree_path,
+        selected_model,
+        timeout,
+        step_results,
+        "coverage",
+        || check::coverage(&check_dir, command, config.min_coverage, &log_dir),
+      );
+    }
+  }
+
+  check::teardown(worktree_path, &config.check_teardown);
+  outcome
+}
+
+/// Run `bench_command` on HEAD and the base branch. Returns the comparison for the
+/// review prompt, or the outcome to fail the task with when HEAD regressed by more
+/// than `max_regression_pct`. There is no corrective pass: regressions go to a human.
+fn run_bench_step(
+  task: &Task,
+  config: &Config,
+  worktree_path: &Path,
+  log_dir: &Path,
+  step_results: &mut Vec<StepResult>,
+) -> std::result::Result<Option<String>, TaskOutcome> {
+  let Some(command) = &config.bench_command else {
+    return Ok(None);
+  };
+  let subdir = task.checks.as_ref().and_then(|c| c.dir.as_deref());
+
+  let start = Instant::now();
+  let result = check::bench(
+    worktree_path,
+    subdir,
+    &config.base_branch,
+    command,
+    &config.check_setup,
+    &config.check_teardown,
+    log_dir,
+  );
+  step_results.push(StepResult {
+    step: "bench".into(),
+    status: StepStatus::of(&result),
+    duration_secs: start.elapsed().as_secs(),
+    model: None,
+    metadata: None,
+  });
+
+  let comparison = result.map_err(|e| TaskOutcome::Failed(format!("bench failed: {e}")))?;
+  let regression = comparison.regression_pct();
+  if regression > config.max_regression_pct {
+    return Err(TaskOutcome::Failed(format!(
+      "bench regression {regression:.1}% exceeds max_regression_pct {:.1}%: {}",
+      config.max_regression_pct,
+      comparison.summary()
+    )));
+  }
+  Ok(Some(format!(
+    "`{command}` (lower is better): {}. Allowed regression: {:.1}%.",
+    comparison.summary(),
+    config.max_regression_pct
+  )))
+}
+
+/// Rewrite the worker's commit subjects to follow `commit_template`.
+/// Failures are logged only; the messages are cosmetic.
+fn run_commit_template_step(config: &Config, intent: &Intent, worktree_path: &Path) {
+  let Some(template) = &config.commit_template else {
+    return;
+  };
+  let result = git::branch::rewrite_subjects(
+    worktree_path,
+    &config.base_branch,
+    config.sign_commits,
+    |subject| commit_template::apply(template, intent, subject),
+  );
+  if let Err(e) = result {
+    warn!("commit_template: {e}");
+  }
+}
+
+/// Check commit subjects against `commit_pattern`. Violations get one fix pass:
+/// the agent proposes subjects, the Runner rewrites them and checks again.
+fn run_commit_lint_step(
+  intent: &Intent,
+  config: &Config,
+  claude: &impl Claude,
+  worktree_path: &Path,
+  step_results: &mut Vec<StepResult>,
+) -> Option<TaskOutcome> {
+  let pattern = config.commit_pattern.as_ref()?;
+  let regex = match regex::Regex::new(pattern) {
+    Ok(r) => r,
+    Err(e) => return Some(TaskOutcome::Failed(format!("invalid commit_pattern: {e}"))),
+  };
+  let violations = |worktree_path: &Path| -> Result<Vec<String>> {
+    let subjects = git::branch::commit_messages(worktree_path, &config.base_branch)?;
+    Ok(
+      subjects
+        .into_iter()
+        .filter(|s| !regex.is_match(s))
+        .collect(),
+    )
+  };
+
+  let bad = match violations(worktree_path) {
+    Ok(bad) if bad.is_empty() => return None,
+    Ok(bad) => bad,
+    Err(e) => return Some(TaskOutcome::Failed(format!("commit lint failed: {e}"))),
+  };
+
+  info!("{} commit subject(s) violate commit_pattern", bad.len());
+  let start = Instant::now();
+  let fix = commit_message::fix_subjects(intent, config, claude, worktree_path, pattern, &bad);
+  step_results.push(StepResult {
+    step: "commit_lint".into(),
+    status: StepStatus::of(&fix),
+    duration_secs: start.elapsed().as_secs(),
+    model: Some(model::resolve(&config.models.implement).into()),
+    metadata: fix.as_ref().ok().map(|(_, m)| m.clone()),
+  });
+  let fixes = match fix {
+    Ok((result, _)) => result.subjects,
+    Err(e) => return Some(TaskOutcome::Failed(format!("commit lint failed: {e}"))),
+  };
+
+  let rewrite = git::branch::rewrite_subjects(
+    worktree_path,
+    &config.base_branch,
+    config.sign_commits,
+    |subject| {
+      fixes
+        .iter()
+        .find(|f| f.original == subject)
+        .map(|f| f.fixed.clone())
+    },
+  );
+  match rewrite.and_then(|_| violations(worktree_path)) {
+    Ok(bad) if bad.is_empty() => None,
+    Ok(bad) => Some(TaskOutcome::Failed(format!(
+      "commit subjects do not match commit_pattern after fix pass: {}",
+      bad.join(", ")
+    ))),
+    Err(e) => Some(TaskOutcome::Failed(format!("commit lint failed: {e}"))),
+  }
+}
+
+/// Verify the commits on the branch match `git_author` / `git_email` / `sign_commits`,
+/// and re-commit them with the configured identity if the worker used another one.
+fn run_identity_step(config: &Config, worktree_path: &Path) -> Option<TaskOutcome> {
+  if config.git_author.is_none() && config.git_email.is_none() && !config.sign_commits {
+    return None;
+  }
+  let mismatched = match git::branch::mismatched_commits(
+    worktree_path,
+    &config.base_branch,
+    config.git_author.as_deref(),
+    config.git_email.as_deref(),
+    config.sign_commits,
+  ) {
+    Ok(m) => m,
+    Err(e) => return Some(TaskOutcome::Failed(format!("identity check failed: {e}"))),
+  };
+  if mismatched.is_empty() {
+    return None;
+  }
+
+  warn!(
+    "{} commit(s) with unexpected identity, rewriting authorship",
+    mismatched.len()
+  );
+  match git::branch::reset_authorship(worktree_path, &config.base_branch) {
+    Ok(()) => None,
+    Err(e) => Some(TaskOutcome::Failed(format!("identity fix failed: {e}"))),
+  }
+}
+
+/// Run the formatter and commit whatever it changed as a separate commit.
+/// Formatter failures are logged only; lint and review still see the code.
+fn run_format_step(worktree_path: &Path, command: &str, step_results: &mut Vec<StepResult>) {
+  let start = Instant::now();
+  let result = check::format(worktree_path, command);
+  match &result {
+    Ok(()) => match git::branch::commit_all(worktree_path, "style: apply format_command") {
+      Ok(true) => info!("format: committed formatting changes"),
+      Ok(false) => {}
+      Err(e) => warn!("format: failed to commit: {e}"),
+    },
+    Err(e) => warn!("{e}"),
+  }
+  step_results.push(StepResult {
+    step: "format".into(),
+    status: StepStatus::of(&result),
+    duration_secs: start.elapsed().as_secs(),
+    model: None,
+    metadata: None,
+  });
+}
+
+/// Run a single check in the worktree. On failure, give the Implement Agent one
+/// corrective pass with the check's feedback, then run the check again.
+/// Returns `None` when the check passes, or the outcome to fail the task with.
+#[allow(clippy::too_many_arguments)]
+fn run_check_step(
+  intent: &mut Intent,
+  task: &Task,
+  config: &Config,
+  claude: &impl Claude,
+  repo_path: &Path,
+  worktree_path: &Path,
+  selected_model: &str,
+  timeout: std::time::Duration,
+  step_results: &mut Vec<StepResult>,
+  step: &str,
+  check: impl Fn() -> Result<CheckOutcome>,
+) -> Option<TaskOutcome> {
+  let span = info_span!("stage", stage = step);
+  let _span = span.enter();
+  let run_check = |step_results: &mut Vec<StepResult>| {
+    let start = Instant::now();
+    let result = check();
+    step_results.push(StepResult {
+      step: step.into(),
+      status: if matches!(result, Ok(CheckOutcome::Passed)) {
+        StepStatus::Success
+      } else {
+        StepStatus::Failed
+      },
+      duration_secs: start.elapsed().as_secs(),
+      model: None,
+      metadata: None,
+    });
+    result
+  };
+
+  let failure = match run_check(step_results) {
+    Ok(CheckOutcome::Passed) => return None,
+    Ok(failure) => failure,
+    Err(e) => return Some(TaskOutcome::Failed(format!("{step} failed: {e}"))),
+  };
+
+  info!("{step} failed for {}, running corrective pass", intent.id());
+  let session = SessionMode::new_session();
+  if let Some(sid) = session.session_id() {
+    intent.sessions.implement = Some(sid.to_string());
+    update_intent_file(repo_path, intent).ok();
+  }
+  let start = Instant::now();
+  let fix_result = implement::run(
+    intent,
+    task,
+    claude,
+    config.prompts.get("implement"),
+    selected_model,
+    worktree_path,
+    Some(timeout),
+    failure.feedback(),
+    &session,
+  );
+  let fix_meta = fix_result.as_ref().ok().map(|raw| parse_metadata(raw));
+  step_results.push(StepResult {
+    step: "implement".into(),
+    status: StepStatus::of(&fix_result),
+    duration_secs: start.elapsed().as_secs(),
+    model: Some(selected_model.into()),
+    metadata: fix_meta,
+  });
+  if let Err(e) = fix_result {
+    return Some(TaskOutcome::Failed(format!("implement failed: {e}")));
+  }
+  commit_sandboxed_changes(config, worktree_path, &task.title);
+
+  match run_check(step_results) {
+    Ok(CheckOutcome::Passed) => None,
+    Ok(_) => {
+      let log_dir = check::log_dir(repo_path, intent.id(), &task.id);
+      let log = check::latest_log(&log_dir, step).unwrap_or(log_dir);
+      let log = log.strip_prefix(repo_path).unwrap_or(&log).to_path_buf();
+      Some(TaskOutcome::Failed(format!(
+        "{step} failed after corrective pass (full output: {})",
+        log.display()
+      )))
+    }
+    Err(e) => Some(TaskOutcome::Failed(format!("{step} failed: {e}"))),
+  }
+}
+
 fn run_audit_report_flow(
   intent: &mut Intent,
   config: &Config,
@@ -857,32 +2210,46 @@ fn run_audit_report_flow(
   let audit_meta = audit_result.as_ref().ok().map(|(_, m)| m.clone());
   step_results.push(StepResult {
     step: "audit".into(),
+    status: StepStatus::of(&audit_result),
     duration_secs: start.elapsed().as_secs(),
+    model: Some(model::resolve(&config.models.audit).into()),
     metadata: audit_meta,
   });
 
   let (outcome, failure_reason) = match audit_result {
     Ok((result, _meta)) => {
-      // Report: read observations and output summary
+      // Report: write the findings document (no code changes)
       let start = Instant::now();
-      let obs_path = repo_path.join(".forge").join("observations.yaml");
-      let observations = crate::knowledge::observation::load(&obs_path).unwrap_or_default();
-      info!("report: {} observations", observations.len());
-      for obs in &observations {
-        info!("  - {}", obs.content);
-      }
+      let written =
+        crate::knowledge::audit_report::write(repo_path, intent, &result, config.locale);
       step_results.push(StepResult {
         step: "report".into(),
+        status: StepStatus::of(&written),
         duration_secs: start.elapsed().as_secs(),
+        model: None,
         metadata: None,
       });
 
-      intent.status = IntentStatus::Done;
-      info!(
-        "audit complete: {} observations found",
-        result.observations.len()
-      );
-      (Outcome::Success, None)
+      // The report is the audit's only deliverable
+      match written {
+        Ok(path) => {
+          info!("report: {}", path.display());
+          intent.status = IntentStatus::Done;
+          info!(
+            "audit complete: {} observations found",
+            result.observations.len()
+          );
+          (Outcome::Success, None)
+        }
+        Err(e) => {
+          warn!("failed to write audit report: {e}");
+          intent.status = IntentStatus::Error;
+          (
+            Outcome::Failed,
+            Some(format!("audit report write failed: {e}")),
+          )
+        }
+      }
     }
     Err(e) => {
       intent.status = IntentStatus::Error;
@@ -903,6 +2270,7 @@ fn run_audit_report_flow(
     failure_reason: failure_reason.clone(),
     observations: vec![],
     created_at: Some(chrono::Utc::now().to_rfc3339()),
+    run_id: intent.run_id.clone(),
   };
   if let Err(e) = history::write(repo_path, &entry) {
     warn!("failed to write history: {e}");
@@ -931,7 +2299,9 @@ fn run_skill_extraction_flow(
   let observe_meta = observe_result.as_ref().ok().map(|(_, m)| m.clone());
   step_results.push(StepResult {
     step: "observe".into(),
+    status: StepStatus::of(&observe_result),
     duration_secs: start.elapsed().as_secs(),
+    model: Some(model::resolve(&config.models.skill).into()),
     metadata: observe_meta,
   });
 
@@ -949,7 +2319,9 @@ fn run_skill_extraction_flow(
         let abstract_meta = abstract_result.as_ref().ok().map(|(_, m)| m.clone());
         step_results.push(StepResult {
           step: "abstract".into(),
+          status: StepStatus::of(&abstract_result),
           duration_secs: start.elapsed().as_secs(),
+          model: Some(model::resolve(&config.models.skill).into()),
           metadata: abstract_meta,
         });
 
@@ -960,7 +2332,9 @@ fn run_skill_extraction_flow(
             let record_result = skill::record(repo_path, &abstract_out.skills);
             step_results.push(StepResult {
               step: "record".into(),
+              status: StepStatus::of(&record_result),
               duration_secs: start.elapsed().as_secs(),
+              model: None,
               metadata: None,
             });
 
@@ -1002,6 +2376,7 @@ fn run_skill_extraction_flow(
     failure_reason: failure_reason.clone(),
     observations: vec![],
     created_at: Some(chrono::Utc::now().to_rfc3339()),
+    run_id: intent.run_id.clone(),
   };
   if let Err(e) = history::write(repo_path, &entry) {
     warn!("failed to write history: {e}");
@@ -1015,23 +2390,83 @@ fn run_skill_extraction_flow(
   })
 }
 
-fn run_worktree_setup(worktree_path: &Path, commands: &[String]) -> Result<()> {
-  for cmd in commands {
-    info!("worktree setup: {cmd}");
-    let output = std::process::Command::new("sh")
-      .args(["-c", cmd])
-      .current_dir(worktree_path)
-      .output()?;
-    if !output.status.success() {
-      let stderr = String::from_utf8_lossy(&output.stderr);
-      return Err(crate::error::ForgeError::Git(format!(
-        "worktree setup command failed: {cmd}: {stderr}"
-      )));
+/// Directories to check out in the worktree: `sparse_paths`, plus the parent
+/// directories of the tasks' relevant files when `sparse_from_relevant_files` is set.
+/// Empty means a full checkout.
+fn sparse_paths(config: &Config, tasks: &[Task]) -> Vec<String> {
+  let mut paths = config.sparse_paths.clone();
+  if config.sparse_from_relevant_files {
+    let dirs = tasks
+      .iter()
+      .flat_map(|t| &t.relevant_files)
+      .filter_map(|f| Path::new(f).parent())
+      .filter(|d| !d.as_os_str().is_empty())
+      .map(|d| d.to_string_lossy().into_owned());
+    for dir in dirs {
+      if !paths.contains(&dir) {
+        paths.push(dir);
+      }
     }
   }
+  paths
+}
+
+/// Bring a fresh worktree to a buildable state before `worktree_setup`:
+/// submodules and Git LFS objects, when configured.
+fn prepare_worktree(config: &Config, worktree_path: &Path) -> Result<()> {
+  if config.init_submodules {
+    git::worktree::init_submodules(worktree_path)?;
+  }
+  if config.git_lfs && git::worktree::uses_lfs(worktree_path) {
+    git::worktree::pull_lfs(worktree_path)?;
+  }
   Ok(())
 }
 
+/// Mark the intent `setup_failure` and drop the worktree, so the next run
+/// recreates it from the saved tasks and runs setup again.
+fn fail_setup(
+  intent: &mut Intent,
+  repo_path: &Path,
+  worktree_path: &Path,
+  flow_names: Vec<String>,
+  step_results: Vec<StepResult>,
+  error: ForgeError,
+) -> Result<IntentResult> {
+  warn!("{}: {error}", intent.id());
+  if let Err(e) = git::worktree::remove(repo_path, worktree_path) {
+    warn!("failed to remove worktree: {e}");
+  }
+  let _ = git::branch::delete(repo_path, &intent.branch_name());
+  intent.status = IntentStatus::SetupFailure;
+  update_intent_file(repo_path, intent)?;
+
+  let failure_reason = Some(error.to_string());
+  history::write(
+    repo_path,
+    &HistoryEntry {
+      intent_id: intent.id().to_string(),
+      intent_type: intent.intent_type.clone(),
+      intent_risk: intent.risk.clone(),
+      title: intent.title.clone(),
+      flow: flow_names.clone(),
+      step_results: step_results.clone(),
+      outcome: Outcome::Failed,
+      failure_reason: failure_reason.clone(),
+      observations: vec![],
+      created_at: Some(chrono::Utc::now().to_rfc3339()),
+      run_id: intent.run_id.clone(),
+    },
+  )?;
+
+  Ok(IntentResult {
+    flow: flow_names,
+    step_results,
+    outcome: Outcome::Failed,
+    failure_reason,
+  })
+}
+
 fn has_children(repo_path: &Path, intent_id: &str) -> bool {
   let intents_dir = repo_path.join(".forge").join("intents");
   Intent::fetch_all(&intents_dir)
@@ -1051,17 +2486,42 @@ pub fn slugify(s: &str) -> String {
     .join("-")
 }
 
+/// `pfl-forge approve`: approve the intent and reset its tasks' retry
+/// counters, so a task that ran out of retries is attempted again.
+pub fn approve_intent(repo_path: &Path, intent: &mut Intent) -> Result<()> {
+  intent.approve();
+  task::reset_retries(repo_path, intent.id())?;
+  update_intent_file(repo_path, intent)
+}
+
 pub fn update_intent_file(repo_path: &Path, intent: &Intent) -> Result<()> {
   let intents_dir = repo_path.join(".forge").join("intents");
   let path = intents_dir.join(format!("{}.yaml", intent.id()));
   if !path.exists() {
     return Ok(());
   }
+  let previous = webhook::enabled().then(|| stored_status(&path)).flatten();
   let content = serde_yaml::to_string(intent)?;
   std::fs::write(&path, content)?;
+  if let Some(from) = previous.filter(|from| *from != intent.status) {
+    webhook::status_changed(repo_path, intent, &from);
+  }
   Ok(())
 }
 
+/// Status currently on disk for an intent file.
+fn stored_status(path: &Path) -> Option<IntentStatus> {
+  #[derive(serde::Deserialize)]
+  struct Stored {
+    #[serde(default)]
+    status: IntentStatus,
+  }
+  let content = std::fs::read_to_string(path).ok()?;
+  serde_yaml::from_str::<Stored>(&content)
+    .ok()
+    .map(|s| s.status)
+}
+
 fn gather_active_intents(repo_path: &Path, current_id: &str) -> Vec<ActiveIntentContext> {
   let intents_dir = repo_path.join(".forge").join("intents");
   let intents = Intent::fetch_all(&intents_dir).unwrap_or_default();
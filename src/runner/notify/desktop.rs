@@ -0,0 +1,52 @@
+//! Desktop notifications (`desktop_notifications`) for people running forge on
+//! their workstation: `notify-send` on Linux, `osascript` on macOS.
+
+use std::process::Command;
+
+use tracing::warn;
+
+pub fn send(title: &str, body: &str) {
+  // Not a worker process: it needs the desktop session's environment (e.g.
+  // DBUS_SESSION_BUS_ADDRESS), so `worker_env` does not apply
+  let mut cmd = if cfg!(target_os = "macos") {
+    let mut cmd = Command::new("osascript");
+    cmd.args([
+      "-e",
+      &format!(
+        "display notification {} with title {}",
+        applescript_string(body),
+        applescript_string(title)
+      ),
+    ]);
+    cmd
+  } else {
+    let mut cmd = Command::new("notify-send");
+    cmd.args(["--app-name=pfl-forge", title, body]);
+    cmd
+  };
+  match cmd.output() {
+    Ok(output) if output.status.success() => {}
+    Ok(output) => warn!(
+      "desktop notification failed: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ),
+    Err(e) => warn!("desktop notification failed: {e}"),
+  }
+}
+
+fn applescript_string(text: &str) -> String {
+  format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn applescriptの文字列をエスケープする() {
+    assert_eq!(
+      applescript_string(r#"say "hi" \ bye"#),
+      r#""say \"hi\" \\ bye""#
+    );
+  }
+}
@@ -0,0 +1,74 @@
+//! Discord webhooks (`notifications.discord`), posted with `curl` like the
+//! email digest.
+
+use std::io::Write;
+use std::process::Stdio;
+
+use tracing::warn;
+
+use crate::process;
+
+/// Discord rejects messages longer than this.
+const MAX_CONTENT_CHARS: usize = 2000;
+
+pub fn send(url: &str, title: &str, body: &str) {
+  let payload = serde_json::json!({
+    "username": "pfl-forge",
+    "content": content(title, body),
+  });
+  if let Err(e) = post(url, &payload.to_string()) {
+    warn!("discord notification failed: {e}");
+  }
+}
+
+fn post(url: &str, payload: &str) -> std::io::Result<()> {
+  // The URL carries the webhook token, so it goes through stdin (`-K -`)
+  let mut child = process::command("curl")
+    .args(["--silent", "--show-error", "--fail", "-K", "-"])
+    .args(["--header", "Content-Type: application/json"])
+    .args(["--data-binary", payload])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(super::curl_config_line("url", url).as_bytes())?;
+  }
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(std::io::Error::other(format!(
+      "curl failed: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    )));
+  }
+  Ok(())
+}
+
+/// Bold title and body, cut to Discord's length limit.
+fn content(title: &str, body: &str) -> String {
+  let text = if body.is_empty() {
+    format!("**{title}**")
+  } else {
+    format!("**{title}**\n{body}")
+  };
+  if text.chars().count() <= MAX_CONTENT_CHARS {
+    return text;
+  }
+  let mut cut: String = text.chars().take(MAX_CONTENT_CHARS - 1).collect();
+  cut.push('…');
+  cut
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 長い本文はdiscordの上限で切り詰める() {
+    assert_eq!(content("done", ""), "**done**");
+    let long = content("done", &"a".repeat(3000));
+    assert_eq!(long.chars().count(), MAX_CONTENT_CHARS);
+    assert!(long.starts_with("**done**\na"));
+    assert!(long.ends_with('…'));
+  }
+}
@@ -0,0 +1,157 @@
+//! Notification dispatch. The runner reports an [`Event`] once and each
+//! configured channel decides whether and where to deliver it: the desktop
+//! (`desktop_notifications`) and Discord webhooks (`notifications.discord`).
+//! Delivery is best effort; failures only log a warning.
+
+mod desktop;
+mod discord;
+
+use crate::config::Config;
+use crate::intent::registry::Intent;
+use crate::knowledge::history::Outcome;
+use crate::knowledge::run_report::IntentReport;
+
+/// Named by [`Event::name`], one of `config::NOTIFY_EVENTS`.
+pub enum Event<'a> {
+  RunFinished {
+    run_id: &'a str,
+    intents: &'a [IntentReport],
+  },
+  /// One intent's outcome in a finished run.
+  IntentFinished {
+    report: &'a IntentReport,
+    branch: String,
+  },
+  /// Analyze asked questions and the intent is blocked until answered.
+  Clarification {
+    intent: &'a Intent,
+    questions: &'a [String],
+  },
+}
+
+impl Event<'_> {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Event::RunFinished { .. } => "run_finished",
+      Event::IntentFinished { report, .. } => match report.outcome {
+        Outcome::Success => "succeeded",
+        Outcome::Failed => "failed",
+        Outcome::Escalated => "escalated",
+      },
+      Event::Clarification { .. } => "clarification",
+    }
+  }
+
+  pub fn title(&self) -> String {
+    match self {
+      Event::RunFinished { run_id, .. } => format!("pfl-forge run {run_id} finished"),
+      Event::IntentFinished { report, .. } => {
+        format!("{} {}: {}", report.intent_id, self.name(), report.title)
+      }
+      Event::Clarification { intent, .. } => format!("{} needs clarification", intent.id()),
+    }
+  }
+
+  pub fn body(&self) -> String {
+    match self {
+      Event::RunFinished { intents, .. } => run_summary(intents.iter().map(|i| &i.outcome)),
+      Event::IntentFinished { report, branch } => match (&report.outcome, &report.failure_reason) {
+        (Outcome::Success, _) => format!("branch: {branch}"),
+        (_, Some(reason)) => reason.clone(),
+        (_, None) => String::new(),
+      },
+      Event::Clarification { intent, questions } => format!(
+        "{}\nanswer with: pfl-forge answer {}",
+        questions.join("\n"),
+        intent.id()
+      ),
+    }
+  }
+}
+
+/// Deliver `event` to every channel configured for it.
+pub fn dispatch(config: &Config, event: &Event) {
+  // Per-intent toasts would be noise next to the run summary
+  let for_desktop = !matches!(event, Event::IntentFinished { .. });
+  if config.desktop_notifications && for_desktop {
+    desktop::send(&event.title(), &event.body());
+  }
+  if let Some(discord) = &config.notifications.discord {
+    if let Some(url) = discord.webhook_for(event.name()) {
+      discord::send(url, &event.title(), &event.body());
+    }
+  }
+}
+
+/// "3 succeeded, 1 failed, 1 escalated" for run summaries.
+pub fn run_summary<'a>(outcomes: impl IntoIterator<Item = &'a Outcome>) -> String {
+  let outcomes: Vec<&Outcome> = outcomes.into_iter().collect();
+  let count = |o: Outcome| outcomes.iter().filter(|&&r| *r == o).count();
+  let mut parts = vec![
+    format!("{} succeeded", count(Outcome::Success)),
+    format!("{} failed", count(Outcome::Failed)),
+  ];
+  let escalated = count(Outcome::Escalated);
+  if escalated > 0 {
+    parts.push(format!("{escalated} escalated"));
+  }
+  parts.join(", ")
+}
+
+/// One `name = "value"` line of a curl config file (`curl -K`), which keeps
+/// secrets out of the process list.
+pub fn curl_config_line(name: &str, value: &str) -> String {
+  format!(
+    "{name} = \"{}\"\n",
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 実行結果を件数でまとめる() {
+    let outcomes = [Outcome::Success, Outcome::Failed, Outcome::Success];
+    assert_eq!(run_summary(&outcomes), "2 succeeded, 1 failed");
+  }
+
+  #[test]
+  fn intentの結果ごとにイベント名と本文を変える() {
+    let report = IntentReport {
+      intent_id: "fix-login".into(),
+      title: "Fix login".into(),
+      outcome: Outcome::Failed,
+      failure_reason: Some("tests failed".into()),
+      cost_usd: 0.0,
+      next_action: None,
+    };
+    let failed = Event::IntentFinished {
+      report: &report,
+      branch: "forge/fix-login".into(),
+    };
+    assert_eq!(failed.name(), "failed");
+    assert_eq!(failed.title(), "fix-login failed: Fix login");
+    assert_eq!(failed.body(), "tests failed");
+
+    let report = IntentReport {
+      outcome: Outcome::Success,
+      ..report
+    };
+    let succeeded = Event::IntentFinished {
+      report: &report,
+      branch: "forge/fix-login".into(),
+    };
+    assert_eq!(succeeded.name(), "succeeded");
+    assert_eq!(succeeded.body(), "branch: forge/fix-login");
+  }
+
+  #[test]
+  fn curl設定の値をエスケープする() {
+    assert_eq!(
+      curl_config_line("user", r#"me:p"a\ss"#),
+      "user = \"me:p\\\"a\\\\ss\"\n"
+    );
+  }
+}
@@ -0,0 +1,246 @@
+//! Live per-intent progress for `run --progress`.
+//!
+//! A tracing layer watches the `intent` and `stage` spans the runner already
+//! opens and keeps one spinner line per in-flight intent: its elapsed time, the
+//! current stage and the latest log message inside it. The claude CLI is run
+//! with `--output-format json`, so tool calls are not visible before a stage
+//! finishes; the last message is the closest live signal.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+use tracing_subscriber::Layer;
+
+pub struct ProgressView {
+  multi: Arc<MultiProgress>,
+  header: ProgressBar,
+  drawer: Mutex<Option<JoinHandle<()>>>,
+  running: AtomicUsize,
+  finished: AtomicUsize,
+}
+
+impl ProgressView {
+  /// Start drawing to stderr (hidden when stderr is not a terminal).
+  pub fn start() -> Arc<Self> {
+    let multi = Arc::new(MultiProgress::new());
+    // MultiProgress stops drawing once every bar is finished, so the header
+    // stays open for the whole run
+    let header = multi.add(ProgressBar::new_spinner());
+    header.set_style(spinner_style("{spinner} {elapsed:>4} {wide_msg}"));
+    header.set_message("starting");
+    header.enable_steady_tick(200);
+    let drawer = {
+      let multi = multi.clone();
+      std::thread::spawn(move || {
+        let _ = multi.join_and_clear();
+      })
+    };
+    Arc::new(Self {
+      multi,
+      header,
+      drawer: Mutex::new(Some(drawer)),
+      running: AtomicUsize::new(0),
+      finished: AtomicUsize::new(0),
+    })
+  }
+
+  /// A tracing layer feeding this view.
+  pub fn layer(self: &Arc<Self>) -> ProgressLayer {
+    ProgressLayer(self.clone())
+  }
+
+  /// Clear the display and wait for the drawing thread.
+  pub fn finish(&self) {
+    self.header.finish_and_clear();
+    if let Some(drawer) = self.drawer.lock().unwrap().take() {
+      let _ = drawer.join();
+    }
+  }
+
+  fn add(&self, intent: &str) -> ProgressBar {
+    let bar = self.multi.add(ProgressBar::new_spinner());
+    bar.set_style(spinner_style(
+      "{spinner} {prefix:24!} {elapsed:>4} {wide_msg}",
+    ));
+    bar.set_prefix(intent);
+    bar.set_message("starting");
+    bar.enable_steady_tick(200);
+    self.running.fetch_add(1, Ordering::Relaxed);
+    self.update_header();
+    bar
+  }
+
+  fn done(&self, bar: &ProgressBar) {
+    bar.finish_and_clear();
+    self.running.fetch_sub(1, Ordering::Relaxed);
+    self.finished.fetch_add(1, Ordering::Relaxed);
+    self.update_header();
+  }
+
+  fn update_header(&self) {
+    self.header.set_message(&format!(
+      "{} running, {} finished",
+      self.running.load(Ordering::Relaxed),
+      self.finished.load(Ordering::Relaxed)
+    ));
+  }
+}
+
+fn spinner_style(template: &str) -> ProgressStyle {
+  ProgressStyle::default_spinner().template(template)
+}
+
+/// The bar of one `intent` span and the stage it is in.
+struct IntentLine {
+  bar: ProgressBar,
+  stage: Mutex<Option<String>>,
+}
+
+impl IntentLine {
+  fn show(&self, message: &str) {
+    match self.stage.lock().unwrap().as_deref() {
+      Some(stage) => self.bar.set_message(&format!("{stage}: {message}")),
+      None => self.bar.set_message(message),
+    }
+  }
+}
+
+pub struct ProgressLayer(Arc<ProgressView>);
+
+impl<S> Layer<S> for ProgressLayer
+where
+  S: Subscriber + for<'a> LookupSpan<'a>,
+{
+  fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(id) else {
+      return;
+    };
+    match attrs.metadata().name() {
+      "intent" => {
+        let intent = field(attrs, "intent").unwrap_or_default();
+        let line = Arc::new(IntentLine {
+          bar: self.0.add(&intent),
+          stage: Mutex::new(None),
+        });
+        span.extensions_mut().insert(line);
+      }
+      "stage" => {
+        let Some(line) = intent_line(span.scope().skip(1)) else {
+          return;
+        };
+        *line.stage.lock().unwrap() = field(attrs, "stage");
+        line.show("started");
+      }
+      _ => {}
+    }
+  }
+
+  fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+    if *event.metadata().level() > Level::INFO {
+      return;
+    }
+    let Some(line) = ctx.event_scope(event).and_then(intent_line) else {
+      return;
+    };
+    let mut visitor = FieldVisitor::new("message");
+    event.record(&mut visitor);
+    if let Some(message) = visitor.value {
+      line.show(&message);
+    }
+  }
+
+  fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+    let Some(span) = ctx.span(&id) else {
+      return;
+    };
+    match span.name() {
+      "intent" => {
+        if let Some(line) = span.extensions().get::<Arc<IntentLine>>() {
+          self.0.done(&line.bar);
+        }
+      }
+      "stage" => {
+        if let Some(line) = intent_line(span.scope().skip(1)) {
+          *line.stage.lock().unwrap() = None;
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// The innermost enclosing `intent` span's line.
+fn intent_line<'a, S>(mut spans: impl Iterator<Item = SpanRef<'a, S>>) -> Option<Arc<IntentLine>>
+where
+  S: Subscriber + for<'b> LookupSpan<'b>,
+{
+  spans.find_map(|span| span.extensions().get::<Arc<IntentLine>>().cloned())
+}
+
+fn field(attrs: &Attributes<'_>, name: &'static str) -> Option<String> {
+  let mut visitor = FieldVisitor::new(name);
+  attrs.record(&mut visitor);
+  visitor.value
+}
+
+struct FieldVisitor {
+  name: &'static str,
+  value: Option<String>,
+}
+
+impl FieldVisitor {
+  fn new(name: &'static str) -> Self {
+    Self { name, value: None }
+  }
+}
+
+impl Visit for FieldVisitor {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    if field.name() == self.name {
+      self.value = Some(value.to_string());
+    }
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    if field.name() == self.name {
+      self.value = Some(format!("{value:?}"));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tracing_subscriber::layer::SubscriberExt;
+
+  use super::*;
+
+  #[test]
+  fn intent_spanごとに行を追加し閉じたら完了数に数える() {
+    let view = ProgressView::start();
+    let subscriber = tracing_subscriber::registry().with(view.layer());
+
+    tracing::subscriber::with_default(subscriber, || {
+      let intent = tracing::info_span!("intent", intent = "fix-login");
+      let _intent = intent.enter();
+      {
+        let stage = tracing::info_span!("stage", stage = "implement");
+        let _stage = stage.enter();
+        tracing::info!("running claude");
+        assert_eq!(view.running.load(Ordering::Relaxed), 1);
+      }
+      assert_eq!(view.finished.load(Ordering::Relaxed), 0);
+    });
+
+    assert_eq!(view.running.load(Ordering::Relaxed), 0);
+    assert_eq!(view.finished.load(Ordering::Relaxed), 1);
+    view.finish();
+  }
+}
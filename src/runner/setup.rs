@@ -0,0 +1,215 @@
+//! `worktree_setup` with retries and an artifact cache.
+//!
+//! Setup commands (`npm install`, ...) fail for reasons unrelated to the task,
+//! such as a network blip, so they are retried on their own without touching
+//! the Claude budget. With `worktree_setup_cache`, the listed artifacts are
+//! copied to `.forge/setup-cache/<key>/` after a successful setup and copied
+//! back into later worktrees instead of running the commands again. The key
+//! hashes the commands and the `key_files` (lockfiles) in the worktree.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::error::{ForgeError, Result};
+use crate::process;
+
+/// Run `worktree_setup` in the worktree, restoring from the cache when possible.
+pub fn run(config: &Config, repo_path: &Path, worktree_path: &Path) -> Result<()> {
+  if config.worktree_setup.is_empty() {
+    return Ok(());
+  }
+  let cache_dir = config
+    .worktree_setup_cache
+    .as_ref()
+    .map(|_| cache_root(repo_path).join(cache_key(config, worktree_path)));
+
+  if let (Some(cache), Some(dir)) = (&config.worktree_setup_cache, &cache_dir) {
+    if dir.is_dir() {
+      match restore(dir, worktree_path, &cache.paths) {
+        Ok(()) => {
+          info!("worktree setup: restored from cache {}", dir.display());
+          return Ok(());
+        }
+        Err(e) => warn!("worktree setup: cache restore failed, running setup: {e}"),
+      }
+    }
+  }
+
+  run_with_retries(config, worktree_path)?;
+
+  if let (Some(cache), Some(dir)) = (&config.worktree_setup_cache, &cache_dir) {
+    if let Err(e) = save(worktree_path, dir, &cache.paths) {
+      warn!("worktree setup: failed to cache artifacts: {e}");
+    }
+  }
+  Ok(())
+}
+
+fn run_with_retries(config: &Config, worktree_path: &Path) -> Result<()> {
+  let mut attempt = 0;
+  loop {
+    match run_commands(worktree_path, &config.worktree_setup) {
+      Ok(()) => return Ok(()),
+      Err(e) if attempt < config.worktree_setup_retries => {
+        attempt += 1;
+        warn!(
+          "{e}; retrying ({attempt}/{})",
+          config.worktree_setup_retries
+        );
+        std::thread::sleep(Duration::from_secs(config.worktree_setup_retry_secs));
+      }
+      Err(e) => return Err(e),
+    }
+  }
+}
+
+fn run_commands(worktree_path: &Path, commands: &[String]) -> Result<()> {
+  for cmd in commands {
+    info!("worktree setup: {cmd}");
+    let output = process::command("sh")
+      .args(["-c", cmd])
+      .current_dir(worktree_path)
+      .output()?;
+    if !output.status.success() {
+      let stderr = String::from_utf8_lossy(&output.stderr);
+      return Err(ForgeError::Git(format!(
+        "worktree setup command failed: {cmd}: {}",
+        stderr.trim_end()
+      )));
+    }
+  }
+  Ok(())
+}
+
+fn cache_root(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("setup-cache")
+}
+
+/// FNV-1a over the commands and key files, so the key is stable across builds.
+fn cache_key(config: &Config, worktree_path: &Path) -> String {
+  let mut hash: u64 = 0xcbf29ce484222325;
+  let mut feed = |bytes: &[u8]| {
+    for b in bytes {
+      hash ^= u64::from(*b);
+      hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= 0xff;
+  };
+  for cmd in &config.worktree_setup {
+    feed(cmd.as_bytes());
+  }
+  let key_files = config
+    .worktree_setup_cache
+    .as_ref()
+    .map(|c| c.key_files.as_slice())
+    .unwrap_or_default();
+  for file in key_files {
+    feed(file.as_bytes());
+    feed(&std::fs::read(worktree_path.join(file)).unwrap_or_default());
+  }
+  format!("{hash:016x}")
+}
+
+fn restore(cache_dir: &Path, worktree_path: &Path, paths: &[String]) -> Result<()> {
+  for path in paths {
+    let cached = cache_dir.join(path);
+    if cached.exists() {
+      copy(&cached, &worktree_path.join(path))?;
+    }
+  }
+  Ok(())
+}
+
+/// Copy the artifacts into a staging directory of this call's own first, so a failed
+/// copy never leaves a partial cache entry behind and parallel workers never share
+/// one. When another worker has already stored the same key, its entry is kept.
+fn save(worktree_path: &Path, cache_dir: &Path, paths: &[String]) -> Result<()> {
+  let key = cache_dir
+    .file_name()
+    .map(|n| n.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  let staging = cache_dir.with_file_name(format!("{key}.tmp-{}", uuid::Uuid::new_v4().simple()));
+  let result = stage(worktree_path, &staging, paths).and_then(|()| {
+    match std::fs::rename(&staging, cache_dir) {
+      Ok(()) => Ok(()),
+      // Lost the race: the other worker's entry is just as good
+      Err(_) if cache_dir.is_dir() => {
+        info!(
+          "worktree setup: artifacts already cached in {}",
+          cache_dir.display()
+        );
+        let _ = std::fs::remove_dir_all(&staging);
+        Ok(())
+      }
+      Err(e) => Err(e.into()),
+    }
+  });
+  if result.is_err() {
+    let _ = std::fs::remove_dir_all(&staging);
+  }
+  result?;
+  info!(
+    "worktree setup: cached artifacts in {}",
+    cache_dir.display()
+  );
+  Ok(())
+}
+
+fn stage(worktree_path: &Path, staging: &Path, paths: &[String]) -> Result<()> {
+  std::fs::create_dir_all(staging)?;
+  for path in paths {
+    let src = worktree_path.join(path);
+    if src.exists() {
+      copy(&src, &staging.join(path))?;
+    }
+  }
+  Ok(())
+}
+
+fn copy(src: &Path, dst: &Path) -> Result<()> {
+  if let Some(parent) = dst.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  let output = process::command("cp")
+    .arg("-a")
+    .arg(src)
+    .arg(dst)
+    .output()?;
+  if !output.status.success() {
+    return Err(ForgeError::Git(format!(
+      "failed to copy {}: {}",
+      src.display(),
+      String::from_utf8_lossy(&output.stderr).trim_end()
+    )));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 同じキーがすでにキャッシュ済みなら保存済みとして扱う() {
+    let dir = tempfile::tempdir().unwrap();
+    let worktree = dir.path().join("wt");
+    std::fs::create_dir_all(worktree.join("node_modules")).unwrap();
+    std::fs::write(worktree.join("node_modules").join("dep.js"), "new").unwrap();
+    let cache_dir = dir.path().join("cache").join("0123456789abcdef");
+    std::fs::create_dir_all(cache_dir.join("node_modules")).unwrap();
+    std::fs::write(cache_dir.join("node_modules").join("dep.js"), "first").unwrap();
+
+    save(&worktree, &cache_dir, &["node_modules".into()]).unwrap();
+
+    let cached = std::fs::read_to_string(cache_dir.join("node_modules").join("dep.js")).unwrap();
+    assert_eq!(cached, "first");
+    // No staging directory is left behind
+    let entries: Vec<_> = std::fs::read_dir(dir.path().join("cache"))
+      .unwrap()
+      .collect();
+    assert_eq!(entries.len(), 1);
+  }
+}
@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
+
+use super::cron::CronSchedule;
+use crate::config::Config;
+use crate::error::{ForgeError, Result};
+
+/// Fraction of the delay added or removed at random, so several forge instances
+/// polling the same repo drift apart instead of waking together.
+const JITTER: f64 = 0.1;
+
+/// Delay between watch polls. Starts at `poll_interval_secs`, doubles for every
+/// consecutive poll that processed nothing (or failed), capped at `max`.
+#[derive(Debug, Clone)]
+pub struct PollSchedule {
+  base: Duration,
+  max: Duration,
+  idle_streak: u32,
+}
+
+impl PollSchedule {
+  pub fn new(base: Duration, max: Duration) -> Self {
+    Self {
+      base,
+      max: max.max(base),
+      idle_streak: 0,
+    }
+  }
+
+  /// Record a poll. `busy` is true when at least one intent was processed.
+  pub fn record(&mut self, busy: bool) {
+    if busy {
+      self.idle_streak = 0;
+    } else {
+      self.idle_streak = self.idle_streak.saturating_add(1);
+    }
+  }
+
+  /// Delay before the next poll without jitter.
+  pub fn delay(&self) -> Duration {
+    let factor = 2u32.saturating_pow(self.idle_streak.saturating_sub(1).min(16));
+    let delay = if self.idle_streak == 0 {
+      self.base
+    } else {
+      self.base.saturating_mul(factor)
+    };
+    delay.min(self.max)
+  }
+
+  /// `delay()` scaled by `1 + JITTER * r`, where `r` is in `-1.0..=1.0`.
+  pub fn delay_with_jitter(&self, r: f64) -> Duration {
+    self.delay().mul_f64(1.0 + JITTER * r.clamp(-1.0, 1.0))
+  }
+}
+
+/// A uniformly distributed value in `-1.0..=1.0`.
+pub fn random_unit() -> f64 {
+  let bits = uuid::Uuid::new_v4().as_u128() as u64;
+  (bits as f64 / u64::MAX as f64) * 2.0 - 1.0
+}
+
+fn wake_path(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("watch-wake")
+}
+
+/// Ask a running `watch` to poll now (e.g. after an answer or approval).
+pub fn request_wake(repo_path: &Path) {
+  if let Err(e) = std::fs::write(wake_path(repo_path), "") {
+    warn!("failed to request watch wake-up: {e}");
+  }
+}
+
+fn disabled_path(repo_path: &Path) -> PathBuf {
+  repo_path.join(".forge").join("disabled")
+}
+
+/// Pause or resume processing without editing the config (`pfl-forge
+/// disable` / `enable`). Intents and history are left as they are.
+pub fn set_disabled(repo_path: &Path, disabled: bool) -> Result<()> {
+  let path = disabled_path(repo_path);
+  if disabled {
+    if let Some(dir) = path.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, chrono::Utc::now().to_rfc3339())?;
+  } else if path.exists() {
+    std::fs::remove_file(&path)?;
+    request_wake(repo_path);
+  }
+  Ok(())
+}
+
+/// Whether processing is off, by `enabled: false` or `pfl-forge disable`.
+pub fn is_disabled(config: &Config, repo_path: &Path) -> bool {
+  !config.enabled || disabled_path(repo_path).exists()
+}
+
+/// Sleep for `delay`, returning early when a wake-up was requested.
+/// Returns true if woken early.
+pub fn sleep_or_wake(repo_path: &Path, delay: Duration) -> bool {
+  let path = wake_path(repo_path);
+  let deadline = Instant::now() + delay;
+  loop {
+    if path.exists() {
+      let _ = std::fs::remove_file(&path);
+      debug!("watch: woken up");
+      return true;
+    }
+    let now = Instant::now();
+    if now >= deadline {
+      return false;
+    }
+    std::thread::sleep((deadline - now).min(Duration::from_secs(1)));
+  }
+}
+
+/// Sleep until the next scheduled time (local clock), returning early on a
+/// wake-up request so answered clarifications are still picked up promptly.
+pub fn sleep_until_scheduled(repo_path: &Path, cron: &CronSchedule) -> Result<()> {
+  let now = chrono::Local::now().naive_local();
+  let next = cron
+    .next_after(now)
+    .ok_or_else(|| ForgeError::Config("schedule never matches".into()))?;
+  info!("watch: next poll at {}", next.format("%Y-%m-%d %H:%M"));
+  let delay = (next - now).to_std().unwrap_or_default();
+  sleep_or_wake(repo_path, delay);
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn schedule() -> PollSchedule {
+    PollSchedule::new(Duration::from_secs(60), Duration::from_secs(600))
+  }
+
+  #[test]
+  fn 空振りが続くと間隔を倍にし上限で止める() {
+    let mut s = schedule();
+    assert_eq!(s.delay(), Duration::from_secs(60));
+    s.record(false);
+    assert_eq!(s.delay(), Duration::from_secs(60));
+    s.record(false);
+    assert_eq!(s.delay(), Duration::from_secs(120));
+    s.record(false);
+    assert_eq!(s.delay(), Duration::from_secs(240));
+    for _ in 0..40 {
+      s.record(false);
+    }
+    assert_eq!(s.delay(), Duration::from_secs(600));
+  }
+
+  #[test]
+  fn 処理があれば基本間隔に戻す() {
+    let mut s = schedule();
+    s.record(false);
+    s.record(false);
+    s.record(true);
+    assert_eq!(s.delay(), Duration::from_secs(60));
+  }
+
+  #[test]
+  fn ジッターは前後10パーセント以内() {
+    let s = schedule();
+    assert_eq!(s.delay_with_jitter(1.0), Duration::from_secs(66));
+    assert_eq!(s.delay_with_jitter(-1.0), Duration::from_secs(54));
+    for _ in 0..100 {
+      let r = random_unit();
+      assert!((-1.0..=1.0).contains(&r));
+    }
+  }
+
+  #[test]
+  fn wake要求があれば待たずに戻る() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".forge")).unwrap();
+    request_wake(dir.path());
+
+    let start = Instant::now();
+    assert!(sleep_or_wake(dir.path(), Duration::from_secs(30)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!wake_path(dir.path()).exists());
+  }
+}
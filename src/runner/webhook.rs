@@ -0,0 +1,163 @@
+//! Outbound webhooks (`webhooks`) on intent status transitions, so external
+//! systems can react to forge without polling `.forge/intents/`.
+//!
+//! Every status change written through `update_intent_file` posts a JSON body
+//! to each configured URL, signed with HMAC-SHA256 when the webhook has a
+//! secret. The settings are installed once at startup with [`init`]; until
+//! then nothing is sent.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use ring::hmac;
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use super::notify::curl_config_line;
+use crate::config::WebhookSettings;
+use crate::intent::registry::{Intent, IntentStatus};
+use crate::process;
+
+static WEBHOOKS: OnceLock<Vec<WebhookSettings>> = OnceLock::new();
+
+/// Seconds before a webhook post is abandoned.
+const TIMEOUT_SECS: &str = "10";
+
+/// Install the webhooks for the rest of the process. Later calls are ignored.
+pub fn init(webhooks: &[WebhookSettings]) {
+  let _ = WEBHOOKS.set(webhooks.to_vec());
+}
+
+pub(super) fn enabled() -> bool {
+  WEBHOOKS.get().is_some_and(|w| !w.is_empty())
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusChange<'a> {
+  pub event: &'static str,
+  pub repo: String,
+  pub intent_id: &'a str,
+  pub title: &'a str,
+  pub from: &'a IntentStatus,
+  pub to: &'a IntentStatus,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub run_id: Option<&'a str>,
+  pub timestamp: String,
+}
+
+impl<'a> StatusChange<'a> {
+  pub fn new(repo_path: &Path, intent: &'a Intent, from: &'a IntentStatus) -> Self {
+    Self {
+      event: "intent.status_changed",
+      repo: super::repo_name(repo_path),
+      intent_id: intent.id(),
+      title: &intent.title,
+      from,
+      to: &intent.status,
+      run_id: intent.run_id.as_deref(),
+      timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+  }
+}
+
+/// Post the transition to every webhook. Failures are logged only.
+pub(super) fn status_changed(repo_path: &Path, intent: &Intent, from: &IntentStatus) {
+  let Some(webhooks) = WEBHOOKS.get() else {
+    return;
+  };
+  let body = match serde_json::to_string(&StatusChange::new(repo_path, intent, from)) {
+    Ok(body) => body,
+    Err(e) => {
+      warn!("failed to encode webhook body: {e}");
+      return;
+    }
+  };
+  for webhook in webhooks {
+    if let Err(e) = post(webhook, &body) {
+      warn!("webhook {} failed: {e}", host(&webhook.url));
+    }
+  }
+}
+
+fn post(webhook: &WebhookSettings, body: &str) -> std::io::Result<()> {
+  let mut cmd = process::command("curl");
+  cmd
+    .args([
+      "--silent",
+      "--show-error",
+      "--fail",
+      "--max-time",
+      TIMEOUT_SECS,
+    ])
+    .args(["--header", "Content-Type: application/json"])
+    .args(["--header", "X-Forge-Event: intent.status_changed"])
+    .args(["--data-binary", body]);
+  if let Some(name) = &webhook.secret_env {
+    let secret = std::env::var(name)
+      .map_err(|_| std::io::Error::other(format!("{name} is not set; not sending unsigned")))?;
+    cmd.args([
+      "--header",
+      &format!("X-Forge-Signature: sha256={}", sign(&secret, body)),
+    ]);
+  }
+  // The URL may carry a token, so it goes through stdin (`-K -`)
+  let mut child = cmd
+    .args(["-K", "-"])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(curl_config_line("url", &webhook.url).as_bytes())?;
+  }
+  let output = child.wait_with_output()?;
+  if !output.status.success() {
+    return Err(std::io::Error::other(format!(
+      "curl failed: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    )));
+  }
+  debug!("webhook {} delivered", host(&webhook.url));
+  Ok(())
+}
+
+/// Hex HMAC-SHA256 of `body`, as receivers recompute it to verify the sender.
+pub fn sign(secret: &str, body: &str) -> String {
+  let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+  hmac::sign(&key, body.as_bytes())
+    .as_ref()
+    .iter()
+    .map(|b| format!("{b:02x}"))
+    .collect()
+}
+
+/// The URL without path or query, for logs.
+fn host(url: &str) -> &str {
+  let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+  rest.split(['/', '?']).next().unwrap_or(rest)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn 署名はrfc4231のhmac_sha256と一致する() {
+    // RFC 4231 test case 2
+    assert_eq!(
+      sign("Jefe", "what do ya want for nothing?"),
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+  }
+
+  #[test]
+  fn ログにはurlのホストだけを出す() {
+    assert_eq!(
+      host("https://hooks.example.com/T0/secret?x=1"),
+      "hooks.example.com"
+    );
+    assert_eq!(host("hooks.example.com"), "hooks.example.com");
+  }
+}
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::agent::analyze::TaskSpec;
+use crate::agent::analyze::{CheckOverride, TaskSpec};
 use crate::claude::model;
 use crate::error::Result;
 use crate::intent::registry::Intent;
@@ -14,6 +14,8 @@ pub enum WorkStatus {
   #[default]
   Pending,
   Implementing,
+  /// Implement committed; checks, rebase and review have not finished yet.
+  Implemented,
   Completed,
   Failed,
 }
@@ -32,6 +34,25 @@ pub struct Task {
   pub context: String,
   #[serde(default)]
   pub depends_on: Vec<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub checks: Option<CheckOverride>,
+  /// Implement runs that errored, across runs.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub implement_failures: u32,
+  /// Rebase conflicts hit, across runs.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub rebase_conflicts: u32,
+  /// Errors from failed implement / rebase attempts, oldest first.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub errors: Vec<String>,
+  /// Set once `max_implement_retries` / `max_rebase_retries` is used up; the
+  /// task stays `failed` on resume instead of running again.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub retries_exhausted: bool,
+}
+
+fn is_zero(n: &u32) -> bool {
+  *n == 0
 }
 
 impl Task {
@@ -57,6 +78,11 @@ impl Task {
       implementation_steps: spec.implementation_steps.clone(),
       context: spec.context.clone(),
       depends_on: spec.depends_on.clone(),
+      checks: spec.checks.clone(),
+      implement_failures: 0,
+      rebase_conflicts: 0,
+      errors: Vec::new(),
+      retries_exhausted: false,
     }
   }
 
@@ -96,3 +122,71 @@ pub fn read_all_tasks(repo_path: &Path, intent_id: &str) -> Result<Vec<Task>> {
 pub fn tasks_exist(repo_path: &Path, intent_id: &str) -> bool {
   tasks_file(repo_path, intent_id).exists()
 }
+
+/// Persist one task's current state into `.forge/tasks/{intent_id}.yaml`, so a
+/// later run can resume from the last completed stage.
+pub fn save_task(repo_path: &Path, task: &Task) -> Result<()> {
+  let mut tasks = read_all_tasks(repo_path, &task.intent_id)?;
+  if let Some(saved) = tasks.iter_mut().find(|t| t.id == task.id) {
+    *saved = task.clone();
+  }
+  write_all_tasks(repo_path, &task.intent_id, &tasks)
+}
+
+/// Give the intent's tasks a fresh retry budget when a human re-approves it,
+/// so tasks that exhausted their implement or rebase retries run again.
+pub fn reset_retries(repo_path: &Path, intent_id: &str) -> Result<()> {
+  if !tasks_exist(repo_path, intent_id) {
+    return Ok(());
+  }
+  let mut tasks = read_all_tasks(repo_path, intent_id)?;
+  if !tasks
+    .iter()
+    .any(|t| t.retries_exhausted || t.implement_failures > 0 || t.rebase_conflicts > 0)
+  {
+    return Ok(());
+  }
+  for task in &mut tasks {
+    task.implement_failures = 0;
+    task.rebase_conflicts = 0;
+    task.retries_exhausted = false;
+  }
+  write_all_tasks(repo_path, intent_id, &tasks)
+}
+
+/// Prepare tasks read back on resume: completed and implemented tasks keep their
+/// stage, anything interrupted mid-implement or failed is run again unless its
+/// retries are exhausted.
+pub fn reset_interrupted(tasks: &mut [Task]) {
+  for task in tasks {
+    if task.retries_exhausted {
+      continue;
+    }
+    if matches!(task.status, WorkStatus::Implementing | WorkStatus::Failed) {
+      task.status = WorkStatus::Pending;
+    }
+  }
+}
+
+/// Completed count and failed task IDs, for blocked / error intents in `status`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakdown<'a> {
+  pub completed: usize,
+  pub total: usize,
+  pub failed: Vec<&'a str>,
+}
+
+pub fn breakdown(tasks: &[Task]) -> Breakdown<'_> {
+  Breakdown {
+    completed: tasks
+      .iter()
+      .filter(|t| t.status == WorkStatus::Completed)
+      .count(),
+    total: tasks.len(),
+    failed: tasks
+      .iter()
+      .filter(|t| t.status == WorkStatus::Failed)
+      .map(|t| t.id.as_str())
+      .collect(),
+  }
+}
@@ -19,7 +19,6 @@ fn sample_intent() -> Intent {
   std::fs::write(dir.path().join("add-tests.yaml"), yaml).unwrap();
   let intents = Intent::fetch_all(dir.path()).unwrap();
   // Leak tempdir so the intent stays valid
-  let intents = intents;
   std::mem::forget(dir);
   intents.into_iter().next().unwrap()
 }
@@ -0,0 +1,34 @@
+use pfl_forge::agent::commit_message;
+use pfl_forge::config::Config;
+use pfl_forge::intent::registry::Intent;
+
+use crate::mock_claude::MockClaude;
+
+fn default_config() -> Config {
+  serde_yaml::from_str("{}").unwrap()
+}
+
+#[test]
+fn 規約違反の件名とパターンをプロンプトに含め修正案を返す() {
+  let mock =
+    MockClaude::with_json(r#"{"subjects":[{"original":"wip","fixed":"fix: reject empty input"}]}"#);
+  let config = default_config();
+  let intent = Intent::synthetic("Fix validation", "Reject empty input");
+  let dir = tempfile::tempdir().unwrap();
+
+  let (result, _meta) = commit_message::fix_subjects(
+    &intent,
+    &config,
+    &mock,
+    dir.path(),
+    "^(feat|fix): .+",
+    &["wip".to_string()],
+  )
+  .unwrap();
+
+  assert_eq!(result.subjects[0].fixed, "fix: reject empty input");
+  let call = mock.last_call();
+  assert!(call.prompt.contains("`^(feat|fix): .+`"));
+  assert!(call.prompt.contains("- wip"));
+  assert!(call.prompt.contains("Reject empty input"));
+}
@@ -0,0 +1,15 @@
+use pfl_forge::agent::conflict;
+
+#[test]
+fn コンフリクトマーカーの範囲だけを取り出す() {
+  let content = "fn a() {}\n<<<<<<< HEAD\nlet x = 1;\n=======\nlet x = 2;\n>>>>>>> abc123 (change)\nfn b() {}\n";
+  assert_eq!(
+    conflict::conflict_hunks(content),
+    "<<<<<<< HEAD\nlet x = 1;\n=======\nlet x = 2;\n>>>>>>> abc123 (change)"
+  );
+}
+
+#[test]
+fn マーカーがなければ空を返す() {
+  assert!(conflict::conflict_hunks("fn a() {}\n").is_empty());
+}
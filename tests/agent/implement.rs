@@ -1,4 +1,4 @@
-use pfl_forge::agent::implement;
+use pfl_forge::agent::implement::{self, Feedback};
 use pfl_forge::agent::review::ReviewResult;
 use pfl_forge::claude::runner::SessionMode;
 use pfl_forge::intent::registry::Intent;
@@ -27,6 +27,11 @@ fn sample_task(intent: &Intent) -> Task {
     implementation_steps: vec!["Add email check".into(), "Add tests".into()],
     context: "Login module context".into(),
     depends_on: vec![],
+    checks: None,
+    implement_failures: 0,
+    rebase_conflicts: 0,
+    errors: vec![],
+    retries_exhausted: false,
   }
 }
 
@@ -41,6 +46,7 @@ fn intentコンテキストで実装を実行する() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "sonnet",
     dir.path(),
     None,
@@ -57,6 +63,37 @@ fn intentコンテキストで実装を実行する() {
   assert!(call.prompt.contains("Add email check"));
 }
 
+#[test]
+fn 受け入れ条件をimplementプロンプトに含める() {
+  let mock = MockClaude::with_json("{}");
+  let dir = tempfile::tempdir().unwrap();
+  let yaml = "title: Fix bug\nbody: |\n  Fix login\n\n  ## Expected behavior\n\n  Errors are shown inline.\n\n  ## Acceptance criteria\n\n  1. Empty email is rejected\nsource: human\n";
+  std::fs::write(dir.path().join("fix-bug.yaml"), yaml).unwrap();
+  let intent = Intent::fetch_all(dir.path()).unwrap().remove(0);
+  let task = sample_task(&intent);
+
+  implement::run(
+    &intent,
+    &task,
+    &mock,
+    pfl_forge::prompt::IMPLEMENT,
+    "sonnet",
+    dir.path(),
+    None,
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  let call = mock.last_call();
+  assert!(call
+    .prompt
+    .contains("## Expected Behavior\n\nErrors are shown inline."));
+  assert!(call
+    .prompt
+    .contains("## Acceptance Criteria\n\n1. Empty email is rejected"));
+}
+
 #[test]
 fn 低complexityではデフォルトモデルを選択する() {
   let mock = MockClaude::with_json("{}");
@@ -68,6 +105,7 @@ fn 低complexityではデフォルトモデルを選択する() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "default-model",
     dir.path(),
     None,
@@ -91,6 +129,7 @@ fn 高complexityではcomplexモデルを選択する() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "complex-model",
     dir.path(),
     None,
@@ -122,18 +161,30 @@ fn リトライ時にレビューフィードバックをプロンプトに含
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "sonnet",
     dir.path(),
     None,
-    Some(&feedback),
+    Some(Feedback::Review(&feedback)),
     &SessionMode::new_session(),
   )
   .unwrap();
 
   let call = mock.last_call();
   assert!(call.prompt.contains("Previous Review Feedback"));
-  assert!(call.prompt.contains("Missing error handling"));
+  assert!(call.prompt.contains("1. Missing error handling"));
   assert!(call.prompt.contains("Add try-catch block"));
+  assert!(call.prompt.contains(implement::REMEDIATION_HEADING));
+}
+
+#[test]
+fn 最終応答からremediationノートを取り出す() {
+  let raw = r#"{"result": "Done.\n\n## Remediation\n1. Added error handling in parse()"}"#;
+  assert_eq!(
+    implement::remediation_notes(raw).as_deref(),
+    Some("1. Added error handling in parse()")
+  );
+  assert!(implement::remediation_notes(r#"{"result": "Done."}"#).is_none());
 }
 
 #[test]
@@ -147,6 +198,7 @@ fn 初回実行時はレビューセクションを省略する() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "sonnet",
     dir.path(),
     None,
@@ -170,6 +222,7 @@ fn claudeエラーを伝播する() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "sonnet",
     dir.path(),
     None,
@@ -190,6 +243,7 @@ fn プロンプトにtaskのplan_steps_filesが含まれる() {
     &intent,
     &task,
     &mock,
+    pfl_forge::prompt::IMPLEMENT,
     "sonnet",
     dir.path(),
     None,
@@ -210,3 +264,60 @@ fn プロンプトにtaskのplan_steps_filesが含まれる() {
   assert!(call.prompt.contains("Login module context"));
   assert!(call.prompt.contains("**Complexity:** low"));
 }
+
+#[test]
+fn lint失敗時にlint出力をプロンプトに含める() {
+  let mock = MockClaude::with_json("{}");
+  let intent = sample_intent();
+  let task = sample_task(&intent);
+  let dir = tempfile::tempdir().unwrap();
+
+  implement::run(
+    &intent,
+    &task,
+    &mock,
+    pfl_forge::prompt::IMPLEMENT,
+    "sonnet",
+    dir.path(),
+    None,
+    Some(Feedback::Lint("warning: unused variable `x`")),
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  let call = mock.last_call();
+  assert!(call.prompt.contains("Lint Failure"));
+  assert!(call.prompt.contains("unused variable `x`"));
+  assert!(!call.prompt.contains("Previous Review Feedback"));
+}
+
+#[test]
+fn カバレッジ不足時に閾値と出力をプロンプトに含める() {
+  let mock = MockClaude::with_json("{}");
+  let intent = sample_intent();
+  let task = sample_task(&intent);
+  let dir = tempfile::tempdir().unwrap();
+
+  implement::run(
+    &intent,
+    &task,
+    &mock,
+    pfl_forge::prompt::IMPLEMENT,
+    "sonnet",
+    dir.path(),
+    None,
+    Some(Feedback::Coverage {
+      coverage: 62.5,
+      min_coverage: 80.0,
+      output: "src/login.rs 40.00%",
+    }),
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  let call = mock.last_call();
+  assert!(call.prompt.contains("## Coverage Failure"));
+  assert!(call.prompt.contains("62.5%"));
+  assert!(call.prompt.contains("80.0%"));
+  assert!(call.prompt.contains("src/login.rs 40.00%"));
+}
@@ -2,6 +2,8 @@ mod mock_claude;
 
 mod analyze;
 mod audit;
+mod commit_message;
+mod conflict;
 mod implement;
 mod operator;
 mod reflect;
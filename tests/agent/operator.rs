@@ -1,6 +1,10 @@
 use pfl_forge::agent::operator;
+use pfl_forge::claude::model;
+use pfl_forge::config::Config;
 use pfl_forge::prompt;
 
+use crate::mock_claude::MockClaude;
+
 #[test]
 fn intentがない場合のメッセージ() {
   let dir = tempfile::tempdir().unwrap();
@@ -95,6 +99,128 @@ fn clarificationの質問内容がinboxに表示される() {
   assert!(!msg.contains("Q: Use REST or gRPC?"));
 }
 
+#[test]
+fn 前回runの結果がinitial_messageに含まれる() {
+  let dir = tempfile::tempdir().unwrap();
+  let forge_dir = dir.path().join(".forge");
+  std::fs::create_dir_all(&forge_dir).unwrap();
+
+  std::fs::write(
+    forge_dir.join("last-run.yaml"),
+    r#"finished_at: "2026-01-01T00:00:00Z"
+intents:
+  - intent_id: fix-login
+    title: Fix login
+    outcome: failed
+    failure_reason: review rejected after max retries
+    cost_usd: 1.5
+    next_action: "investigate failure, then re-approve: pfl-forge approve fix-login"
+"#,
+  )
+  .unwrap();
+
+  let msg = operator::build_initial_message(dir.path());
+  assert!(msg.contains("## Last Run"));
+  assert!(msg.contains("**fix-login** (failed, $1.50)"));
+  assert!(msg.contains("Reason: review rejected after max retries"));
+  assert!(msg.contains("Next: investigate failure"));
+}
+
+#[test]
+fn 最近の失敗がエラー抜粋付きで新しい順に含まれる() {
+  let dir = tempfile::tempdir().unwrap();
+  let history_dir = dir.path().join(".forge").join("knowledge").join("history");
+  std::fs::create_dir_all(&history_dir).unwrap();
+
+  let long_reason = format!("cargo test failed:\n{}", "x".repeat(1000));
+  std::fs::write(
+    history_dir.join("old-fail.yaml"),
+    format!(
+      "intent_id: old-fail\ntitle: Old failure\nflow: []\noutcome: failed\nfailure_reason: {:?}\ncreated_at: \"2026-01-01T00:00:00Z\"\n",
+      long_reason
+    ),
+  )
+  .unwrap();
+  std::fs::write(
+    history_dir.join("new-esc.yaml"),
+    "intent_id: new-esc\ntitle: New escalation\nflow: []\noutcome: escalated\nfailure_reason: needs clarification\ncreated_at: \"2026-02-01T00:00:00Z\"\n",
+  )
+  .unwrap();
+  std::fs::write(
+    history_dir.join("ok.yaml"),
+    "intent_id: ok\ntitle: Fine\nflow: []\noutcome: success\ncreated_at: \"2026-02-02T00:00:00Z\"\n",
+  )
+  .unwrap();
+
+  let msg = operator::build_initial_message(dir.path());
+  assert!(msg.contains("## Recent Failures"));
+  let new_pos = msg.find("**new-esc** (escalated").unwrap();
+  let old_pos = msg.find("**old-fail** (failed").unwrap();
+  assert!(new_pos < old_pos);
+  assert!(msg.contains("Error: cargo test failed: xxx"));
+  assert!(msg.contains("…"));
+  assert!(!msg.contains(&"x".repeat(1000)));
+  assert!(!msg.contains("**ok**"));
+}
+
+#[test]
+fn 今日のコストが集計される() {
+  let dir = tempfile::tempdir().unwrap();
+  let history_dir = dir.path().join(".forge").join("knowledge").join("history");
+  std::fs::create_dir_all(&history_dir).unwrap();
+
+  let now = chrono::Utc::now().to_rfc3339();
+  for (id, cost) in [("a", 1.25), ("b", 0.5)] {
+    std::fs::write(
+      history_dir.join(format!("{id}.yaml")),
+      format!(
+        "intent_id: {id}\ntitle: {id}\nflow: []\noutcome: success\nstep_results:\n  - step: implement\n    duration_secs: 10\n    metadata:\n      cost_usd: {cost}\ncreated_at: \"{now}\"\n"
+      ),
+    )
+    .unwrap();
+  }
+  // Yesterday's spend is not counted
+  std::fs::write(
+    history_dir.join("old.yaml"),
+    "intent_id: old\ntitle: old\nflow: []\noutcome: success\nstep_results:\n  - step: implement\n    duration_secs: 10\n    metadata:\n      cost_usd: 9.0\ncreated_at: \"2020-01-01T00:00:00Z\"\n",
+  )
+  .unwrap();
+
+  let msg = operator::build_initial_message(dir.path());
+  assert!(msg.contains("## Cost Today"));
+  assert!(msg.contains("$1.75 across 2 intent(s)"));
+}
+
+#[test]
+fn 状態に応じた次のアクションを提案する() {
+  let dir = tempfile::tempdir().unwrap();
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&intents_dir).unwrap();
+
+  std::fs::write(
+    intents_dir.join("p.yaml"),
+    "title: P\nbody: b\nsource: human\nstatus: proposed\n",
+  )
+  .unwrap();
+  std::fs::write(
+    intents_dir.join("c.yaml"),
+    "title: C\nbody: b\nsource: human\nstatus: blocked\nclarifications:\n  - question: Which?\n",
+  )
+  .unwrap();
+  std::fs::write(
+    intents_dir.join("a.yaml"),
+    "title: A\nbody: b\nsource: human\nstatus: approved\n",
+  )
+  .unwrap();
+
+  let msg = operator::build_initial_message(dir.path());
+  assert!(msg.contains("## Suggested Next Actions"));
+  assert!(msg.contains("1. Ask the user the open clarification questions (1 intent(s))"));
+  assert!(msg.contains("2. Review 1 proposed intent(s)"));
+  assert!(msg.contains("3. Start a run for 1 approved intent(s)"));
+  assert!(!msg.contains("Investigate"));
+}
+
 #[test]
 fn プロンプトがbackground実行後のポーリングを禁止する() {
   let prompt = prompt::OPERATOR;
@@ -107,3 +233,163 @@ fn プロンプトがbackground実行後のポーリングを禁止する() {
     "operator prompt must not encourage monitoring progress"
   );
 }
+
+// --- Report mode ---
+
+#[test]
+fn reportモードはブリーフィングを渡してレポート文を返す() {
+  let dir = tempfile::tempdir().unwrap();
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&intents_dir).unwrap();
+  std::fs::write(
+    intents_dir.join("feat-a.yaml"),
+    "title: Feature A\nbody: Do A\nsource: human\nstatus: proposed\n",
+  )
+  .unwrap();
+
+  let mock = MockClaude::with_json(r#"{"report":"- Needs humans: approve feat-a"}"#);
+  let config: Config = serde_yaml::from_str("{}").unwrap();
+
+  let (result, _meta) = operator::report(&config, &mock, None, dir.path()).unwrap();
+
+  assert_eq!(result.report, "- Needs humans: approve feat-a");
+  let call = mock.last_call();
+  assert!(call.prompt.contains("## Inbox"));
+  assert!(call.prompt.contains("**feat-a**"));
+  assert_eq!(call.system_prompt, prompt::OPERATOR_REPORT);
+  assert_eq!(call.model, model::SONNET);
+}
+
+#[test]
+fn reportモードで指定したモデルを使う() {
+  let dir = tempfile::tempdir().unwrap();
+  let mock = MockClaude::with_json(r#"{"report":"nothing to report"}"#);
+  let config: Config = serde_yaml::from_str("{}").unwrap();
+
+  operator::report(&config, &mock, Some("opus"), dir.path()).unwrap();
+
+  assert_eq!(mock.last_call().model, model::OPUS);
+}
+
+// --- MCP server ---
+
+fn no_run() -> pfl_forge::error::Result<u32> {
+  panic!("run should not be triggered")
+}
+
+fn call(dir: &std::path::Path, name: &str, arguments: serde_json::Value) -> serde_json::Value {
+  let request = serde_json::json!({
+    "jsonrpc": "2.0",
+    "id": 1,
+    "method": "tools/call",
+    "params": { "name": name, "arguments": arguments },
+  });
+  pfl_forge::mcp::handle(dir, &request, &no_run).unwrap()["result"].clone()
+}
+
+#[test]
+fn mcpでforgeのツール一覧を返す() {
+  let dir = tempfile::tempdir().unwrap();
+  let request = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+
+  let response = pfl_forge::mcp::handle(dir.path(), &request, &no_run).unwrap();
+
+  let names: Vec<&str> = response["result"]["tools"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|t| t["name"].as_str().unwrap())
+    .collect();
+  assert_eq!(names, vec!["status", "clarifications", "answer", "run"]);
+}
+
+#[test]
+fn mcpのanswerで複数行の回答を記録して承認する() {
+  let dir = tempfile::tempdir().unwrap();
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&intents_dir).unwrap();
+  std::fs::write(
+    intents_dir.join("fix-auth.yaml"),
+    "title: Fix auth\nbody: b\nsource: human\nstatus: blocked\nclarifications:\n  - question: Which provider?\n    answer: null\n",
+  )
+  .unwrap();
+
+  let pending = call(dir.path(), "clarifications", serde_json::json!({}));
+  assert!(pending["content"][0]["text"]
+    .as_str()
+    .unwrap()
+    .contains("Which provider?"));
+
+  let answer = "Google, \"not\" GitHub\nand keep 'SAML' out";
+  let result = call(
+    dir.path(),
+    "answer",
+    serde_json::json!({ "id": "fix-auth", "answer": answer }),
+  );
+  assert_eq!(result["isError"], false);
+
+  let intents = pfl_forge::intent::registry::Intent::fetch_all(&intents_dir).unwrap();
+  assert_eq!(intents[0].clarifications[0].answer.as_deref(), Some(answer));
+  assert_eq!(
+    intents[0].status,
+    pfl_forge::intent::registry::IntentStatus::Approved
+  );
+}
+
+#[test]
+fn mcpで存在しないintentへのanswerはエラーを返す() {
+  let dir = tempfile::tempdir().unwrap();
+
+  let result = call(
+    dir.path(),
+    "answer",
+    serde_json::json!({ "id": "missing", "answer": "x" }),
+  );
+
+  assert_eq!(result["isError"], true);
+  assert!(result["content"][0]["text"]
+    .as_str()
+    .unwrap()
+    .contains("not found"));
+}
+
+#[test]
+fn mcpのrunでバックグラウンド実行を起動する() {
+  let dir = tempfile::tempdir().unwrap();
+  let request = serde_json::json!({
+    "jsonrpc": "2.0",
+    "id": 7,
+    "method": "tools/call",
+    "params": { "name": "run", "arguments": {} },
+  });
+
+  let response = pfl_forge::mcp::handle(dir.path(), &request, &|| Ok(4242)).unwrap();
+
+  assert_eq!(response["id"], 7);
+  assert!(response["result"]["content"][0]["text"]
+    .as_str()
+    .unwrap()
+    .contains("4242"));
+}
+
+#[test]
+fn mcpの通知には応答せず未知のメソッドはエラーにする() {
+  let dir = tempfile::tempdir().unwrap();
+  let notification = serde_json::json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+  assert!(pfl_forge::mcp::handle(dir.path(), &notification, &no_run).is_none());
+
+  let request = serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" });
+  let response = pfl_forge::mcp::handle(dir.path(), &request, &no_run).unwrap();
+  assert_eq!(response["error"]["code"], -32601);
+}
+
+#[test]
+fn operatorにforgeのmcpサーバーを登録する() {
+  let config = operator::mcp_config(std::path::Path::new("/usr/local/bin/pfl-forge"));
+  let value: serde_json::Value = serde_json::from_str(&config).unwrap();
+  assert_eq!(
+    value["mcpServers"]["pfl-forge"]["command"],
+    "/usr/local/bin/pfl-forge"
+  );
+  assert_eq!(value["mcpServers"]["pfl-forge"]["args"][0], "mcp");
+}
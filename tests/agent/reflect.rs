@@ -195,3 +195,77 @@ fn claudeエラーを伝播する() {
   );
   assert!(result.is_err());
 }
+
+#[test]
+fn guidanceの学びはガイダンス更新intentとして別に提案する() {
+  let dir = tempfile::tempdir().unwrap();
+  std::fs::create_dir_all(dir.path().join(".forge")).unwrap();
+  std::fs::write(
+    dir.path().join("AGENTS.md"),
+    "# Agents\n\n- Use cargo nextest\n",
+  )
+  .unwrap();
+  let intent = sample_intent(dir.path());
+  write_observations(dir.path(), "fix-bug", 1);
+
+  let mock = MockClaude::with_json(
+    r#"{"intents":[],"guidance":["Run `make gen` before tests","test_upload is flaky on CI"]}"#,
+  );
+  let config = default_config();
+
+  let (result, _meta) = reflect::reflect(
+    &intent,
+    &config,
+    &mock,
+    dir.path(),
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+  assert_eq!(result.guidance.len(), 2);
+
+  // The current guidance file is shown to Reflect
+  let prompt = mock.last_call().prompt;
+  assert!(prompt.contains("## Current Agent Guidance (AGENTS.md)"));
+  assert!(prompt.contains("Use cargo nextest"));
+
+  let intents_dir = dir.path().join(".forge").join("intents");
+  let intents = Intent::fetch_all(&intents_dir).unwrap();
+  let guidance = intents
+    .iter()
+    .find(|i| i.id() == "update-agent-guidance-fix-bug")
+    .expect("guidance intent");
+  assert_eq!(guidance.title, "Update AGENTS.md from fix-bug");
+  assert!(guidance.body.contains("- Run `make gen` before tests"));
+  assert!(guidance.body.contains("- test_upload is flaky on CI"));
+  assert_eq!(guidance.source, "reflection");
+}
+
+#[test]
+fn guidanceが空ならガイダンス更新intentを作らない() {
+  let dir = tempfile::tempdir().unwrap();
+  std::fs::create_dir_all(dir.path().join(".forge")).unwrap();
+  let intent = sample_intent(dir.path());
+  write_observations(dir.path(), "fix-bug", 1);
+
+  let mock = MockClaude::with_json(reflect_json());
+  let config = default_config();
+
+  reflect::reflect(
+    &intent,
+    &config,
+    &mock,
+    dir.path(),
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  assert!(mock
+    .last_call()
+    .prompt
+    .contains("## Current Agent Guidance (CLAUDE.md)\n\n(none)"));
+  let intents_dir = dir.path().join(".forge").join("intents");
+  let intents = Intent::fetch_all(&intents_dir).unwrap();
+  assert!(!intents
+    .iter()
+    .any(|i| i.id().starts_with("update-agent-guidance")));
+}
@@ -33,6 +33,11 @@ fn sample_task() -> Task {
     implementation_steps: vec!["step 1".into()],
     context: "context".into(),
     depends_on: vec![],
+    checks: None,
+    implement_failures: 0,
+    rebase_conflicts: 0,
+    errors: vec![],
+    retries_exhausted: false,
   }
 }
 
@@ -83,6 +88,7 @@ fn 承認されたレビュー結果を返す() {
     &mock,
     repo.path(),
     "main",
+    None,
     &SessionMode::new_session(),
   )
   .unwrap();
@@ -107,6 +113,7 @@ fn 却下時にissueを返す() {
     &mock,
     repo.path(),
     "main",
+    None,
     &SessionMode::new_session(),
   )
   .unwrap();
@@ -131,6 +138,7 @@ fn プロンプトにdiffとplanを含める() {
     &mock,
     repo.path(),
     "main",
+    None,
     &SessionMode::new_session(),
   )
   .unwrap();
@@ -140,6 +148,36 @@ fn プロンプトにdiffとplanを含める() {
   assert!(call.prompt.contains("modified"));
 }
 
+#[test]
+fn 受け入れ条件をプロンプトのセクションとして渡す() {
+  let json = r#"{"approved":true,"issues":[],"suggestions":[]}"#;
+  let mock = MockClaude::with_json(json);
+  let config = default_config();
+  let dir = tempfile::tempdir().unwrap();
+  let yaml = "title: Fix bug\nbody: |\n  Fix validation\n\n  ## Acceptance criteria\n\n  - rejects empty input\n  - keeps valid input\nsource: human\n";
+  std::fs::write(dir.path().join("fix-bug.yaml"), yaml).unwrap();
+  let intent = Intent::fetch_all(dir.path()).unwrap().remove(0);
+  let task = sample_task();
+  let repo = setup_git_repo();
+
+  review::review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  let call = mock.last_call();
+  assert!(call
+    .prompt
+    .contains("## Acceptance Criteria\n\n1. rejects empty input\n2. keeps valid input"));
+}
+
 #[test]
 fn configのデフォルトモデルを使用する() {
   let json = r#"{"approved":true,"issues":[],"suggestions":[]}"#;
@@ -156,6 +194,7 @@ fn configのデフォルトモデルを使用する() {
     &mock,
     repo.path(),
     "main",
+    None,
     &SessionMode::new_session(),
   )
   .unwrap();
@@ -164,6 +203,36 @@ fn configのデフォルトモデルを使用する() {
   assert_eq!(call.model, pfl_forge::claude::model::SONNET);
 }
 
+#[test]
+fn リポジトリのプロンプト上書きをsystem_promptに使う() {
+  let json = r#"{"approved":true,"issues":[],"suggestions":[]}"#;
+  let mock = MockClaude::with_json(json);
+  let mut config = default_config();
+  config
+    .prompts
+    .set("review", "Review like a security auditor.");
+  let intent = sample_intent();
+  let task = sample_task();
+  let repo = setup_git_repo();
+
+  review::review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  assert_eq!(
+    mock.last_call().system_prompt,
+    "Review like a security auditor."
+  );
+}
+
 #[test]
 fn 大きなdiffを切り詰める() {
   let json = r#"{"approved":true,"issues":[],"suggestions":[]}"#;
@@ -204,6 +273,7 @@ fn 大きなdiffを切り詰める() {
     &mock,
     p,
     "main",
+    None,
     &SessionMode::new_session(),
   )
   .unwrap();
@@ -232,7 +302,135 @@ fn claudeエラーを伝播する() {
     &mock,
     repo.path(),
     "main",
+    None,
     &SessionMode::new_session(),
   );
   assert!(result.is_err());
 }
+
+#[test]
+fn 再レビューでは前回のissueと差分とremediationを渡す() {
+  let mock = MockClaude::with_json(r#"{"approved":true,"issues":[],"suggestions":[]}"#);
+  let config = default_config();
+  let intent = sample_intent();
+  let task = sample_task();
+  let repo = setup_git_repo();
+  let previous = review::ReviewResult {
+    task_id: task.id.clone(),
+    approved: false,
+    issues: vec!["Missing tests".into(), "Unchecked unwrap".into()],
+    suggestions: vec![],
+    observations: vec![],
+    session_id: None,
+  };
+  let follow_up = review::FollowUp {
+    previous: &previous,
+    changes: Some("+fn test_parse() {}"),
+    remediation: Some("1. Added test_parse\n2. Replaced unwrap with ?"),
+  };
+
+  review::re_review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &follow_up,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  let prompt = mock.last_call().prompt;
+  assert!(prompt.contains("## Previous Review"));
+  assert!(prompt.contains("1. Missing tests\n2. Unchecked unwrap"));
+  assert!(prompt.contains("## Changes Since Previous Attempt"));
+  assert!(prompt.contains("+fn test_parse() {}"));
+  assert!(prompt.contains("## Remediation Notes\n\n1. Added test_parse"));
+}
+
+#[test]
+fn 初回レビューには前回レビューのセクションを含めない() {
+  let mock = MockClaude::with_json(r#"{"approved":true,"issues":[],"suggestions":[]}"#);
+  let config = default_config();
+  let intent = sample_intent();
+  let task = sample_task();
+  let repo = setup_git_repo();
+
+  review::review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  assert!(!mock.last_call().prompt.contains("## Previous Review"));
+}
+
+#[test]
+fn パースできない出力は修復パスを経て結果を返す() {
+  let mock = MockClaude::with_sequence(vec![
+    Ok(r#"{"result": "Looks good. approved: true, no issues"}"#.into()),
+    Ok(r#"{"result": "{\"approved\":true,\"issues\":[],\"suggestions\":[]}", "total_cost_usd": 0.001}"#.into()),
+  ]);
+  let config = default_config();
+  let intent = sample_intent();
+  let task = sample_task();
+  let repo = setup_git_repo();
+
+  let (result, _meta) = review::review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap();
+
+  assert!(result.approved);
+  assert_eq!(mock.call_count(), 2);
+  let repair = mock.last_call();
+  assert_eq!(repair.model, pfl_forge::claude::model::HAIKU);
+  assert_eq!(repair.system_prompt, pfl_forge::prompt::JSON_REPAIR);
+  assert!(repair.prompt.contains("## Parse Error"));
+  assert!(repair
+    .prompt
+    .contains("Looks good. approved: true, no issues"));
+  assert!(repair
+    .prompt
+    .contains(pfl_forge::prompt::REVIEW.lines().next().unwrap()));
+}
+
+#[test]
+fn 修復しても不正なら元のパースエラーを返す() {
+  let mock = MockClaude::with_json("not json at all");
+  let config = default_config();
+  let intent = sample_intent();
+  let task = sample_task();
+  let repo = setup_git_repo();
+
+  let err = review::review(
+    &intent,
+    &task,
+    &config,
+    &mock,
+    repo.path(),
+    "main",
+    None,
+    &SessionMode::new_session(),
+  )
+  .unwrap_err();
+
+  assert!(err.to_string().contains("failed to parse result"));
+  assert!(err.to_string().contains("repair failed"));
+  assert_eq!(mock.call_count(), 2);
+}
@@ -0,0 +1,96 @@
+use pfl_forge::knowledge::export;
+use pfl_forge::knowledge::history::{self, HistoryEntry, Outcome, StepResult, StepStatus};
+
+fn setup() -> tempfile::TempDir {
+  let dir = tempfile::tempdir().unwrap();
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&intents_dir).unwrap();
+  std::fs::write(
+    intents_dir.join("fix-login.yaml"),
+    "title: \"Fix login, again\"\nbody: b\nsource: human\nstatus: done\ntype: fix\nrun_id: run-1\n",
+  )
+  .unwrap();
+  std::fs::write(
+    intents_dir.join("add-auth.yaml"),
+    "title: Add auth\nbody: b\nsource: human\nstatus: proposed\n",
+  )
+  .unwrap();
+
+  let entry = HistoryEntry {
+    intent_id: "fix-login".into(),
+    intent_type: Some("fix".into()),
+    intent_risk: None,
+    title: "Fix login, again".into(),
+    flow: vec![],
+    step_results: vec![
+      StepResult {
+        step: "implement".into(),
+        status: StepStatus::Success,
+        duration_secs: 100,
+        model: None,
+        metadata: Some(pfl_forge::claude::runner::ClaudeMetadata {
+          cost_usd: Some(1.25),
+          ..Default::default()
+        }),
+      },
+      StepResult {
+        step: "review".into(),
+        status: StepStatus::Success,
+        duration_secs: 20,
+        model: None,
+        metadata: None,
+      },
+    ],
+    outcome: Outcome::Success,
+    failure_reason: None,
+    observations: vec![],
+    created_at: Some("2026-01-01T00:00:00Z".into()),
+    run_id: Some("run-1".into()),
+  };
+  history::write(dir.path(), &entry).unwrap();
+  dir
+}
+
+#[test]
+fn 全intentを履歴と合わせて平坦化する() {
+  let dir = setup();
+
+  let rows = export::rows(dir.path()).unwrap();
+
+  assert_eq!(rows.len(), 2);
+  assert_eq!(rows[0].intent_id, "add-auth");
+  assert!(rows[0].outcome.is_none());
+  assert_eq!(rows[1].intent_id, "fix-login");
+  assert_eq!(rows[1].outcome, Some(Outcome::Success));
+  assert_eq!(rows[1].duration_secs, 120);
+  assert_eq!(rows[1].cost_usd, 1.25);
+  assert_eq!(rows[1].run_id.as_deref(), Some("run-1"));
+}
+
+#[test]
+fn csvではカンマを含むフィールドを引用符で囲む() {
+  let dir = setup();
+
+  let csv = export::to_csv(&export::rows(dir.path()).unwrap());
+  let lines: Vec<&str> = csv.lines().collect();
+
+  assert_eq!(lines.len(), 3);
+  assert!(lines[0].starts_with("intent_id,title,status"));
+  assert_eq!(lines[1], "add-auth,Add auth,proposed,,,,,,,,0,0.0000");
+  assert_eq!(
+    lines[2],
+    "fix-login,\"Fix login, again\",done,fix,,,run-1,success,,2026-01-01T00:00:00Z,120,1.2500"
+  );
+}
+
+#[test]
+fn jsonでは行の配列を出力する() {
+  let dir = setup();
+
+  let json = export::to_json(&export::rows(dir.path()).unwrap()).unwrap();
+  let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+  assert_eq!(value.as_array().unwrap().len(), 2);
+  assert_eq!(value[1]["status"], "done");
+  assert_eq!(value[1]["outcome"], "success");
+}
@@ -1,4 +1,4 @@
-use pfl_forge::knowledge::history::{self, HistoryEntry, Outcome, StepResult};
+use pfl_forge::knowledge::history::{self, HistoryEntry, Outcome, StepResult, StepStatus};
 
 // --- YAML パース ---
 
@@ -78,6 +78,7 @@ fn historyエントリをyamlに書き込む() {
     failure_reason: None,
     observations: vec![],
     created_at: None,
+    run_id: None,
   };
 
   history::write(dir.path(), &entry).unwrap();
@@ -98,12 +99,16 @@ fn step_resultsに所要時間が含まれる() {
     step_results: vec![
       StepResult {
         step: "analyze".into(),
+        status: StepStatus::Success,
         duration_secs: 45,
+        model: None,
         metadata: None,
       },
       StepResult {
         step: "implement".into(),
+        status: StepStatus::Success,
         duration_secs: 300,
+        model: None,
         metadata: None,
       },
     ],
@@ -111,6 +116,7 @@ fn step_resultsに所要時間が含まれる() {
     failure_reason: None,
     observations: vec![],
     created_at: None,
+    run_id: None,
   };
 
   history::write(dir.path(), &entry).unwrap();
@@ -135,9 +141,201 @@ fn observation参照が含まれる() {
     failure_reason: None,
     observations: vec!["obs-001".into(), "obs-002".into()],
     created_at: None,
+    run_id: None,
   };
 
   history::write(dir.path(), &entry).unwrap();
   let loaded = history::load(dir.path(), "obs-test").unwrap();
   assert_eq!(loaded.observations, vec!["obs-001", "obs-002"]);
 }
+
+#[test]
+fn run_idでhistoryを絞り込む() {
+  let dir = tempfile::tempdir().unwrap();
+  for (id, run) in [("a", Some("run-1")), ("b", Some("run-2")), ("c", None)] {
+    let entry = HistoryEntry {
+      intent_id: id.into(),
+      intent_type: None,
+      intent_risk: None,
+      title: id.into(),
+      flow: vec![],
+      step_results: vec![],
+      outcome: Outcome::Success,
+      failure_reason: None,
+      observations: vec![],
+      created_at: None,
+      run_id: run.map(String::from),
+    };
+    history::write(dir.path(), &entry).unwrap();
+  }
+
+  let entries = history::load_by_run(dir.path(), "run-1").unwrap();
+  let ids: Vec<&str> = entries.iter().map(|e| e.intent_id.as_str()).collect();
+  assert_eq!(ids, vec!["a"]);
+}
+
+#[test]
+fn 再実行しても以前のrunのhistoryを残す() {
+  let dir = tempfile::tempdir().unwrap();
+  for (run, created_at, outcome) in [
+    (
+      "2026-01-01T00:00:00Z-aaaa",
+      "2026-01-01T00:00:00Z",
+      Outcome::Failed,
+    ),
+    (
+      "2026-01-02T00:00:00Z-bbbb",
+      "2026-01-02T00:00:00Z",
+      Outcome::Success,
+    ),
+  ] {
+    let entry = HistoryEntry {
+      intent_id: "rerun".into(),
+      intent_type: None,
+      intent_risk: None,
+      title: "rerun".into(),
+      flow: vec![],
+      step_results: vec![],
+      outcome,
+      failure_reason: None,
+      observations: vec![],
+      created_at: Some(created_at.into()),
+      run_id: Some(run.into()),
+    };
+    history::write(dir.path(), &entry).unwrap();
+  }
+
+  assert_eq!(history::load_all(dir.path()).unwrap().len(), 2);
+  assert_eq!(
+    history::load(dir.path(), "rerun").unwrap().outcome,
+    Outcome::Success
+  );
+  let old = history::load_by_run(dir.path(), "2026-01-01T00:00:00Z-aaaa").unwrap();
+  assert_eq!(old.len(), 1);
+  assert_eq!(old[0].outcome, Outcome::Failed);
+}
+
+#[test]
+fn intentごとの1ファイル形式の古いhistoryも読み込む() {
+  let dir = tempfile::tempdir().unwrap();
+  let history_dir = dir.path().join(".forge").join("knowledge").join("history");
+  std::fs::create_dir_all(&history_dir).unwrap();
+  std::fs::write(
+    history_dir.join("legacy.yaml"),
+    "intent_id: legacy\nintent_type: null\nintent_risk: null\ntitle: legacy\nflow: []\noutcome: success\nfailure_reason: null\ncreated_at: null\n",
+  )
+  .unwrap();
+
+  assert_eq!(history::load(dir.path(), "legacy").unwrap().title, "legacy");
+  assert_eq!(history::load_all(dir.path()).unwrap().len(), 1);
+}
+
+// --- 集計 ---
+
+fn entry_with_steps(
+  id: &str,
+  outcome: Outcome,
+  steps: &[(&str, u64, f64)],
+  created_at: &str,
+) -> HistoryEntry {
+  HistoryEntry {
+    intent_id: id.into(),
+    intent_type: None,
+    intent_risk: None,
+    title: id.into(),
+    flow: vec![],
+    step_results: steps
+      .iter()
+      .map(|(step, secs, cost)| StepResult {
+        step: step.to_string(),
+        status: StepStatus::Success,
+        duration_secs: *secs,
+        model: None,
+        metadata: Some(pfl_forge::claude::runner::ClaudeMetadata {
+          cost_usd: Some(*cost),
+          ..Default::default()
+        }),
+      })
+      .collect(),
+    outcome,
+    failure_reason: None,
+    observations: vec![],
+    created_at: Some(created_at.into()),
+    run_id: None,
+  }
+}
+
+#[test]
+fn historyから成功率とステップ平均とコストを集計する() {
+  let entries = vec![
+    entry_with_steps(
+      "a",
+      Outcome::Success,
+      &[("analyze", 60, 0.5), ("implement", 100, 1.0)],
+      "2026-01-01T00:00:00Z",
+    ),
+    entry_with_steps(
+      "b",
+      Outcome::Failed,
+      &[
+        ("analyze", 120, 0.5),
+        ("implement", 200, 1.0),
+        ("implement", 300, 1.0),
+      ],
+      "2026-01-02T00:00:00Z",
+    ),
+  ];
+
+  let stats = pfl_forge::knowledge::stats::summarize(&entries, None);
+
+  assert_eq!(stats.intents, 2);
+  assert_eq!(stats.success, 1);
+  assert_eq!(stats.failed, 1);
+  assert_eq!(stats.success_rate, 50.0);
+  assert_eq!(stats.retries, 1);
+  assert!((stats.total_cost_usd - 4.0).abs() < 1e-9);
+  assert_eq!(stats.steps[0].step, "analyze");
+  assert_eq!(stats.steps[0].avg_secs, 90.0);
+  assert_eq!(stats.steps[1].step, "implement");
+  assert_eq!(stats.steps[1].runs, 3);
+  assert_eq!(stats.steps[1].avg_secs, 200.0);
+}
+
+#[test]
+fn 期間外のhistoryを集計から除外する() {
+  let entries = vec![
+    entry_with_steps("old", Outcome::Failed, &[], "2026-01-01T00:00:00Z"),
+    entry_with_steps("new", Outcome::Success, &[], "2026-03-01T00:00:00Z"),
+  ];
+  let since = "2026-02-01T00:00:00Z".parse().unwrap();
+
+  let stats = pfl_forge::knowledge::stats::summarize(&entries, Some(since));
+
+  assert_eq!(stats.intents, 1);
+  assert_eq!(stats.success_rate, 100.0);
+}
+
+#[test]
+fn ステップごとの所要時間をリトライ分も合算する() {
+  let entry = entry_with_steps(
+    "a",
+    Outcome::Success,
+    &[
+      ("analyze", 60, 0.0),
+      ("implement", 100, 0.0),
+      ("review", 10, 0.0),
+      ("implement", 50, 0.0),
+      ("review", 5, 0.0),
+    ],
+    "2026-01-01T00:00:00Z",
+  );
+
+  assert_eq!(
+    entry.step_durations(),
+    vec![
+      ("analyze".to_string(), 60),
+      ("implement".to_string(), 150),
+      ("review".to_string(), 15),
+    ]
+  );
+}
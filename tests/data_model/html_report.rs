@@ -0,0 +1,86 @@
+use chrono::TimeZone;
+use pfl_forge::i18n::Locale;
+use pfl_forge::knowledge::history::{HistoryEntry, Outcome};
+use pfl_forge::knowledge::{export, html_report};
+
+fn entry(id: &str, run_id: &str, created_at: &str, outcome: Outcome) -> HistoryEntry {
+  HistoryEntry {
+    intent_id: id.into(),
+    intent_type: None,
+    intent_risk: None,
+    title: id.into(),
+    flow: vec![],
+    step_results: vec![],
+    outcome,
+    failure_reason: None,
+    observations: vec![],
+    created_at: Some(created_at.into()),
+    run_id: Some(run_id.into()),
+  }
+}
+
+#[test]
+fn intentとrunとコストを1枚のhtmlにまとめる() {
+  let dir = tempfile::tempdir().unwrap();
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&intents_dir).unwrap();
+  std::fs::write(
+    intents_dir.join("fix-login.yaml"),
+    "title: \"Fix <login> & more\"\nbody: b\nsource: human\nstatus: done\n",
+  )
+  .unwrap();
+  let rows = export::rows(dir.path()).unwrap();
+  let entries = vec![
+    entry(
+      "fix-login",
+      "run-1",
+      "2026-01-01T00:00:00Z",
+      Outcome::Success,
+    ),
+    entry("add-auth", "run-2", "2026-01-02T00:00:00Z", Outcome::Failed),
+  ];
+  let now = chrono::Utc.with_ymd_and_hms(2026, 1, 3, 0, 0, 0).unwrap();
+
+  let html = html_report::render("my-repo", &rows, &entries, now, Locale::En);
+
+  assert!(html.starts_with("<!DOCTYPE html>"));
+  assert!(html.contains("<h1>my-repo</h1>"));
+  assert!(html.contains("Fix &lt;login&gt; &amp; more"));
+  // Newest run first
+  let run1 = html.find("<td>run-1</td>").unwrap();
+  let run2 = html.find("<td>run-2</td>").unwrap();
+  assert!(run2 < run1);
+  assert!(html.contains("<title>2026-01-01: $0.00</title>"));
+  assert!(!html.contains("<script"));
+}
+
+#[test]
+fn 出力先ディレクトリを作ってindex_htmlを書く() {
+  let dir = tempfile::tempdir().unwrap();
+  let out = dir.path().join("out").join("nightly");
+
+  let path = html_report::write(dir.path(), &out, Locale::En).unwrap();
+
+  assert_eq!(path, out.join("index.html"));
+  let html = std::fs::read_to_string(path).unwrap();
+  assert!(html.contains("No history yet."));
+}
+
+#[test]
+fn 日本語localeでは見出しとラベルを翻訳する() {
+  let entries = vec![entry(
+    "fix-login",
+    "run-1",
+    "2026-01-01T00:00:00Z",
+    Outcome::Failed,
+  )];
+  let now = chrono::Utc.with_ymd_and_hms(2026, 1, 3, 0, 0, 0).unwrap();
+
+  let html = html_report::render("my-repo", &[], &entries, now, Locale::Ja);
+
+  assert!(html.contains("<html lang=\"ja\">"));
+  assert!(html.contains("<h2>日別コスト</h2>"));
+  assert!(html.contains("<h2>実行履歴</h2>"));
+  // CSS クラスは英語のまま、表示だけ翻訳する
+  assert!(html.contains("<span class=\"dot failed\" title=\"fix-login: 失敗\">"));
+}
@@ -1,4 +1,4 @@
-use pfl_forge::intent::registry::Intent;
+use pfl_forge::intent::registry::{Intent, IntentStatus, PlanApproval};
 
 // --- YAML パース ---
 
@@ -162,6 +162,25 @@ Fix the broken test suite.
   assert!(draft.risk.is_none());
 }
 
+#[test]
+fn frontmatterのtitleがあればbody全体を本文にする() {
+  let md = "\
+---
+title: Add password reset
+---
+
+Users currently have no way to reset their password.
+
+Link it from the login page.
+";
+  let draft = pfl_forge::intent::draft::parse(md).unwrap();
+  assert_eq!(draft.title, "Add password reset");
+  assert_eq!(
+    draft.body,
+    "Users currently have no way to reset their password.\n\nLink it from the login page."
+  );
+}
+
 // --- convert_drafts ---
 
 #[test]
@@ -196,6 +215,52 @@ fn draftをintent_yamlに変換して元ファイルを削除する() {
   assert_eq!(intents[0].risk.as_deref(), Some("low"));
 }
 
+#[test]
+fn draftのdepends_onをintentに引き継ぐ() {
+  use pfl_forge::intent::registry::Intent;
+
+  let dir = tempfile::tempdir().unwrap();
+  let drafts_dir = dir.path().join(".forge").join("intent-drafts");
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&drafts_dir).unwrap();
+
+  std::fs::write(
+    drafts_dir.join("add-logout.md"),
+    "---\ndepends_on: [add-auth]\n---\n\nAdd logout\n",
+  )
+  .unwrap();
+
+  pfl_forge::intent::draft::convert_drafts(dir.path()).unwrap();
+
+  let intents = Intent::fetch_all(&intents_dir).unwrap();
+  assert_eq!(intents.len(), 1);
+  assert_eq!(intents[0].depends_on, vec!["add-auth"]);
+}
+
+#[test]
+fn draftのtagsをintentに引き継ぐ() {
+  use pfl_forge::intent::registry::Intent;
+
+  let dir = tempfile::tempdir().unwrap();
+  let drafts_dir = dir.path().join(".forge").join("intent-drafts");
+  let intents_dir = dir.path().join(".forge").join("intents");
+  std::fs::create_dir_all(&drafts_dir).unwrap();
+
+  std::fs::write(
+    drafts_dir.join("tune-cache.md"),
+    "---\ntags: [backend, critical]\n---\n\nTune cache\n",
+  )
+  .unwrap();
+
+  pfl_forge::intent::draft::convert_drafts(dir.path()).unwrap();
+
+  let intents = Intent::fetch_all(&intents_dir).unwrap();
+  assert_eq!(intents[0].tags, vec!["backend", "critical"]);
+  assert!(intents[0].matches_tags(&["critical".to_string()]));
+  assert!(intents[0].matches_tags(&[]));
+  assert!(!intents[0].matches_tags(&["frontend".to_string()]));
+}
+
 #[test]
 fn 既存intentがある場合はdraftをスキップする() {
   let dir = tempfile::tempdir().unwrap();
@@ -246,3 +311,122 @@ fn md以外のファイルをスキップする_drafts() {
   // txt file should still be there
   assert!(drafts_dir.join("notes.txt").exists());
 }
+
+// --- reject ---
+
+#[test]
+fn rejectしたintentはrejectedで保存されapproveで戻せる() {
+  let mut intent = Intent::synthetic("T", "B");
+  intent.reject();
+  let yaml = serde_yaml::to_string(&intent).unwrap();
+  assert!(yaml.contains("status: rejected"));
+
+  let mut parsed: Intent = serde_yaml::from_str(&yaml).unwrap();
+  assert_eq!(parsed.status, IntentStatus::Rejected);
+  parsed.approve();
+  assert_eq!(parsed.status, IntentStatus::Approved);
+}
+
+// --- plan approval ---
+
+#[test]
+fn レビュー待ちのplanはapproveで承認済みになる() {
+  let yaml = "title: T\nbody: B\nsource: human\nstatus: blocked\nplan_approval: pending\n";
+  let mut intent: Intent = serde_yaml::from_str(yaml).unwrap();
+  assert!(intent.awaiting_plan_approval());
+
+  intent.approve();
+
+  assert_eq!(intent.status, IntentStatus::Approved);
+  assert_eq!(intent.plan_approval, Some(PlanApproval::Approved));
+  assert!(!intent.awaiting_plan_approval());
+}
+
+#[test]
+fn plan未到達のintentはapproveしてもplan_approvalを持たない() {
+  let mut intent = Intent::synthetic("T", "B");
+  intent.approve();
+  assert_eq!(intent.status, IntentStatus::Approved);
+  assert!(intent.plan_approval.is_none());
+  assert!(!serde_yaml::to_string(&intent)
+    .unwrap()
+    .contains("plan_approval"));
+}
+
+// --- acceptance criteria ---
+
+#[test]
+fn issueテンプレートのセクションを抽出する() {
+  let body = "\
+The login form accepts invalid emails.
+
+## Steps to reproduce
+
+1. Open /login
+2. Enter `foo`
+
+## Expected behavior
+
+The form shows a validation error.
+
+## Acceptance criteria
+
+- [ ] Invalid emails are rejected
+- [x] Valid emails still log in
+
+## Notes
+
+Unrelated.
+";
+  let sections = pfl_forge::intent::criteria::parse(body);
+  assert_eq!(
+    sections.steps_to_reproduce.as_deref(),
+    Some("1. Open /login\n2. Enter `foo`")
+  );
+  assert_eq!(
+    sections.expected_behavior.as_deref(),
+    Some("The form shows a validation error.")
+  );
+  assert_eq!(
+    sections.acceptance_criteria,
+    vec!["Invalid emails are rejected", "Valid emails still log in"]
+  );
+}
+
+#[test]
+fn リストでない受け入れ条件は1項目として扱う() {
+  let body = "### Acceptance Criteria:\n\nThe page loads in under a second.\n";
+  let sections = pfl_forge::intent::criteria::parse(body);
+  assert_eq!(
+    sections.acceptance_criteria,
+    vec!["The page loads in under a second."]
+  );
+}
+
+#[test]
+fn テンプレート見出しがなければ空になる() {
+  let sections = pfl_forge::intent::criteria::parse("Fix validation\n\n## Context\n\nSee #12");
+  assert!(sections.is_empty());
+  assert_eq!(sections.render(), "");
+}
+
+#[test]
+fn child_rollupはrejectedを除いて完了数と未完了の子を集計する() {
+  let dir = tempfile::tempdir().unwrap();
+  for (id, status) in [("a", "done"), ("b", "error"), ("c", "rejected")] {
+    std::fs::write(
+      dir.path().join(format!("{id}.yaml")),
+      format!("title: {id}\nbody: ''\nsource: human\nstatus: {status}\nparent: p\n"),
+    )
+    .unwrap();
+  }
+  let intents = Intent::fetch_all(dir.path()).unwrap();
+  let children: Vec<&Intent> = intents.iter().collect();
+
+  let rollup = Intent::child_rollup(&children);
+
+  assert_eq!(rollup.done, 1);
+  assert_eq!(rollup.counted, 2);
+  let unfinished: Vec<&str> = rollup.unfinished.iter().map(|c| c.id()).collect();
+  assert_eq!(unfinished, vec!["b"]);
+}
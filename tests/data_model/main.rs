@@ -1,4 +1,6 @@
+mod export;
 mod history;
+mod html_report;
 mod intent;
 mod observation;
 mod task;
@@ -2,7 +2,7 @@ use pfl_forge::agent::analyze::TaskSpec;
 use pfl_forge::claude::model::{Complexity, OPUS, SONNET};
 use pfl_forge::config::ModelSettings;
 use pfl_forge::intent::registry::Intent;
-use pfl_forge::task::{Task, WorkStatus};
+use pfl_forge::task::{self, Task, WorkStatus};
 
 fn sample_intent() -> Intent {
   serde_yaml::from_str("title: Test task\nbody: Implement feature X\nsource: human\n").unwrap()
@@ -18,6 +18,7 @@ fn sample_spec() -> TaskSpec {
     implementation_steps: vec!["Add module".into(), "Write tests".into()],
     context: "Background context".into(),
     depends_on: vec![],
+    checks: None,
   }
 }
 
@@ -51,6 +52,24 @@ fn from_specでステータスがpendingになる() {
   assert_eq!(task.status, WorkStatus::Pending);
 }
 
+// --- resume ---
+
+#[test]
+fn 再開時に失敗タスクはpendingに戻すがリトライ上限に達したものは失敗のまま() {
+  let intent = sample_intent();
+  let spec = sample_spec();
+  let mut failed = Task::from_spec(&intent, &spec);
+  failed.status = WorkStatus::Failed;
+  let mut exhausted = failed.clone();
+  exhausted.retries_exhausted = true;
+  let mut tasks = vec![failed, exhausted];
+
+  task::reset_interrupted(&mut tasks);
+
+  assert_eq!(tasks[0].status, WorkStatus::Pending);
+  assert_eq!(tasks[1].status, WorkStatus::Failed);
+}
+
 // --- complexity ---
 
 #[test]
@@ -88,7 +107,7 @@ fn tasks_yamlの書き込みと読み込みが往復する() {
 
   let dir = tempfile::tempdir().unwrap();
   let repo_path = dir.path();
-  pfl_forge::task::write_all_tasks(repo_path, "test-intent", &[task.clone()]).unwrap();
+  pfl_forge::task::write_all_tasks(repo_path, "test-intent", std::slice::from_ref(&task)).unwrap();
 
   let loaded = pfl_forge::task::read_all_tasks(repo_path, "test-intent").unwrap();
   assert_eq!(loaded.len(), 1);
@@ -111,3 +130,24 @@ fn tasks_existが正しく判定する() {
 
   assert!(pfl_forge::task::tasks_exist(repo_path, "exists"));
 }
+
+#[test]
+fn breakdownは完了数と失敗したtask_idを集計する() {
+  let task = |id: &str, status: &str| -> Task {
+    serde_yaml::from_str(&format!(
+      "id: {id}\ntitle: {id}\nintent_id: x\nstatus: {status}\ncomplexity: low\nplan: p\nrelevant_files: []\nimplementation_steps: []\ncontext: ''\n"
+    ))
+    .unwrap()
+  };
+  let tasks = [
+    task("a", "completed"),
+    task("b", "failed"),
+    task("c", "pending"),
+  ];
+
+  let breakdown = task::breakdown(&tasks);
+
+  assert_eq!(breakdown.completed, 1);
+  assert_eq!(breakdown.total, 3);
+  assert_eq!(breakdown.failed, vec!["b"]);
+}
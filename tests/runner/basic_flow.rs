@@ -1,5 +1,9 @@
-use pfl_forge::intent::registry::IntentStatus;
-use pfl_forge::knowledge::history::{self, Outcome};
+use pfl_forge::claude::model;
+use pfl_forge::error::ErrorCategory;
+use pfl_forge::i18n::Locale;
+use pfl_forge::intent::registry::{IntentStatus, PlanApproval};
+use pfl_forge::knowledge::history::{self, Outcome, StepStatus};
+use pfl_forge::knowledge::run_report;
 use pfl_forge::runner;
 
 use crate::helpers::*;
@@ -11,6 +15,7 @@ fn approvedのintentのみ処理する() {
   let (_dir, repo) = setup_repo_with_intent("target");
   add_intent(&repo, "proposed-one", "proposed");
   add_intent(&repo, "done-one", "done");
+  add_intent(&repo, "rejected-one", "rejected");
   let config = default_config();
 
   let mock = MockClaude::with_sequence(vec![
@@ -43,6 +48,214 @@ fn approved_intentがなければ空を返す() {
   assert_eq!(mock.call_count(), 0);
 }
 
+#[test]
+fn 停止中は承認済みintentも処理せず状態を残す() {
+  let (_dir, repo) = setup_repo_with_intent("paused");
+  let config = default_config();
+  runner::watch::set_disabled(&repo, true).unwrap();
+
+  let mock = MockClaude::with_sequence(vec![]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert!(results.is_empty());
+  assert_eq!(mock.call_count(), 0);
+  assert_eq!(load_intent(&repo, "paused").status, IntentStatus::Approved);
+
+  runner::watch::set_disabled(&repo, false).unwrap();
+  assert!(!runner::watch::is_disabled(&config, &repo));
+}
+
+#[test]
+fn 設定のenabled_falseでも処理しない() {
+  let (_dir, repo) = setup_repo_with_intent("paused");
+  let mut config = default_config();
+  config.enabled = false;
+
+  let mock = MockClaude::with_sequence(vec![]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert!(results.is_empty());
+  assert_eq!(mock.call_count(), 0);
+}
+
+#[test]
+fn only_tagsに一致するintentだけを処理する() {
+  let (_dir, repo) = setup_repo_with_intent("untagged");
+  let intents_dir = repo.join(".forge").join("intents");
+  std::fs::write(
+    intents_dir.join("tagged.yaml"),
+    "title: tagged\nbody: Body\nsource: human\nstatus: approved\ntags: [backend]\n",
+  )
+  .unwrap();
+  let mut config = default_config();
+  config.only_tags = vec!["backend".to_string()];
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].0, "tagged");
+  assert_eq!(
+    load_intent(&repo, "untagged").status,
+    IntentStatus::Approved
+  );
+}
+
+#[test]
+fn 指定したintentは承認前でも単独で処理する() {
+  let (_dir, repo) = setup_repo_with_intent("other");
+  add_intent(&repo, "on-demand", "proposed");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_intent(&config, &mock, &repo, "on-demand").unwrap();
+
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].0, "on-demand");
+  assert_eq!(results[0].1.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 3);
+  assert!(load_intent(&repo, "on-demand").run_id.is_some());
+  // The other approved intent is left for the next run
+  assert!(load_intent(&repo, "other").run_id.is_none());
+}
+
+#[test]
+fn 存在しないintentの指定はエラーになる() {
+  let (_dir, repo) = setup_repo_with_intent("other");
+  let mock = MockClaude::with_sequence(vec![]);
+
+  let err = runner::run_intent(&default_config(), &mock, &repo, "missing").unwrap_err();
+
+  assert!(err.to_string().contains("intent not found: missing"));
+  assert_eq!(mock.call_count(), 0);
+}
+
+#[test]
+fn リストのintentだけを指定順に処理する() {
+  let (_dir, repo) = setup_repo_with_intent("not-listed");
+  add_intent(&repo, "second", "proposed");
+  add_intent(&repo, "first", "error");
+  let mut config = default_config();
+  config.parallel_workers = 1;
+
+  let ids = runner::parse_intent_list("# planning 10/15\nfirst\n\nsecond  # after first\nfirst\n");
+  assert_eq!(ids, vec!["first", "second"]);
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_listed_intents(&config, &mock, &repo, &ids).unwrap();
+
+  let order: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+  assert_eq!(order, vec!["first", "second"]);
+  assert!(load_intent(&repo, "not-listed").run_id.is_none());
+}
+
+#[test]
+fn リストに存在しないintentがあれば何も処理しない() {
+  let (_dir, repo) = setup_repo_with_intent("listed");
+  let mock = MockClaude::with_sequence(vec![]);
+  let ids = vec!["listed".to_string(), "typo".to_string()];
+
+  let err = runner::run_listed_intents(&default_config(), &mock, &repo, &ids).unwrap_err();
+
+  assert!(err.to_string().contains("intent not found: typo"));
+  assert_eq!(mock.call_count(), 0);
+  assert!(load_intent(&repo, "listed").run_id.is_none());
+}
+
+#[test]
+fn sandbox時はimplementの変更をホストでコミットする() {
+  let (_dir, repo) = setup_repo_with_intent("sandboxed");
+  let mut intent = load_intent(&repo, "sandboxed");
+  for (key, value) in [("user.name", "test"), ("user.email", "test@test.com")] {
+    std::process::Command::new("git")
+      .args(["config", key, value])
+      .current_dir(&repo)
+      .output()
+      .unwrap();
+  }
+  let mut config = default_config();
+  config.claude.sandbox = Some(serde_yaml::from_str("image: forge-worker:latest").unwrap());
+
+  let mock = UncommittingClaude(MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]));
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let show = std::process::Command::new("git")
+    .args(["show", "forge/sandboxed:sandboxed.txt"])
+    .current_dir(&repo)
+    .output()
+    .unwrap();
+  assert!(show.status.success());
+}
+
+#[test]
+fn 全intentが同じエラーで失敗したら残りのキューを処理せずに中断する() {
+  let (_dir, repo) = setup_repo_with_intent("ff-a");
+  for id in ["ff-b", "ff-c", "ff-d"] {
+    add_intent(&repo, id, "approved");
+  }
+  let mut config = default_config();
+  config.parallel_workers = 1;
+  config.fast_fail_after = 2;
+
+  let mock = MockClaude::with_sequence(vec![error_response(
+    "failed to spawn claude: No such file or directory",
+  )]);
+
+  let err = runner::run_intents(&config, &mock, &repo, false).unwrap_err();
+
+  assert_eq!(mock.call_count(), 2);
+  assert_eq!(err.category(), ErrorCategory::Environment);
+  let message = err.to_string();
+  assert!(message.contains("all 2 intent(s) failed with the same error"));
+  assert!(message.contains("claude.bin"));
+  assert!(message.contains("2 intent(s) left approved"));
+  let left = ["ff-a", "ff-b", "ff-c", "ff-d"]
+    .iter()
+    .filter(|id| load_intent(&repo, id).status == IntentStatus::Approved)
+    .count();
+  assert_eq!(left, 4);
+}
+
+#[test]
+fn 失敗理由が異なればキューを最後まで処理する() {
+  let (_dir, repo) = setup_repo_with_intent("mixed-a");
+  add_intent(&repo, "mixed-b", "approved");
+  add_intent(&repo, "mixed-c", "approved");
+  let mut config = default_config();
+  config.parallel_workers = 1;
+  config.fast_fail_after = 2;
+
+  let mock = MockClaude::with_sequence(vec![
+    error_response("first error"),
+    error_response("second error"),
+    error_response("third error"),
+  ]);
+
+  runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(mock.call_count(), 3);
+}
+
 #[test]
 fn dry_runではanalyzeを実行しない() {
   let (_dir, repo) = setup_repo_with_intent("dry-target");
@@ -217,6 +430,32 @@ fn レビュー却下時にimplement_reviewサイクルをリトライする() {
   assert_eq!(mock.call_count(), 5);
 }
 
+#[test]
+fn 再レビューに前回のissueと再実装の差分とremediationを渡す() {
+  let (_dir, repo) = setup_repo_with_intent("re-review");
+  let mut intent = load_intent(&repo, "re-review");
+  let mut config = default_config();
+  config.max_review_retries = 1;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("First attempt"),
+    json_response(rejected_review_json()),
+    // JSON-escaped newlines, as in real CLI output
+    raw_response("Fixed.\\n\\n## Remediation\\n1. Added the missing check"),
+    json_response(approved_review_json()),
+  ]);
+
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let calls = mock.captured_calls();
+  assert!(!calls[2].prompt.contains("## Previous Review"));
+  let re_review = &calls[4].prompt;
+  assert!(re_review.contains("## Previous Review"), "{re_review}");
+  assert!(re_review.contains("## Changes Since Previous Attempt"));
+  assert!(re_review.contains("## Remediation Notes\n\n1. Added the missing check"));
+}
+
 #[test]
 fn リトライ上限でタスクが失敗する() {
   let (_dir, repo) = setup_repo_with_intent("exhaust");
@@ -350,10 +589,11 @@ fn rebase失敗時に再実装する() {
   let mut intent = load_intent(&repo, "conflict-rebase");
   let config = default_config();
 
-  // analyze → implement → (rebase fails, reimpl) → implement → review(approved)
+  // analyze → implement → (rebase fails, resolution fails, reimpl) → implement → review(approved)
   let mock = MockClaude::with_sequence(vec![
     json_response(analysis_json()),        // analyze
     raw_response("First attempt"),         // implement (on conflicting branch)
+    raw_response("Cannot resolve"),        // conflict resolution leaves markers
     raw_response("Reimplementation"),      // implement (fresh from updated main)
     json_response(approved_review_json()), // review
   ]);
@@ -362,8 +602,64 @@ fn rebase失敗時に再実装する() {
 
   assert_eq!(result.outcome, Outcome::Success);
   assert_eq!(intent.status, IntentStatus::Done);
-  // 4 Claude calls: analyze + implement + reimpl + review
-  assert_eq!(mock.call_count(), 4);
+  // 5 Claude calls: analyze + implement + resolve + reimpl + review
+  assert_eq!(mock.call_count(), 5);
+}
+
+#[test]
+fn rebaseのコンフリクトをその場で解決して再実装しない() {
+  let (_dir, repo) = setup_repo_with_conflict("conflict-resolve");
+  let mut intent = load_intent(&repo, "conflict-resolve");
+  let config = default_config();
+
+  let claude = ResolvingClaude(MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("First attempt"),
+    raw_response("Resolved"),
+    json_response(approved_review_json()),
+  ]));
+
+  let result = runner::process_intent(&mut intent, &config, &claude, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  // analyze + implement + resolve + review: no reimplementation
+  let calls = claude.0.captured_calls();
+  assert_eq!(calls.len(), 4);
+  assert!(calls[2].prompt.contains("<<<<<<<"));
+  assert!(calls[2].prompt.contains("main change"));
+  assert!(calls[2].prompt.contains("branch commit"));
+
+  let entry = history::load(&repo, "conflict-resolve").unwrap();
+  let resolve = entry
+    .step_results
+    .iter()
+    .find(|s| s.step == "resolve_conflicts")
+    .unwrap();
+  assert_eq!(resolve.status, StepStatus::Success);
+  let tasks = pfl_forge::task::read_all_tasks(&repo, "conflict-resolve").unwrap();
+  assert_eq!(tasks[0].rebase_conflicts, 0);
+}
+
+#[test]
+fn コンフリクト解決後にチェックを再実行する() {
+  let (dir, repo) = setup_repo_with_conflict("conflict-recheck");
+  let mut intent = load_intent(&repo, "conflict-recheck");
+  let runs = dir.path().join("lint-runs.txt");
+  let mut config = default_config();
+  config.lint_command = Some(format!("echo run >> {}", runs.display()));
+
+  let claude = ResolvingClaude(MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("First attempt"),
+    raw_response("Resolved"),
+    json_response(approved_review_json()),
+  ]));
+
+  let result = runner::process_intent(&mut intent, &config, &claude, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  // Once after implement, once more on the resolved rebase
+  assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 2);
 }
 
 #[test]
@@ -372,10 +668,11 @@ fn 再実装失敗時にエスカレートする() {
   let mut intent = load_intent(&repo, "conflict-escalate");
   let config = default_config();
 
-  // analyze → implement → (rebase fails) → reimpl fails → escalated
+  // analyze → implement → (rebase fails, resolution fails) → reimpl fails → escalated
   let mock = MockClaude::with_sequence(vec![
     json_response(analysis_json()),            // analyze
     raw_response("First attempt"),             // implement (on conflicting branch)
+    raw_response("Cannot resolve"),            // conflict resolution leaves markers
     error_response("reimplementation failed"), // reimpl fails
   ]);
 
@@ -389,6 +686,152 @@ fn 再実装失敗時にエスカレートする() {
     .contains("reimplementation failed"));
 }
 
+#[test]
+fn 再実装失敗時に引き継ぎ資料を書き出して通知コマンドに渡す() {
+  let (_dir, repo) = setup_repo_with_conflict("conflict-bundle");
+  let mut intent = load_intent(&repo, "conflict-bundle");
+  let mut config = default_config();
+  config.escalation_command =
+    Some("cp \"$FORGE_ESCALATION_FILE\" posted-$FORGE_INTENT_ID.md".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("First attempt"),
+    raw_response("Both sides rewrite the same line"),
+    error_response("reimplementation failed"),
+  ]);
+
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let bundle =
+    std::fs::read_to_string(runner::escalation::dir(&repo).join("conflict-bundle.md")).unwrap();
+  assert!(bundle.contains("- Branch: forge/conflict-bundle"));
+  assert!(bundle.contains("## Plan\n\nWrite tests"));
+  assert!(bundle.contains("## Conflicting Files\n\n- file.txt"));
+  assert!(bundle.contains("Both sides rewrite the same line"));
+  assert!(bundle.contains("reimplementation failed"));
+  let posted = std::fs::read_to_string(repo.join("posted-conflict-bundle.md")).unwrap();
+  assert_eq!(posted, bundle);
+}
+
+#[test]
+fn max_rebase_retriesが0ならコンフリクトで再実装せず失敗する() {
+  let (_dir, repo) = setup_repo_with_conflict("conflict-no-retry");
+  let mut intent = load_intent(&repo, "conflict-no-retry");
+  let mut config = default_config();
+  config.max_rebase_retries = 0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("First attempt"),
+    raw_response("Cannot resolve"),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Escalated);
+  assert_eq!(result.failure_reason.as_deref(), Some("rebase conflict"));
+  // analyze + implement + resolve only: no reimplementation
+  assert_eq!(mock.call_count(), 3);
+  let tasks = pfl_forge::task::read_all_tasks(&repo, "conflict-no-retry").unwrap();
+  assert_eq!(tasks[0].rebase_conflicts, 1);
+  assert!(tasks[0].retries_exhausted);
+  // No reimplementation was attempted, so nothing is escalated
+  assert!(!runner::escalation::dir(&repo).exists());
+}
+
+// --- Implement リトライ ---
+
+#[test]
+fn max_implement_retriesの範囲でimplementの失敗をリトライする() {
+  let (_dir, repo) = setup_repo_with_intent("impl-retry");
+  let mut intent = load_intent(&repo, "impl-retry");
+  let mut config = default_config();
+  config.max_implement_retries = 1;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("worker crashed"),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 4);
+  let tasks = pfl_forge::task::read_all_tasks(&repo, "impl-retry").unwrap();
+  assert_eq!(tasks[0].implement_failures, 1);
+  assert_eq!(tasks[0].errors.len(), 1);
+  assert!(!tasks[0].retries_exhausted);
+}
+
+#[test]
+fn implementのリトライを使い切るとエラーログ付きで失敗にする() {
+  let (_dir, repo) = setup_repo_with_intent("impl-exhausted");
+  let mut intent = load_intent(&repo, "impl-exhausted");
+  let mut config = default_config();
+  config.max_implement_retries = 1;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("first crash"),
+    error_response("second crash"),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert_eq!(intent.status, IntentStatus::Error);
+  let reason = result.failure_reason.unwrap();
+  assert!(reason.contains("first crash"), "{reason}");
+  assert!(reason.contains("second crash"), "{reason}");
+  assert_eq!(mock.call_count(), 3);
+
+  let tasks = pfl_forge::task::read_all_tasks(&repo, "impl-exhausted").unwrap();
+  assert_eq!(tasks[0].status, pfl_forge::task::WorkStatus::Failed);
+  assert_eq!(tasks[0].implement_failures, 2);
+  assert!(tasks[0].retries_exhausted);
+
+  // A later run does not retry the exhausted task
+  let mock = MockClaude::with_sequence(vec![]);
+  let mut intent = load_intent(&repo, "impl-exhausted");
+  intent.status = IntentStatus::Approved;
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(mock.call_count(), 0);
+}
+
+#[test]
+fn 再承認でリトライを使い切ったtaskをもう一度実行する() {
+  let (_dir, repo) = setup_repo_with_intent("impl-reapprove");
+  let mut intent = load_intent(&repo, "impl-reapprove");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("implement timed out"),
+  ]);
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(result.outcome, Outcome::Failed);
+
+  let mut intent = load_intent(&repo, "impl-reapprove");
+  runner::approve_intent(&repo, &mut intent).unwrap();
+  let tasks = pfl_forge::task::read_all_tasks(&repo, "impl-reapprove").unwrap();
+  assert_eq!(tasks[0].implement_failures, 0);
+  assert!(!tasks[0].retries_exhausted);
+
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  // Resumed from the saved tasks: implement + review, no new analyze
+  assert_eq!(mock.call_count(), 2);
+  assert_eq!(intent.status, IntentStatus::Done);
+}
+
 // --- History 記録 ---
 
 #[test]
@@ -433,30 +876,183 @@ fn historyにstep_resultsが含まれる() {
   assert_eq!(entry.flow, vec!["analyze", "implement", "review"]);
 }
 
-// --- Resume ---
-
 #[test]
-fn sessions付きapproved_intentを再開する() {
-  // sessions.analyze + worktree with tasks.yaml → skip analyze
-  let (_dir, repo) = setup_repo_with_intent("resume-target");
+fn historyのstep_resultsに状態_モデル_ターン数_コストを記録する() {
+  let (_dir, repo) = setup_repo_with_intent("step-cost");
+  let mut intent = load_intent(&repo, "step-cost");
   let config = default_config();
 
-  // Overwrite the intent as approved with sessions.analyze set
-  add_approved_intent_with_sessions(
-    &repo,
-    "resume-target",
-    Some(ResumeIntentOptions {
-      analyze_session: Some("prev-analyze-session".to_string()),
-      implement_session: None,
-    }),
-  );
-
-  // Create worktree with tasks.yaml
-  setup_worktree_with_tasks(&repo, &config, "resume-target");
-
-  // Only implement + review needed (no analyze)
   let mock = MockClaude::with_sequence(vec![
-    raw_response("Implementation done"),
+    json_response(analysis_json()),
+    Ok(r#"{"result": "Done", "total_cost_usd": 0.5, "num_turns": 7}"#.to_string()),
+    json_response(approved_review_json()),
+  ]);
+
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let dir = repo
+    .join(".forge")
+    .join("knowledge")
+    .join("history")
+    .join("step-cost");
+  let file = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+  let content = std::fs::read_to_string(file.path()).unwrap();
+  assert!(content.contains("status: success"), "{content}");
+
+  let entry = history::load(&repo, "step-cost").unwrap();
+  let implement = entry
+    .step_results
+    .iter()
+    .find(|s| s.step == "implement")
+    .unwrap();
+  assert_eq!(implement.status, StepStatus::Success);
+  assert_eq!(implement.model.as_deref(), Some(model::SONNET));
+  let meta = implement.metadata.as_ref().unwrap();
+  assert_eq!(meta.num_turns, Some(7));
+  assert_eq!(meta.cost_usd, Some(0.5));
+  assert_eq!(entry.cost_usd(), 0.5);
+
+  let rebase = entry
+    .step_results
+    .iter()
+    .find(|s| s.step == "rebase")
+    .unwrap();
+  assert!(rebase.model.is_none());
+}
+
+#[test]
+fn 失敗したステップはstatus_failedで記録する() {
+  let (_dir, repo) = setup_repo_with_intent("step-failed");
+  let mut intent = load_intent(&repo, "step-failed");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("implement crashed"),
+  ]);
+
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let entry = history::load(&repo, "step-failed").unwrap();
+  let implement = entry
+    .step_results
+    .iter()
+    .find(|s| s.step == "implement")
+    .unwrap();
+  assert_eq!(implement.status, StepStatus::Failed);
+}
+
+// --- Run Report ---
+
+#[test]
+fn process_intentがエラーを返したintentもrun_reportに失敗として載る() {
+  let (_dir, repo) = setup_repo_with_intent("report-error");
+  let mut config = default_config();
+  config.flows.insert(
+    "default".into(),
+    vec!["analyze".into(), "no-such-step".into()],
+  );
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+  let report = runner::build_run_report(&repo, &results);
+
+  assert_eq!(report.intents.len(), 1);
+  let entry = &report.intents[0];
+  assert_eq!(entry.intent_id, "report-error");
+  assert_eq!(entry.outcome, Outcome::Failed);
+  assert!(entry
+    .failure_reason
+    .as_deref()
+    .unwrap()
+    .contains("no-such-step"));
+}
+
+#[test]
+fn run_reportに結果と次のアクションが含まれる() {
+  let (_dir, repo) = setup_repo_with_intent("report-fail");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("implement crashed"),
+  ]);
+
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+  let report = runner::build_run_report(&repo, &results);
+  run_report::write(&repo, &report).unwrap();
+
+  let loaded = run_report::load(&repo).unwrap().unwrap();
+  assert_eq!(loaded.intents.len(), 1);
+  let entry = &loaded.intents[0];
+  assert_eq!(entry.intent_id, "report-fail");
+  assert_eq!(entry.title, "Fix bug");
+  assert_eq!(entry.outcome, Outcome::Failed);
+  assert!(entry
+    .failure_reason
+    .as_deref()
+    .unwrap()
+    .contains("implement failed"));
+  assert!(entry
+    .next_action
+    .as_deref()
+    .unwrap()
+    .contains("pfl-forge approve report-fail"));
+}
+
+#[test]
+fn 同じrunで処理したintentとhistoryとreportにrun_idを記録する() {
+  let (_dir, repo) = setup_repo_with_intent("run-a");
+  add_intent(&repo, "run-b", "approved");
+  let mut config = default_config();
+  config.parallel_workers = 1; // Sequential: mock responses depend on order
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    error_response("implement crashed"),
+    json_response(analysis_json()),
+    error_response("implement crashed"),
+  ]);
+
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+  let report = runner::build_run_report(&repo, &results);
+
+  let run_id = report.run_id.clone().unwrap();
+  for id in ["run-a", "run-b"] {
+    assert_eq!(
+      load_intent(&repo, id).run_id.as_deref(),
+      Some(run_id.as_str())
+    );
+  }
+  let entries = history::load_by_run(&repo, &run_id).unwrap();
+  assert_eq!(entries.len(), 2);
+}
+
+// --- Resume ---
+
+#[test]
+fn sessions付きapproved_intentを再開する() {
+  // sessions.analyze + worktree with tasks.yaml → skip analyze
+  let (_dir, repo) = setup_repo_with_intent("resume-target");
+  let config = default_config();
+
+  // Overwrite the intent as approved with sessions.analyze set
+  add_approved_intent_with_sessions(
+    &repo,
+    "resume-target",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: None,
+    }),
+  );
+
+  // Create worktree with tasks.yaml
+  setup_worktree_with_tasks(&repo, &config, "resume-target");
+
+  // Only implement + review needed (no analyze)
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Implementation done"),
     json_response(approved_review_json()),
   ]);
 
@@ -530,6 +1126,11 @@ fn tasksファイルあり_worktreeなしならanalyzeスキップしworktree作
     implementation_steps: vec!["Step 1".to_string()],
     context: String::new(),
     depends_on: vec![],
+    checks: None,
+    implement_failures: 0,
+    rebase_conflicts: 0,
+    errors: vec![],
+    retries_exhausted: false,
   }];
   pfl_forge::task::write_all_tasks(&repo, "resume-from-tasks", &tasks).unwrap();
 
@@ -554,6 +1155,113 @@ fn tasksファイルあり_worktreeなしならanalyzeスキップしworktree作
   assert!(!steps.contains(&"analyze"));
 }
 
+#[test]
+fn implement済みでコミットがあればreviewから再開する() {
+  let (_dir, repo) = setup_repo_with_intent("resume-stage");
+  add_approved_intent_with_sessions(
+    &repo,
+    "resume-stage",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: Some("prev-implement-session".to_string()),
+    }),
+  );
+  let config = default_config();
+  let worktree = setup_worktree_with_tasks(&repo, &config, "resume-stage");
+  commit_in_worktree(&worktree, "file.txt", "implemented\n");
+  set_task_status(
+    &repo,
+    "resume-stage",
+    pfl_forge::task::WorkStatus::Implemented,
+  );
+
+  // Only review (implement already committed)
+  let mock = MockClaude::with_sequence(vec![json_response(approved_review_json())]);
+
+  let mut intent = load_intent(&repo, "resume-stage");
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 1);
+  let steps: Vec<&str> = result
+    .step_results
+    .iter()
+    .map(|s| s.step.as_str())
+    .collect();
+  assert!(!steps.contains(&"implement"));
+  assert!(steps.contains(&"review"));
+}
+
+#[test]
+fn implement済みでもコミットがなければimplementからやり直す() {
+  let (_dir, repo) = setup_repo_with_intent("resume-no-commits");
+  add_approved_intent_with_sessions(
+    &repo,
+    "resume-no-commits",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: None,
+    }),
+  );
+  let config = default_config();
+  setup_worktree_with_tasks(&repo, &config, "resume-no-commits");
+  set_task_status(
+    &repo,
+    "resume-no-commits",
+    pfl_forge::task::WorkStatus::Implemented,
+  );
+
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let mut intent = load_intent(&repo, "resume-no-commits");
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 2);
+}
+
+#[test]
+fn 完了済みtaskは再開時に実行しない() {
+  let (_dir, repo) = setup_repo_with_intent("resume-completed");
+  add_approved_intent_with_sessions(
+    &repo,
+    "resume-completed",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: None,
+    }),
+  );
+  let config = default_config();
+  setup_worktree_with_tasks(&repo, &config, "resume-completed");
+  let mut tasks = pfl_forge::task::read_all_tasks(&repo, "resume-completed").unwrap();
+  tasks[0].status = pfl_forge::task::WorkStatus::Completed;
+  let mut second = tasks[0].clone();
+  second.id = "second".to_string();
+  second.status = pfl_forge::task::WorkStatus::Pending;
+  second.depends_on = vec!["resume-completed".to_string()];
+  tasks.push(second);
+  pfl_forge::task::write_all_tasks(&repo, "resume-completed", &tasks).unwrap();
+
+  // implement + review for the second task only
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let mut intent = load_intent(&repo, "resume-completed");
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 2);
+  let saved = pfl_forge::task::read_all_tasks(&repo, "resume-completed").unwrap();
+  assert!(saved
+    .iter()
+    .all(|t| t.status == pfl_forge::task::WorkStatus::Completed));
+}
+
 #[test]
 fn analyze完了後にtasksファイルがメインリポに作成される() {
   let (_dir, repo) = setup_repo_with_intent("persist-tasks");
@@ -688,7 +1396,9 @@ fn resume時にimplementにresumeセッションを渡す() {
       implement_session: Some(prev_session.to_string()),
     }),
   );
-  setup_worktree_with_tasks(&repo, &config, "session-resume");
+  let worktree = setup_worktree_with_tasks(&repo, &config, "session-resume");
+  // The last snapshot was taken mid-implement, at the worktree's current HEAD
+  write_interrupted_checkpoint(&repo, "session-resume", &worktree);
 
   let mock = MockClaude::with_sequence(vec![
     raw_response("Done"),
@@ -711,6 +1421,89 @@ fn resume時にimplementにresumeセッションを渡す() {
   }
 }
 
+fn write_interrupted_checkpoint(
+  repo: &std::path::Path,
+  intent_id: &str,
+  worktree: &std::path::Path,
+) {
+  let checkpoint = runner::checkpoint::Checkpoint {
+    task_id: intent_id.to_string(),
+    step: "implement".into(),
+    head: Some(pfl_forge::git::branch::head_commit(worktree).unwrap()),
+    commits: 0,
+    elapsed_secs: 30,
+    num_turns: None,
+    finished: false,
+    updated_at: "2026-01-01T00:00:00Z".into(),
+  };
+  runner::checkpoint::write(repo, intent_id, &checkpoint).unwrap();
+}
+
+#[test]
+fn 失敗で終わったimplementのcheckpointからはセッションを再開しない() {
+  let (_dir, repo) = setup_repo_with_intent("session-failed");
+  let config = default_config();
+  add_approved_intent_with_sessions(
+    &repo,
+    "session-failed",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: Some("prev-implement-session-id".to_string()),
+    }),
+  );
+  let worktree = setup_worktree_with_tasks(&repo, &config, "session-failed");
+  // A failed or timed-out call returns no turn count, but the step did finish
+  write_interrupted_checkpoint(&repo, "session-failed", &worktree);
+  let mut checkpoint = runner::checkpoint::read(&repo, "session-failed").unwrap();
+  checkpoint.finished = true;
+  runner::checkpoint::write(&repo, "session-failed", &checkpoint).unwrap();
+
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let mut intent = load_intent(&repo, "session-failed");
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let calls = mock.captured_calls();
+  match &calls[0].session {
+    CapturedSession::New(id) => assert!(!id.is_empty()),
+    other => panic!("expected New session for implement, got {:?}", other),
+  }
+}
+
+#[test]
+fn checkpoint後にheadが変わっていればimplementを新しいセッションでやり直す() {
+  let (_dir, repo) = setup_repo_with_intent("session-restart");
+  let config = default_config();
+  add_approved_intent_with_sessions(
+    &repo,
+    "session-restart",
+    Some(ResumeIntentOptions {
+      analyze_session: Some("prev-analyze-session".to_string()),
+      implement_session: Some("prev-implement-session-id".to_string()),
+    }),
+  );
+  let worktree = setup_worktree_with_tasks(&repo, &config, "session-restart");
+  write_interrupted_checkpoint(&repo, "session-restart", &worktree);
+  commit_in_worktree(&worktree, "moved.txt", "committed after the snapshot");
+
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let mut intent = load_intent(&repo, "session-restart");
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let calls = mock.captured_calls();
+  match &calls[0].session {
+    CapturedSession::New(id) => assert!(!id.is_empty()),
+    other => panic!("expected New session for implement, got {:?}", other),
+  }
+}
+
 #[test]
 fn session_idがintent_yamlにspawn前に書き込まれる() {
   let (_dir, repo) = setup_repo_with_intent("session-write");
@@ -750,3 +1543,229 @@ fn session_idがintent_yamlにspawn前に書き込まれる() {
     Some(analyze_sid.as_str())
   );
 }
+
+// --- Checkpoint ---
+
+#[test]
+fn implement後のcheckpointを失敗時に残す() {
+  let (_dir, repo) = setup_repo_with_intent("checkpoint-kept");
+  let mut intent = load_intent(&repo, "checkpoint-kept");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    Ok(r#"{"result": "Done", "num_turns": 7}"#.to_string()),
+    error_response("review crashed"),
+  ]);
+
+  runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  let cp = runner::checkpoint::read(&repo, "checkpoint-kept").unwrap();
+  assert_eq!(cp.step, "implement");
+  assert_eq!(cp.task_id, "checkpoint-kept");
+  assert_eq!(cp.num_turns, Some(7));
+  assert!(cp.head.is_some());
+}
+
+#[test]
+fn intent完了時にcheckpointを削除する() {
+  let (_dir, repo) = setup_repo_with_intent("checkpoint-cleared");
+  let mut intent = load_intent(&repo, "checkpoint-cleared");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert!(runner::checkpoint::read(&repo, "checkpoint-cleared").is_none());
+}
+
+// --- Dry-run Report ---
+
+#[test]
+fn dry_runレポートはanalyze結果を集め状態を変えない() {
+  let (_dir, repo) = setup_repo_with_intent("multi");
+  add_intent(&repo, "unclear", "approved");
+  let mut config = default_config();
+  config.parallel_workers = 1;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(multi_task_analysis_json()),
+    json_response(r#"{"outcome":"needs_clarification","clarifications":["Which API?"]}"#),
+  ]);
+
+  let report = runner::dry_run::plan(&config, &mock, &repo).unwrap();
+
+  assert_eq!(report.intents.len(), 2);
+  let multi = &report.intents[0];
+  assert_eq!(multi.intent_id, "multi");
+  assert_eq!(multi.outcome, "tasks");
+  assert_eq!(multi.effort.as_deref(), Some("low"));
+  assert_eq!(multi.tasks.len(), 2);
+  assert_eq!(multi.tasks[0].title, "First task");
+  assert_eq!(multi.tasks[0].relevant_files, vec!["a.rs"]);
+  assert_eq!(multi.tasks[0].steps, 1);
+  let unclear = &report.intents[1];
+  assert_eq!(unclear.outcome, "needs_clarification");
+  assert_eq!(unclear.notes, vec!["Which API?"]);
+
+  // Analyze only: no tasks written and intents stay approved
+  assert!(!pfl_forge::task::tasks_exist(&repo, "multi"));
+  assert_eq!(load_intent(&repo, "multi").status, IntentStatus::Approved);
+  assert_eq!(load_intent(&repo, "unclear").status, IntentStatus::Approved);
+}
+
+#[test]
+fn dry_runレポートを拡張子に応じてmarkdownかjsonで書き出す() {
+  let (dir, repo) = setup_repo_with_intent("target");
+  let config = default_config();
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+  let report = runner::dry_run::plan(&config, &mock, &repo).unwrap();
+
+  let md_path = dir.path().join("out").join("plan.md");
+  runner::dry_run::write(&md_path, &report, Locale::En).unwrap();
+  let md = std::fs::read_to_string(&md_path).unwrap();
+  assert!(md.starts_with("# Dry-run report"));
+  assert!(md.contains("## target — "));
+  assert!(md.contains("- Effort: low"));
+  assert!(md.contains("Write tests"));
+  assert!(md.contains("- `src/lib.rs`"));
+
+  let json_path = dir.path().join("plan.json");
+  runner::dry_run::write(&json_path, &report, Locale::En).unwrap();
+  let json: serde_json::Value =
+    serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+  assert_eq!(json["intents"][0]["intent_id"], "target");
+  assert_eq!(json["intents"][0]["tasks"][0]["complexity"], "low");
+
+  let ja = runner::dry_run::to_markdown(&report, Locale::Ja);
+  assert!(ja.starts_with("# Dry-run レポート"));
+  assert!(ja.contains("- 工数: low"));
+}
+
+// --- Plan Approval ---
+
+#[test]
+fn plan承認が必要ならanalyze後に止めapprove後はtasksから再開する() {
+  let (_dir, repo) = setup_repo_with_intent("gated");
+  let mut config = default_config();
+  config.require_plan_approval = true;
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Failed);
+  assert_eq!(
+    results[0].1.failure_reason.as_deref(),
+    Some("awaiting plan approval")
+  );
+  assert_eq!(mock.call_count(), 1);
+  let mut intent = load_intent(&repo, "gated");
+  assert_eq!(intent.status, IntentStatus::Blocked);
+  assert!(intent.awaiting_plan_approval());
+  assert!(pfl_forge::task::tasks_exist(&repo, "gated"));
+  let worktree = pfl_forge::git::worktree::path_for(&repo, &config.worktree_dir, "forge/gated");
+  assert!(!worktree.exists());
+
+  // Human approves the plan: the next run skips analyze
+  intent.approve();
+  runner::update_intent_file(&repo, &intent).unwrap();
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 2);
+  assert_eq!(load_intent(&repo, "gated").status, IntentStatus::Done);
+}
+
+#[test]
+fn 再analyzeで新しいtasksを書いたら以前のplan承認を外す() {
+  let (_dir, repo) = setup_repo_with_intent("regated");
+  let mut config = default_config();
+  config.require_plan_approval = true;
+  // 前回の計画は承認済みだが、tasks は残っていない
+  let mut intent = load_intent(&repo, "regated");
+  intent.plan_approval = Some(PlanApproval::Approved);
+  runner::update_intent_file(&repo, &intent).unwrap();
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(
+    results[0].1.failure_reason.as_deref(),
+    Some("awaiting plan approval")
+  );
+  assert_eq!(mock.call_count(), 1);
+  let intent = load_intent(&repo, "regated");
+  assert_eq!(intent.status, IntentStatus::Blocked);
+  assert!(intent.awaiting_plan_approval());
+}
+
+// --- Parent / Child Intents ---
+
+fn add_child_intent(repo: &std::path::Path, id: &str, parent: &str, status: &str) {
+  let intents_dir = repo.join(".forge").join("intents");
+  let yaml = format!(
+    "title: {id}\nbody: Body of {id}\nsource: analyze\nparent: {parent}\nstatus: {status}\n"
+  );
+  std::fs::write(intents_dir.join(format!("{id}.yaml")), yaml).unwrap();
+}
+
+#[test]
+fn 分解された親intentは子が終わるまで処理されない() {
+  let (_dir, repo) = setup_repo_with_intent("epic");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![json_response(
+    r#"{"outcome":"child_intents","child_intents":[{"title":"Part A","body":"Do A"}]}"#,
+  )]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Success);
+  assert_eq!(load_intent(&repo, "epic").status, IntentStatus::Approved);
+  assert_eq!(load_intent(&repo, "part-a").parent.as_deref(), Some("epic"));
+
+  // Child approved: only the child runs, the parent is not re-analyzed
+  add_child_intent(&repo, "part-a", "epic", "approved");
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].0, "part-a");
+  assert_eq!(mock.call_count(), 3);
+  assert_eq!(load_intent(&repo, "part-a").status, IntentStatus::Done);
+  assert_eq!(load_intent(&repo, "epic").status, IntentStatus::Done);
+}
+
+#[test]
+fn 親intentは子の結果を集約する() {
+  let (_dir, repo) = setup_repo_with_intent("all-done");
+  add_child_intent(&repo, "a1", "all-done", "done");
+  add_child_intent(&repo, "a2", "all-done", "rejected");
+  add_intent(&repo, "mixed", "approved");
+  add_child_intent(&repo, "m1", "mixed", "done");
+  add_child_intent(&repo, "m2", "mixed", "error");
+  add_intent(&repo, "waiting", "approved");
+  add_child_intent(&repo, "w1", "waiting", "done");
+  add_child_intent(&repo, "w2", "waiting", "proposed");
+
+  let mut settled = runner::settle_parents(&repo).unwrap();
+  settled.sort();
+
+  assert_eq!(settled, vec!["all-done", "mixed"]);
+  assert_eq!(load_intent(&repo, "all-done").status, IntentStatus::Done);
+  assert_eq!(load_intent(&repo, "mixed").status, IntentStatus::Blocked);
+  assert_eq!(load_intent(&repo, "waiting").status, IntentStatus::Approved);
+}
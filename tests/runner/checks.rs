@@ -0,0 +1,579 @@
+use pfl_forge::knowledge::history::Outcome;
+use pfl_forge::runner;
+
+use crate::helpers::*;
+
+// --- Format ---
+
+#[test]
+fn format_commandの変更を別コミットとして記録する() {
+  let (_dir, repo) = setup_repo_with_intent("format-test");
+  let mut intent = load_intent(&repo, "format-test");
+  for (key, value) in [("user.name", "test"), ("user.email", "test@test.com")] {
+    std::process::Command::new("git")
+      .args(["config", key, value])
+      .current_dir(&repo)
+      .output()
+      .unwrap();
+  }
+  let mut config = default_config();
+  config.format_command = Some("printf 'formatted\\n' > file.txt".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert!(result.step_results.iter().any(|s| s.step == "format"));
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("format-test");
+  let log = std::process::Command::new("git")
+    .args(["log", "--format=%s", "-1"])
+    .current_dir(&worktree_path)
+    .output()
+    .unwrap();
+  assert_eq!(
+    String::from_utf8_lossy(&log.stdout).trim(),
+    "style: apply format_command"
+  );
+}
+
+#[test]
+fn format_command失敗時もtaskは継続する() {
+  let (_dir, repo) = setup_repo_with_intent("format-fail");
+  let mut intent = load_intent(&repo, "format-fail");
+  let mut config = default_config();
+  config.format_command = Some("exit 1".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+}
+
+// --- Lint ---
+
+#[test]
+fn lint成功時はreviewへ進む() {
+  let (_dir, repo) = setup_repo_with_intent("lint-ok");
+  let mut intent = load_intent(&repo, "lint-ok");
+  let mut config = default_config();
+  config.lint_command = Some("true".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let steps: Vec<&str> = result
+    .step_results
+    .iter()
+    .map(|s| s.step.as_str())
+    .collect();
+  assert_eq!(
+    steps,
+    vec![
+      "analyze",
+      "implement",
+      "lint",
+      "rebase",
+      "review",
+      "reflect"
+    ]
+  );
+}
+
+#[test]
+fn lint失敗時は出力をフィードバックして一度だけ修正させる() {
+  let (_dir, repo) = setup_repo_with_intent("lint-fix");
+  let mut intent = load_intent(&repo, "lint-fix");
+  let mut config = default_config();
+  // Fails on the first run, passes afterwards
+  config.lint_command = Some(
+    "if [ -f lint-ran ]; then exit 0; fi; touch lint-ran; echo 'warning: unused variable'; exit 1"
+      .into(),
+  );
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Fixed lint"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let calls = mock.captured_calls();
+  assert!(calls[2].prompt.contains("## Lint Failure"));
+  assert!(calls[2].prompt.contains("warning: unused variable"));
+  let lint_steps = result
+    .step_results
+    .iter()
+    .filter(|s| s.step == "lint")
+    .count();
+  assert_eq!(lint_steps, 2);
+}
+
+/// Leaves the lint fix uncommitted, like a sandboxed agent.
+struct LintFixingClaude(MockClaude);
+
+impl pfl_forge::claude::runner::Claude for LintFixingClaude {
+  fn run_prompt(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &std::path::Path,
+    timeout: Option<std::time::Duration>,
+    session: &pfl_forge::claude::runner::SessionMode,
+  ) -> pfl_forge::error::Result<String> {
+    if prompt.contains("## Lint Failure") {
+      std::fs::write(cwd.join("lint-fixed.txt"), "fixed\n").unwrap();
+    }
+    self
+      .0
+      .run_prompt(prompt, system_prompt, model, cwd, timeout, session)
+  }
+}
+
+#[test]
+fn sandbox時はlint修正パスの変更もホストでコミットする() {
+  let (_dir, repo) = setup_repo_with_intent("lint-sandbox");
+  let mut intent = load_intent(&repo, "lint-sandbox");
+  for (key, value) in [("user.name", "test"), ("user.email", "test@test.com")] {
+    std::process::Command::new("git")
+      .args(["config", key, value])
+      .current_dir(&repo)
+      .output()
+      .unwrap();
+  }
+  let mut config = default_config();
+  config.claude.sandbox = Some(serde_yaml::from_str("image: forge-worker:latest").unwrap());
+  config.lint_command = Some("test -f lint-fixed.txt".into());
+
+  let mock = LintFixingClaude(MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Fixed lint"),
+    json_response(approved_review_json()),
+  ]));
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let show = std::process::Command::new("git")
+    .args(["show", "forge/lint-sandbox:lint-fixed.txt"])
+    .current_dir(&repo)
+    .output()
+    .unwrap();
+  assert!(show.status.success());
+}
+
+#[test]
+fn 修正後もlintが失敗すればtaskを失敗させる() {
+  let (_dir, repo) = setup_repo_with_intent("lint-fail");
+  let mut intent = load_intent(&repo, "lint-fail");
+  let mut config = default_config();
+  config.lint_command = Some("echo 'error: bad'; exit 1".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Tried"),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert!(result.failure_reason.unwrap().contains("lint failed"));
+  // analyze + implement + corrective implement, no review
+  assert_eq!(mock.call_count(), 3);
+}
+
+#[test]
+fn lintの全出力をforge_logsに保存し失敗理由から参照する() {
+  let (_dir, repo) = setup_repo_with_intent("lint-log");
+  let mut intent = load_intent(&repo, "lint-log");
+  let mut config = default_config();
+  // Longer than the prompt excerpt so only the log keeps every line
+  config.lint_command = Some("seq 1 3000; echo 'error: bad' >&2; exit 1".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Tried"),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  // Logs are kept per task and attempt: before and after the corrective pass
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .contains(".forge/logs/lint-log/lint-log/lint-2.log"));
+  for attempt in [1, 2] {
+    let path = format!(".forge/logs/lint-log/lint-log/lint-{attempt}.log");
+    let log = std::fs::read_to_string(repo.join(path)).unwrap();
+    assert!(log.contains("\n1500\n"));
+    assert!(log.ends_with("error: bad\n"));
+  }
+  let calls = mock.captured_calls();
+  assert!(calls[2].prompt.contains("bytes omitted"));
+  assert!(!calls[2].prompt.contains("\n1500\n"));
+}
+
+// --- Check Setup / Teardown ---
+
+#[test]
+fn lint前にsetupを実行し後にteardownを実行する() {
+  let (_dir, repo) = setup_repo_with_intent("check-setup");
+  let mut intent = load_intent(&repo, "check-setup");
+  let mut config = default_config();
+  config.check_setup = vec!["touch service-up".into()];
+  config.lint_command = Some("test -f service-up".into());
+  config.check_teardown = vec!["rm service-up && touch torn-down".into()];
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("check-setup");
+  assert!(!worktree_path.join("service-up").exists());
+  assert!(worktree_path.join("torn-down").exists());
+}
+
+#[test]
+fn setup失敗時はlintせずteardownしてtaskを失敗させる() {
+  let (_dir, repo) = setup_repo_with_intent("setup-fail");
+  let mut intent = load_intent(&repo, "setup-fail");
+  let mut config = default_config();
+  config.check_setup = vec!["echo 'compose down' >&2; exit 1".into()];
+  config.lint_command = Some("true".into());
+  config.check_teardown = vec!["touch torn-down".into()];
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json()), raw_response("Done")]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .contains("setup command failed"));
+  assert!(!result.step_results.iter().any(|s| s.step == "lint"));
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("setup-fail");
+  assert!(worktree_path.join("torn-down").exists());
+}
+
+// --- Coverage ---
+
+#[test]
+fn カバレッジが閾値未満ならテスト追加のため一度だけ修正させる() {
+  let (_dir, repo) = setup_repo_with_intent("coverage-fix");
+  let mut intent = load_intent(&repo, "coverage-fix");
+  let mut config = default_config();
+  // 50% on the first run, 90% afterwards
+  config.coverage_command = Some(
+    "if [ -f coverage-ran ]; then echo 'TOTAL 90.0%'; else touch coverage-ran; echo 'TOTAL 50.0%'; fi"
+      .into(),
+  );
+  config.min_coverage = 80.0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Added tests"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let calls = mock.captured_calls();
+  assert!(calls[2].prompt.contains("## Coverage Failure"));
+  assert!(calls[2].prompt.contains("50.0%"));
+  let coverage_steps = result
+    .step_results
+    .iter()
+    .filter(|s| s.step == "coverage")
+    .count();
+  assert_eq!(coverage_steps, 2);
+}
+
+#[test]
+fn カバレッジが閾値以上なら修正なしでreviewへ進む() {
+  let (_dir, repo) = setup_repo_with_intent("coverage-ok");
+  let mut intent = load_intent(&repo, "coverage-ok");
+  let mut config = default_config();
+  config.coverage_command = Some("echo 'TOTAL 85.3%'".into());
+  config.min_coverage = 80.0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 3); // analyze + implement + review
+}
+
+// --- Bench ---
+
+/// Prints 100ms in the base checkout and `head_ms` in the task worktree.
+fn bench_command(head_ms: u32) -> String {
+  format!("case \"$PWD\" in *.bench-base) echo 'time: 100ms';; *) echo 'time: {head_ms}ms';; esac")
+}
+
+#[test]
+fn ベンチマークが閾値を超えて悪化したらreviewせずに失敗する() {
+  let (_dir, repo) = setup_repo_with_intent("bench-regress");
+  let mut intent = load_intent(&repo, "bench-regress");
+  let mut config = default_config();
+  config.bench_command = Some(bench_command(150));
+  config.max_regression_pct = 10.0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .contains("bench regression 50.0%"));
+  assert_eq!(mock.call_count(), 2); // analyze + implement
+  let base_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("bench-regress.bench-base");
+  assert!(!base_path.exists());
+}
+
+#[test]
+fn ベンチマークが閾値内なら比較結果をreviewに渡す() {
+  let (_dir, repo) = setup_repo_with_intent("bench-ok");
+  let mut intent = load_intent(&repo, "bench-ok");
+  let mut config = default_config();
+  config.bench_command = Some(bench_command(105));
+  config.max_regression_pct = 10.0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert!(result.step_results.iter().any(|s| s.step == "bench"));
+  let calls = mock.captured_calls();
+  assert!(calls[2].prompt.contains("## Benchmark"));
+  assert!(calls[2]
+    .prompt
+    .contains("base 100.000, head 105.000 (+5.0%)"));
+}
+
+#[test]
+fn ベンチマークはheadとbaseの両方をcheck_setupで囲んで実行する() {
+  let (dir, repo) = setup_repo_with_intent("bench-setup");
+  let mut intent = load_intent(&repo, "bench-setup");
+  let runs = dir.path().join("setup-runs.txt");
+  let mut config = default_config();
+  config.bench_command = Some(bench_command(100));
+  config.check_setup = vec![format!("echo \"$PWD\" >> {}", runs.display())];
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let runs = std::fs::read_to_string(&runs).unwrap();
+  let dirs: Vec<&str> = runs.lines().collect();
+  assert_eq!(dirs.len(), 2, "{runs}");
+  assert!(dirs[0].ends_with("bench-setup"), "{runs}");
+  assert!(dirs[1].ends_with("bench-setup.bench-base"), "{runs}");
+}
+
+#[test]
+fn 前回の残ったbench_baseのworktreeを削除してから作り直す() {
+  let (_dir, repo) = setup_repo_with_intent("bench-stale");
+  let mut intent = load_intent(&repo, "bench-stale");
+  let mut config = default_config();
+  config.bench_command = Some(bench_command(100));
+  let base_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("bench-stale.bench-base");
+  std::fs::create_dir_all(&base_path).unwrap();
+  std::fs::write(base_path.join("leftover.txt"), "interrupted run\n").unwrap();
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert!(!base_path.exists());
+}
+
+// --- Task ごとの Check Override ---
+
+#[test]
+fn lint_commandsにあるtaskのlint_commandがrepoの設定より優先されsummaryに記録される() {
+  let (_dir, repo) = setup_repo_with_intent("check-override");
+  let mut intent = load_intent(&repo, "check-override");
+  let mut config = default_config();
+  config.lint_command = Some("exit 1".into());
+  config.lint_commands = vec!["true".into()];
+
+  let analysis = r#"{"complexity":"low","plan":"p","relevant_files":[],"implementation_steps":[],"checks":{"lint_command":"true"}}"#;
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  let summary = pfl_forge::knowledge::summary::load(&repo, "check-override").unwrap();
+  let checks = summary.tasks[0].checks.as_ref().unwrap();
+  assert_eq!(checks.lint_command.as_deref(), Some("true"));
+}
+
+#[test]
+fn lint_commandsにないtaskのlint_commandはポリシー違反としてtaskを失敗させる() {
+  let (_dir, repo) = setup_repo_with_intent("check-unlisted");
+  let mut intent = load_intent(&repo, "check-unlisted");
+  let mut config = default_config();
+  config.lint_command = Some("true".into());
+
+  let marker = repo.join("agent-command-ran");
+  let analysis = format!(
+    r#"{{"complexity":"low","plan":"p","relevant_files":[],"implementation_steps":[],"checks":{{"lint_command":"touch {}"}}}}"#,
+    marker.display()
+  );
+  let mock = MockClaude::with_sequence(vec![
+    json_response(&analysis),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .starts_with("policy violation: checks.lint_command"));
+  assert!(!marker.exists());
+}
+
+#[test]
+fn worktreeの外を指すcheck_dirはtaskを失敗させる() {
+  for dir in ["..", "/tmp"] {
+    let (_dir, repo) = setup_repo_with_intent("check-dir-escape");
+    let mut intent = load_intent(&repo, "check-dir-escape");
+    let mut config = default_config();
+    config.lint_command = Some("true".into());
+
+    let analysis = format!(
+      r#"{{"complexity":"low","plan":"p","relevant_files":[],"implementation_steps":[],"checks":{{"dir":"{dir}"}}}}"#
+    );
+    let mock = MockClaude::with_sequence(vec![json_response(&analysis), raw_response("Done")]);
+
+    let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+    assert_eq!(result.outcome, Outcome::Failed, "{dir}");
+    assert!(
+      result
+        .failure_reason
+        .unwrap()
+        .starts_with("policy violation: checks.dir"),
+      "{dir}"
+    );
+  }
+}
+
+#[test]
+fn 存在しないcheck_dirはtaskを失敗させる() {
+  let (_dir, repo) = setup_repo_with_intent("check-dir-missing");
+  let mut intent = load_intent(&repo, "check-dir-missing");
+  let mut config = default_config();
+  config.lint_command = Some("true".into());
+
+  let analysis = r#"{"complexity":"low","plan":"p","relevant_files":[],"implementation_steps":[],"checks":{"dir":"crates/missing"}}"#;
+  let mock = MockClaude::with_sequence(vec![json_response(analysis), raw_response("Done")]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .contains("check dir not found: crates/missing"));
+}
+
+#[test]
+fn check_dir指定時はサブディレクトリでlintを実行する() {
+  let (_dir, repo) = setup_repo_with_intent("check-dir");
+  let mut intent = load_intent(&repo, "check-dir");
+  let mut config = default_config();
+  // The worktree only has tracked files, so create the subdirectory during setup
+  config.worktree_setup = vec!["mkdir -p sub && touch sub/only-here".into()];
+  config.lint_command = Some("test -f only-here".into());
+
+  let analysis = r#"{"complexity":"low","plan":"p","relevant_files":[],"implementation_steps":[],"checks":{"dir":"sub"}}"#;
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 3); // no corrective pass
+}
@@ -85,6 +85,54 @@ impl Claude for MockClaude {
   }
 }
 
+/// Wraps [`MockClaude`] and, on a conflict-resolution prompt, resolves
+/// `file.txt` in the worktree and stages it like the agent would.
+pub struct ResolvingClaude(pub MockClaude);
+
+impl Claude for ResolvingClaude {
+  fn run_prompt(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    if prompt.contains("## Conflicting Hunks") {
+      std::fs::write(cwd.join("file.txt"), "main change\nbranch change\n").unwrap();
+      git(cwd, &["add", "file.txt"]);
+    }
+    self
+      .0
+      .run_prompt(prompt, system_prompt, model, cwd, timeout, session)
+  }
+}
+
+/// Wraps [`MockClaude`] and, when called in a worktree, leaves an
+/// uncommitted file there like a sandboxed agent that cannot commit.
+pub struct UncommittingClaude(pub MockClaude);
+
+impl Claude for UncommittingClaude {
+  fn run_prompt(
+    &self,
+    prompt: &str,
+    system_prompt: &str,
+    model: &str,
+    cwd: &Path,
+    timeout: Option<Duration>,
+    session: &SessionMode,
+  ) -> Result<String> {
+    // A linked worktree has a `.git` file instead of a directory
+    if cwd.join(".git").is_file() {
+      std::fs::write(cwd.join("sandboxed.txt"), "written in the container\n").unwrap();
+    }
+    self
+      .0
+      .run_prompt(prompt, system_prompt, model, cwd, timeout, session)
+  }
+}
+
 /// Wrap inner_json in Claude's `{"result": "..."}` envelope
 pub fn json_response(inner_json: &str) -> Result<String> {
   let escaped = inner_json.replace('\\', "\\\\").replace('"', "\\\"");
@@ -175,6 +223,41 @@ pub fn setup_repo_with_intent(intent_id: &str) -> (tempfile::TempDir, PathBuf) {
   (dir, repo_path)
 }
 
+/// Commit the given files on main and push them to origin.
+pub fn commit_and_push(repo_path: &Path, files: &[(&str, &str)]) {
+  for (path, content) in files {
+    let file = repo_path.join(path);
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(file, content).unwrap();
+  }
+  git(repo_path, &["add", "."]);
+  git(repo_path, &["commit", "-m", "add files"]);
+  git(repo_path, &["push", "origin", "main"]);
+}
+
+/// Add a local repo containing `lib.txt` as submodule `path`, then commit and push.
+/// Git refuses file:// submodule clones by default, so the file protocol is allowed
+/// for the whole test process (the runner's own `git submodule update` needs it too).
+pub fn add_submodule(repo_path: &Path, path: &str) {
+  std::env::set_var("GIT_CONFIG_COUNT", "1");
+  std::env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+  std::env::set_var("GIT_CONFIG_VALUE_0", "always");
+
+  let sub_path = repo_path.parent().unwrap().join(format!("{path}-origin"));
+  std::fs::create_dir_all(&sub_path).unwrap();
+  git(&sub_path, &["init", "-b", "main"]);
+  std::fs::write(sub_path.join("lib.txt"), "lib\n").unwrap();
+  git(&sub_path, &["add", "."]);
+  git(&sub_path, &["commit", "-m", "initial"]);
+
+  git(
+    repo_path,
+    &["submodule", "add", sub_path.to_str().unwrap(), path],
+  );
+  git(repo_path, &["commit", "-m", "add submodule"]);
+  git(repo_path, &["push", "origin", "main"]);
+}
+
 /// Set up a repo where a pre-existing branch conflicts with origin/main.
 /// Creates branch `forge/{intent_id}` with a conflicting commit on file.txt,
 /// then pushes a different change to file.txt on origin/main.
@@ -182,6 +265,9 @@ pub fn setup_repo_with_conflict(intent_id: &str) -> (tempfile::TempDir, PathBuf)
   let (dir, repo_path) = setup_repo_with_intent(intent_id);
 
   let branch = format!("forge/{intent_id}");
+  // Identity for commits replayed by `git rebase --continue` in worktrees
+  git(&repo_path, &["config", "user.name", "test"]);
+  git(&repo_path, &["config", "user.email", "test@test.com"]);
 
   // Create the branch with a conflicting commit
   git(&repo_path, &["checkout", "-b", &branch]);
@@ -211,9 +297,8 @@ pub fn setup_repo_with_audit_intent(intent_id: &str) -> (tempfile::TempDir, Path
   let knowledge_dir = repo_path.join(".forge").join("knowledge").join("history");
   std::fs::create_dir_all(&knowledge_dir).unwrap();
 
-  let yaml = format!(
-    "title: Audit codebase\nbody: Run audit\nsource: human\ntype: audit\nstatus: approved\n"
-  );
+  let yaml =
+    "title: Audit codebase\nbody: Run audit\nsource: human\ntype: audit\nstatus: approved\n";
   std::fs::write(intents_dir.join(format!("{intent_id}.yaml")), yaml).unwrap();
 
   (dir, repo_path)
@@ -248,9 +333,7 @@ pub fn setup_repo_with_skill_intent(intent_id: &str) -> (tempfile::TempDir, Path
     "intent_id: h1\ntitle: Feature A\nflow: [analyze, implement, review]\nstep_results:\n  - step: analyze\n    duration_secs: 10\noutcome: success\n",
   ).unwrap();
 
-  let yaml = format!(
-    "title: Extract skills\nbody: Run skill extraction\nsource: human\ntype: skill_extraction\nstatus: approved\n"
-  );
+  let yaml = "title: Extract skills\nbody: Run skill extraction\nsource: human\ntype: skill_extraction\nstatus: approved\n";
   std::fs::write(intents_dir.join(format!("{intent_id}.yaml")), yaml).unwrap();
 
   (dir, repo_path)
@@ -323,6 +406,7 @@ pub fn setup_worktree_with_tasks(repo_path: &Path, config: &Config, intent_id: &
     &config.worktree_dir,
     &branch,
     &config.base_branch,
+    &[],
   )
   .unwrap();
   pfl_forge::git::worktree::ensure_gitignore_forge(&worktree_path).unwrap();
@@ -339,8 +423,30 @@ pub fn setup_worktree_with_tasks(repo_path: &Path, config: &Config, intent_id: &
     implementation_steps: vec!["Step 1".to_string()],
     context: String::new(),
     depends_on: vec![],
+    checks: None,
+    implement_failures: 0,
+    rebase_conflicts: 0,
+    errors: vec![],
+    retries_exhausted: false,
   }];
   pfl_forge::task::write_all_tasks(repo_path, intent_id, &tasks).unwrap();
 
   worktree_path
 }
+
+/// Commit a file change on the worktree's branch, as an earlier implement run would have.
+pub fn commit_in_worktree(worktree_path: &Path, path: &str, content: &str) {
+  std::fs::write(worktree_path.join(path), content).unwrap();
+  git(worktree_path, &["add", path]);
+  let out = git(worktree_path, &["commit", "-m", "earlier implement"]);
+  assert!(out.status.success(), "commit failed: {out:?}");
+}
+
+/// Overwrite the status of every task persisted for `intent_id`.
+pub fn set_task_status(repo_path: &Path, intent_id: &str, status: pfl_forge::task::WorkStatus) {
+  let mut tasks = pfl_forge::task::read_all_tasks(repo_path, intent_id).unwrap();
+  for task in &mut tasks {
+    task.status = status.clone();
+  }
+  pfl_forge::task::write_all_tasks(repo_path, intent_id, &tasks).unwrap();
+}
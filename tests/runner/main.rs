@@ -16,6 +16,83 @@ fn skill_extraction種別はobserve_abstract_record() {
   assert_eq!(flow, vec![Step::Observe, Step::Abstract, Step::Record]);
 }
 
+#[test]
+fn flowsでカスタムステップを挿入しreviewを省略できる() {
+  use helpers::*;
+  use pfl_forge::config::CustomStep;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("custom-flow");
+  let mut intent = load_intent(&repo, "custom-flow");
+  let mut config = default_config();
+  config.steps.insert(
+    "docs".into(),
+    CustomStep {
+      prompt: "Update the docs.".into(),
+      model: None,
+    },
+  );
+  config.flows.insert(
+    "default".into(),
+    vec!["analyze".into(), "implement".into(), "docs".into()],
+  );
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    raw_response("Docs updated"),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(intent.status, IntentStatus::Done);
+  // analyze + implement + docs (no review)
+  assert_eq!(mock.call_count(), 3);
+  assert!(mock.captured_calls()[2].prompt.contains("`docs` step"));
+  assert_eq!(result.flow, vec!["analyze", "implement", "docs"]);
+  let steps: Vec<&str> = result
+    .step_results
+    .iter()
+    .map(|s| s.step.as_str())
+    .collect();
+  assert!(steps.contains(&"docs"), "steps: {:?}", steps);
+  assert!(!steps.contains(&"review"), "steps: {:?}", steps);
+}
+
+#[test]
+fn flowsの不正な定義はエラーになる() {
+  use pfl_forge::config::CustomStep;
+  use pfl_forge::runner::parse_flow;
+  use std::collections::BTreeMap;
+
+  let mut steps = BTreeMap::new();
+  steps.insert(
+    "docs".to_string(),
+    CustomStep {
+      prompt: "p".into(),
+      model: None,
+    },
+  );
+  let flow = |names: &[&str]| {
+    let names: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+    parse_flow(&names, &steps)
+  };
+
+  assert_eq!(
+    flow(&["analyze", "implement", "docs", "review"]).unwrap(),
+    vec![
+      Step::Analyze,
+      Step::Implement,
+      Step::Custom("docs".into()),
+      Step::Review
+    ]
+  );
+  assert!(flow(&["analyze", "implement", "lint"]).is_err());
+  assert!(flow(&["implement", "review"]).is_err());
+  assert!(flow(&["analyze", "implement", "review", "docs"]).is_err());
+}
+
 #[test]
 fn audit種別はaudit_reportフローを使う() {
   use helpers::*;
@@ -42,6 +119,42 @@ fn audit種別はaudit_reportフローを使う() {
     .collect();
   assert_eq!(steps, vec!["audit", "report"]);
   assert_eq!(mock.call_count(), 1);
+
+  let report = std::fs::read_to_string(pfl_forge::knowledge::audit_report::path(
+    &repo,
+    "audit-test",
+  ))
+  .unwrap();
+  assert!(report.starts_with("# Audit: Audit codebase"));
+  assert!(report.contains("Findings: 1"));
+  assert!(report.contains("## 1. Found unused import"));
+  assert!(report.contains("- file: `src/main.rs:3`"));
+}
+
+#[test]
+fn auditレポートを書き込めなければintentを失敗にする() {
+  use helpers::*;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::knowledge::history::Outcome;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_audit_intent("audit-test");
+  let mut intent = load_intent(&repo, "audit-test");
+  let config = default_config();
+  // reports をファイルにしてディレクトリを作れなくする
+  std::fs::write(repo.join(".forge").join("reports"), "").unwrap();
+
+  let mock = MockClaude::with_sequence(vec![json_response(audit_result_json())]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(result.outcome, Outcome::Failed);
+  assert_eq!(intent.status, IntentStatus::Error);
+  assert!(result
+    .failure_reason
+    .as_deref()
+    .unwrap()
+    .starts_with("audit report write failed"));
 }
 
 // --- Flow 調整ルール ---
@@ -226,10 +339,115 @@ fn cross_intent依存が完了済みならintentを処理する() {
   );
 }
 
+#[test]
+fn cross_intent依存が失敗していればblockedにして理由を記録する() {
+  use helpers::*;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::knowledge::history::{self, Outcome};
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("broken");
+  add_intent(&repo, "broken", "error");
+  add_intent(&repo, "dropped", "rejected");
+  add_intent_with_depends_on(&repo, "after-broken", "approved", &["broken"]);
+  add_intent_with_depends_on(&repo, "after-dropped", "approved", &["dropped"]);
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![]);
+  let mut results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+  results.sort_by(|a, b| a.0.cmp(&b.0));
+
+  assert_eq!(mock.call_count(), 0);
+  assert_eq!(results.len(), 2);
+  assert_eq!(results[0].0, "after-broken");
+  assert_eq!(results[0].1.outcome, Outcome::Failed);
+  assert_eq!(
+    results[0].1.failure_reason.as_deref(),
+    Some("dependency broken is error")
+  );
+  assert_eq!(
+    results[1].1.failure_reason.as_deref(),
+    Some("dependency dropped is rejected")
+  );
+  assert_eq!(
+    load_intent(&repo, "after-broken").status,
+    IntentStatus::Blocked
+  );
+  let entry = history::load(&repo, "after-dropped").unwrap();
+  assert_eq!(
+    entry.failure_reason.as_deref(),
+    Some("dependency dropped is rejected")
+  );
+}
+
+#[test]
+fn 依存先がblockedなら連鎖してblockedにし今回のrun_idで記録する() {
+  use helpers::*;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::knowledge::history;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("root");
+  add_intent(&repo, "root", "error");
+  // Listed out of dependency order, so one scan over the intents is not enough
+  add_intent_with_depends_on(&repo, "a-leaf", "approved", &["mid"]);
+  add_intent_with_depends_on(&repo, "mid", "approved", &["root"]);
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(mock.call_count(), 0);
+  assert_eq!(results.len(), 2);
+  let leaf = load_intent(&repo, "a-leaf");
+  assert_eq!(leaf.status, IntentStatus::Blocked);
+  let entry = history::load(&repo, "a-leaf").unwrap();
+  assert_eq!(
+    entry.failure_reason.as_deref(),
+    Some("dependency mid is blocked")
+  );
+  assert!(entry.run_id.is_some());
+  assert_eq!(entry.run_id, leaf.run_id);
+  let report = runner::build_run_report(&repo, &results);
+  assert_eq!(report.run_id, entry.run_id);
+}
+
+#[test]
+fn analyzeで検出した依存が失敗していればblockedにする() {
+  use helpers::*;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("needs-base");
+  add_intent(&repo, "base", "error");
+  let mut intent = load_intent(&repo, "needs-base");
+  let config = default_config();
+
+  let mock = MockClaude::with_sequence(vec![json_response(
+    r#"{"complexity":"low","plan":"p","relevant_files":["a.rs"],"implementation_steps":["s"],"context":"","depends_on_intents":["base"]}"#,
+  )]);
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+
+  assert_eq!(
+    result.failure_reason.as_deref(),
+    Some("dependency base is error")
+  );
+  assert_eq!(intent.status, IntentStatus::Blocked);
+  assert_eq!(mock.call_count(), 1);
+}
+
 // --- 基本実行フロー + 自動挿入ステップ ---
 
 mod basic_flow;
 
+// --- 検証コマンド ---
+
+mod checks;
+
+// --- 記録した fixture の再生 ---
+
+mod replay;
+
 // --- Worktree Setup ---
 
 #[test]
@@ -264,3 +482,421 @@ fn implement前にworktreeセットアップコマンドを実行する() {
     "worktree setup command should have created marker file"
   );
 }
+
+#[test]
+fn worktreeセットアップが失敗し続けたらsetup_failureにして次のrunで再試行する() {
+  use helpers::*;
+  use pfl_forge::intent::registry::IntentStatus;
+  use pfl_forge::knowledge::history::{self, Outcome};
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("setup-fail");
+  let mut config = default_config();
+  config.worktree_setup = vec!["echo offline >&2; exit 1".to_string()];
+  config.worktree_setup_retries = 1;
+  config.worktree_setup_retry_secs = 0;
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Failed);
+  assert_eq!(mock.call_count(), 1);
+  assert_eq!(
+    load_intent(&repo, "setup-fail").status,
+    IntentStatus::SetupFailure
+  );
+  let reason = history::load(&repo, "setup-fail")
+    .unwrap()
+    .failure_reason
+    .unwrap();
+  assert!(reason.contains("offline"), "{reason}");
+  assert!(!repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("setup-fail")
+    .exists());
+
+  // Next run: setup succeeds and the saved tasks resume without Analyze
+  config.worktree_setup = vec!["true".to_string()];
+  let mock = MockClaude::with_sequence(vec![
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+  let results = runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 2);
+  assert_eq!(load_intent(&repo, "setup-fail").status, IntentStatus::Done);
+}
+
+#[test]
+fn worktreeセットアップの失敗はclaudeを呼ばずにリトライする() {
+  use helpers::*;
+  use pfl_forge::knowledge::history::Outcome;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("setup-retry");
+  let mut intent = load_intent(&repo, "setup-retry");
+  let mut config = default_config();
+  config.worktree_setup =
+    vec!["if [ -f tried ]; then rm tried; else touch tried; exit 1; fi".to_string()];
+  config.worktree_setup_retries = 1;
+  config.worktree_setup_retry_secs = 0;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(result.outcome, Outcome::Success);
+  assert_eq!(mock.call_count(), 3);
+}
+
+#[test]
+fn worktreeセットアップの成果物をキャッシュして次のworktreeで再利用する() {
+  use helpers::*;
+  use pfl_forge::config::SetupCache;
+  use pfl_forge::runner;
+
+  let (dir, repo) = setup_repo_with_intent("cache-first");
+  add_intent(&repo, "cache-second", "approved");
+  let counter = dir.path().join("setup-runs");
+  let mut config = default_config();
+  config.parallel_workers = 1;
+  config.worktree_setup = vec![format!(
+    "echo run >> {} && mkdir -p node_modules && touch node_modules/pkg",
+    counter.display()
+  )];
+  config.worktree_setup_cache = Some(SetupCache {
+    paths: vec!["node_modules".into()],
+    key_files: vec!["package-lock.json".into()],
+  });
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done 1"),
+    json_response(approved_review_json()),
+    json_response(analysis_json()),
+    raw_response("Done 2"),
+    json_response(approved_review_json()),
+  ]);
+  runner::run_intents(&config, &mock, &repo, false).unwrap();
+
+  // Setup ran once; the second worktree was restored from the cache
+  let runs = std::fs::read_to_string(&counter).unwrap();
+  assert_eq!(runs.lines().count(), 1);
+  assert!(repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("cache-second")
+    .join("node_modules")
+    .join("pkg")
+    .exists());
+}
+
+#[test]
+fn init_submodules指定時はworktreeでサブモジュールを初期化する() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("submodule-test");
+  add_submodule(&repo, "vendor/lib");
+  let mut intent = load_intent(&repo, "submodule-test");
+  let mut config = default_config();
+  config.init_submodules = true;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("submodule-test");
+  assert!(worktree_path.join("vendor/lib/lib.txt").exists());
+}
+
+#[test]
+fn サブモジュール初期化の失敗は環境エラーとして返す() {
+  use helpers::*;
+  use pfl_forge::error::ErrorCategory;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("submodule-fail");
+  add_submodule(&repo, "vendor/lib");
+  std::fs::remove_dir_all(repo.parent().unwrap().join("vendor/lib-origin")).unwrap();
+  let mut intent = load_intent(&repo, "submodule-fail");
+  let mut config = default_config();
+  config.init_submodules = true;
+
+  let mock = MockClaude::with_sequence(vec![json_response(analysis_json())]);
+
+  let err = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap_err();
+  assert!(err.to_string().contains("submodule update failed"));
+  assert_eq!(err.category(), ErrorCategory::Environment);
+  assert_eq!(mock.call_count(), 1); // analyze only, implement never started
+}
+
+#[test]
+fn gitattributesのfilter_lfsでlfs利用を検出する() {
+  let dir = tempfile::tempdir().unwrap();
+  assert!(!pfl_forge::git::worktree::uses_lfs(dir.path()));
+
+  std::fs::write(dir.path().join(".gitattributes"), "*.txt text\n").unwrap();
+  assert!(!pfl_forge::git::worktree::uses_lfs(dir.path()));
+
+  std::fs::write(
+    dir.path().join(".gitattributes"),
+    "*.psd filter=lfs diff=lfs merge=lfs -text\n",
+  )
+  .unwrap();
+  assert!(pfl_forge::git::worktree::uses_lfs(dir.path()));
+}
+
+#[test]
+fn git_lfs有効でもlfsを使わないリポジトリではlfsを実行しない() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("lfs-unused");
+  let mut intent = load_intent(&repo, "lfs-unused");
+  let mut config = default_config();
+  config.git_lfs = true;
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+}
+
+#[test]
+fn 別のidentityで作られたコミットを設定したauthorに書き換える() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("identity-test");
+  let mut intent = load_intent(&repo, "identity-test");
+  let mut config = default_config();
+  config.git_author = Some("Forge Bot".into());
+  config.git_email = Some("forge@example.com".into());
+  // Stands in for a worker that commits with its own identity
+  config.format_command = Some(
+    "printf 'changed\\n' > file.txt && git -c user.name=Wrong -c user.email=wrong@example.com commit -qam 'worker commit'"
+      .into(),
+  );
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("identity-test");
+  let log = std::process::Command::new("git")
+    .args(["log", "--format=%an <%ae> %s", "origin/main..HEAD"])
+    .current_dir(&worktree_path)
+    .output()
+    .unwrap();
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert!(log.contains("Forge Bot <forge@example.com> worker commit"));
+  assert!(log
+    .lines()
+    .all(|l| l.starts_with("Forge Bot <forge@example.com>")));
+  // The identity is passed per command; the shared git config is left alone
+  let main_config = std::fs::read_to_string(repo.join(".git").join("config")).unwrap();
+  assert!(!main_config.contains("Forge Bot"), "{main_config}");
+  assert!(!main_config.contains("worktreeConfig"), "{main_config}");
+}
+
+// --- Commit Template ---
+
+#[test]
+fn commit_templateに合わせてworkerのコミット件名を書き換える() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("template-test");
+  let mut intent = load_intent(&repo, "template-test");
+  let mut config = default_config();
+  config.git_author = Some("Forge Bot".into());
+  config.git_email = Some("forge@example.com".into());
+  config.commit_template = Some("[{intent_id}] {type}: {subject}".into());
+  // Stands in for a worker commit with its own message style
+  config.format_command =
+    Some("printf 'changed\\n' > file.txt && git commit -qam 'fix: tweak file' -m 'details'".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("template-test");
+  let log = std::process::Command::new("git")
+    .args(["log", "--format=%s|%b", "origin/main..HEAD"])
+    .current_dir(&worktree_path)
+    .output()
+    .unwrap();
+  let log = String::from_utf8_lossy(&log.stdout);
+  // The worker's own type is kept; the intent type only fills in a missing one
+  assert!(
+    log.contains("[template-test] fix: tweak file|details"),
+    "{log}"
+  );
+  assert!(log
+    .lines()
+    .filter(|l| !l.is_empty())
+    .all(|l| l.starts_with("[template-test] ")));
+}
+
+#[test]
+fn commit_pattern違反の件名は修正パスで書き換える() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("commit-lint");
+  let mut intent = load_intent(&repo, "commit-lint");
+  let mut config = default_config();
+  config.git_author = Some("Forge Bot".into());
+  config.git_email = Some("forge@example.com".into());
+  config.commit_pattern = Some("^(feat|fix|style|chore)(\\(.+\\))?: .+".into());
+  // Stands in for a worker commit that commitlint would reject
+  config.format_command = Some("printf 'changed\\n' > file.txt && git commit -qam 'wip'".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(r#"{"subjects":[{"original":"wip","fixed":"fix: tweak file"}]}"#),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+  assert!(mock.captured_calls()[2].prompt.contains("- wip"));
+
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("commit-lint");
+  let log = std::process::Command::new("git")
+    .args(["log", "--format=%s", "origin/main..HEAD"])
+    .current_dir(&worktree_path)
+    .output()
+    .unwrap();
+  let log = String::from_utf8_lossy(&log.stdout);
+  assert!(log.lines().any(|l| l == "fix: tweak file"));
+  assert!(!log.lines().any(|l| l == "wip"));
+}
+
+#[test]
+fn 修正後もcommit_patternに合わなければtaskを失敗させる() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("commit-lint-fail");
+  let mut intent = load_intent(&repo, "commit-lint-fail");
+  let mut config = default_config();
+  config.git_author = Some("Forge Bot".into());
+  config.git_email = Some("forge@example.com".into());
+  config.commit_pattern = Some("^(feat|fix|style)(\\(.+\\))?: .+".into());
+  config.format_command = Some("printf 'changed\\n' > file.txt && git commit -qam 'wip'".into());
+
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(r#"{"subjects":[{"original":"wip","fixed":"still wip"}]}"#),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Failed
+  );
+  assert!(result
+    .failure_reason
+    .unwrap()
+    .contains("do not match commit_pattern"));
+}
+
+// --- Sparse Checkout ---
+
+#[test]
+fn sparse_pathsとrelevant_filesのディレクトリだけをチェックアウトする() {
+  use helpers::*;
+  use pfl_forge::runner;
+
+  let (_dir, repo) = setup_repo_with_intent("sparse-test");
+  commit_and_push(
+    &repo,
+    &[
+      ("pkg/a/a.txt", "a\n"),
+      ("pkg/b/b.txt", "b\n"),
+      ("pkg/c/c.txt", "c\n"),
+    ],
+  );
+  let mut intent = load_intent(&repo, "sparse-test");
+  let mut config = default_config();
+  config.sparse_paths = vec!["pkg/a".into()];
+  config.sparse_from_relevant_files = true;
+
+  let analysis =
+    r#"{"complexity":"low","plan":"p","relevant_files":["pkg/b/b.txt"],"implementation_steps":[]}"#;
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis),
+    raw_response("Done"),
+    json_response(approved_review_json()),
+  ]);
+
+  let result = runner::process_intent(&mut intent, &config, &mock, &repo).unwrap();
+  assert_eq!(
+    result.outcome,
+    pfl_forge::knowledge::history::Outcome::Success
+  );
+
+  let worktree_path = repo
+    .join(&config.worktree_dir)
+    .join("forge")
+    .join("sparse-test");
+  assert!(worktree_path.join("file.txt").exists());
+  assert!(worktree_path.join("pkg/a/a.txt").exists());
+  assert!(worktree_path.join("pkg/b/b.txt").exists());
+  assert!(!worktree_path.join("pkg/c/c.txt").exists());
+}
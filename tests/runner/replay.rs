@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use pfl_forge::claude::fixture::{self, RecordingRunner, ReplayRunner};
+use pfl_forge::intent::registry::IntentStatus;
+use pfl_forge::knowledge::history::Outcome;
+use pfl_forge::runner;
+
+use crate::helpers::*;
+
+fn fixture_path(name: &str) -> std::path::PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("tests")
+    .join("runner")
+    .join("fixtures")
+    .join(name)
+}
+
+#[test]
+fn 記録したfixtureを再生してintentを完了する() {
+  let (_dir, repo) = setup_repo_with_intent("replayed");
+  let config = default_config();
+  let replay = ReplayRunner::load(&fixture_path("success.yaml")).unwrap();
+
+  let results = runner::run_intents(&config, &replay, &repo, false).unwrap();
+
+  assert_eq!(results[0].1.outcome, Outcome::Success);
+  assert_eq!(replay.remaining(), 0);
+}
+
+#[test]
+fn 記録した呼び出しを同じ順序とステージで再生する() {
+  let (_dir, repo) = setup_repo_with_intent("recorded");
+  let config = default_config();
+  let out = tempfile::tempdir().unwrap();
+  let path = out.path().join("run.yaml");
+  let mock = MockClaude::with_sequence(vec![
+    json_response(analysis_json()),
+    raw_response("Done"),
+    json_response(rejected_review_json()),
+    raw_response("Fixed"),
+    json_response(approved_review_json()),
+  ]);
+
+  let recorder = RecordingRunner::new(mock, &path);
+  let recorded = runner::run_intents(&config, &recorder, &repo, false).unwrap();
+  let exchanges = fixture::load(&path).unwrap();
+  let stages: Vec<_> = exchanges.iter().map(|e| e.stage.as_deref()).collect();
+  assert_eq!(
+    stages,
+    vec![
+      Some("analyze"),
+      Some("implement"),
+      Some("review"),
+      Some("implement"),
+      Some("review"),
+    ]
+  );
+
+  let (_dir2, repo2) = setup_repo_with_intent("recorded");
+  let replay = ReplayRunner::new(exchanges);
+  let replayed = runner::run_intents(&config, &replay, &repo2, false).unwrap();
+  assert_eq!(replayed[0].1.outcome, recorded[0].1.outcome);
+  assert_eq!(replay.remaining(), 0);
+}
+
+#[test]
+fn 記録と異なるステージの呼び出しはエラーにする() {
+  let (_dir, repo) = setup_repo_with_intent("mismatch");
+  let config = default_config();
+  let replay = ReplayRunner::new(vec![fixture::Exchange {
+    stage: Some("review".into()),
+    model: "sonnet".into(),
+    prompt: String::new(),
+    response: Some(r#"{"result": "{}"}"#.into()),
+    error: None,
+  }]);
+
+  let _ = runner::run_intents(&config, &replay, &repo, false);
+
+  assert_eq!(
+    load_intent(&repo, "mismatch").status,
+    IntentStatus::Approved
+  );
+  assert_eq!(replay.remaining(), 1);
+}